@@ -3,6 +3,11 @@ import { address, type Address } from '@solana/kit';
 
 export const DAC_PROGRAM_ID = address('BaY9vp3RXAQugzAoBojkBEZs9fJKS4dNManN7vwDZSFh');
 
+/**
+ * Fixed-point scale of the session share price (mirrors SHARE_PRECISION in the program)
+ */
+export const SHARE_PRECISION = 1_000_000_000n;
+
 /**
  * Convert bigint to 8-byte little-endian buffer (browser-compatible)
  */
@@ -128,3 +133,21 @@ export async function deriveNodeInfoAddress(
   );
   return address(nodeInfoAddress.toBase58());
 }
+
+/** Node treasury PDA: seeds ["node_treasury", node_info, registration_epoch] */
+export async function deriveNodeTreasuryAddress(
+  programAddress: Address,
+  nodeInfo: Address,
+  registrationEpoch: bigint
+): Promise<Address> {
+  const epochBuffer = bigintToBufferLE(registrationEpoch);
+  const [nodeTreasuryAddress] = PublicKey.findProgramAddressSync(
+    [
+      new TextEncoder().encode('node_treasury'),
+      new PublicKey(nodeInfo).toBuffer(),
+      epochBuffer,
+    ],
+    new PublicKey(programAddress)
+  );
+  return address(nodeTreasuryAddress.toBase58());
+}
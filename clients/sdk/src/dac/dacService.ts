@@ -7,7 +7,7 @@ import type {
   NodeInfo,
   Task,
 } from '../generated/dac/accounts/index.js';
-import type { NodeStatus, AgentStatus, TaskStatus, SessionStatus, NodeType, CodeMeasurementArgs } from '../generated/dac/types/index.js';
+import type { NodeStatus, AgentStatus, TaskStatus, SessionStatus, NodeType, CodeMeasurementArgs, SemanticVersionArgs } from '../generated/dac/types/index.js';
 
 import type { WaitMode } from './dacMonitoring.js';
 import type { TransactionSigner } from './utils.js';
@@ -55,6 +55,8 @@ export interface IQueryService {
 /**
  * Transaction parameter types
  */
+export type TaskTypeParam = { type: 'Completion'; modelId: bigint } | { type: 'Custom'; moduleId: bigint } | { type: 'HumanInLoop' };
+
 export type InitializeNetworkParams = {
   authority: TransactionSigner;
   cidConfig: string;
@@ -62,6 +64,10 @@ export type InitializeNetworkParams = {
   allocateTasks: bigint;
   approvedCodeMeasurements: CodeMeasurementArgs[];
   requiredValidations: number;
+  /** Folded into the genesis hash with the authority for a reproducible genesis. */
+  genesisNonce?: Array<number> | null;
+  /** Task type of pre-allocated and newly created tasks, defaults to Completion(0). */
+  defaultTaskType?: TaskTypeParam | null;
 };
 
 export type RegisterNodeParams = {
@@ -83,6 +89,8 @@ export type CreateSessionParams = {
   networkConfig: Address;
   isOwned: boolean;
   isConfidential: boolean;
+  allowedOwner?: Address | null;
+  fundingThreshold?: bigint | null;
 };
 
 export type SetSessionParams = {
@@ -97,7 +105,14 @@ export type SetSessionParams = {
   /** Compute node pubkey to assign to the task. */
   computeNode: Address;
   /** Task type (e.g. Completion(model_id), Custom(module_id), HumanInLoop). */
-  taskType: { type: 'Completion'; modelId: bigint } | { type: 'Custom'; moduleId: bigint } | { type: 'HumanInLoop' };
+  taskType: TaskTypeParam;
+  maxOutputSize?: bigint | number | null;
+  /** Basis points of each third-party contribution minted to the owner (max 1000). */
+  ownerFeeBps?: number | null;
+  /** Advisory compute estimate advertised on the task, not enforced. */
+  estimatedComputeUnits?: bigint | null;
+  /** Nodes allowed to claim the session's tasks (max 8). Empty or omitted allows any approved node. */
+  allowedComputeNodes?: Address[] | null;
 };
 
 export type ContributeToSessionParams = {
@@ -105,6 +120,8 @@ export type ContributeToSessionParams = {
   networkConfig: Address;
   sessionSlotId: bigint;
   depositAmount: bigint;
+  /** Fail instead of minting fewer shares than this. */
+  minShares?: bigint | null;
 };
 
 export type WithdrawFromSessionParams = {
@@ -126,6 +143,30 @@ export type UpdateNetworkConfigParams = {
   authority: TransactionSigner;
   cidConfig?: string | null;
   newCodeMeasurement?: CodeMeasurementArgs | null;
+  confidentialPublicFallback?: boolean | null;
+  rejectDuplicateResults?: boolean | null;
+  agentRequiredValidations?: number | null;
+  validationStallSlots?: bigint | number | null;
+  /** The default address clears the mint, back to SOL payments. */
+  paymentMint?: Address | null;
+  allowedCidPrefixes?: string[] | null;
+  abandonmentSlots?: bigint | number | null;
+  rejectionReward?: bigint | number | null;
+  agentCreationAllowlist?: Address[] | null;
+  maxSessionsPerAgent?: bigint | number | null;
+  validationWindowSlots?: bigint | number | null;
+  minSlotsBetweenClaims?: bigint | number | null;
+  validatorShareBps?: number | null;
+  protocolShareBps?: number | null;
+  agentValidatorsMustBeConfidential?: boolean | null;
+  settlementHoldSlots?: bigint | number | null;
+  minSlotsBetweenIterations?: bigint | number | null;
+  debugValidationLogs?: boolean | null;
+  maxVaultBalance?: bigint | number | null;
+  processingWindowSlots?: bigint | number | null;
+  minMeasurementVersion?: SemanticVersionArgs | null;
+  paused?: boolean | null;
+  maxOwnershipBps?: number | null;
 };
 
 export type ActivateNodeParams = {
@@ -28,6 +28,8 @@ export interface TaskClaimedEvent {
   taskSlotId: bigint;
   computeNode: Address;
   maxTaskCost: bigint;
+  maxCallCount: bigint;
+  taskIndex: bigint;
 }
 
 export interface TaskResultSubmittedEvent {
@@ -35,6 +37,8 @@ export interface TaskResultSubmittedEvent {
   taskSlotId: bigint;
   inputCid: string;
   outputCid: string;
+  callCount: bigint;
+  checkpointCid?: string;
 }
 
 export interface TaskValidationSubmittedEvent {
@@ -56,6 +60,9 @@ export interface SessionSetEvent {
   specificationCid: string;
   maxIterations: bigint;
   initialDeposit: bigint;
+  computeNode: Address;
+  totalShares: bigint;
+  taskIndexStart: bigint;
 }
 
 export interface ContributionMadeEvent {
@@ -63,6 +70,7 @@ export interface ContributionMadeEvent {
   contributor: Address;
   depositAmount: bigint;
   sharesMinted: bigint;
+  ownerFeeShares: bigint;
   totalShares: bigint;
 }
 
@@ -547,6 +555,17 @@ function optSlot(v: unknown): bigint | undefined {
   }
 }
 
+function optStr(v: unknown): string | undefined {
+  if (v == null) return undefined;
+  try {
+    if (!isSome(v as Parameters<typeof isSome>[0])) return undefined;
+    const raw = unwrapOption(v as Parameters<typeof unwrapOption>[0]);
+    return typeof raw === 'string' ? raw : undefined;
+  } catch {
+    return undefined;
+  }
+}
+
 function str(d: Decoded, snake: string, camel: string): string {
   return String((d[snake] ?? d[camel]) ?? '');
 }
@@ -593,6 +612,9 @@ function parseAnchorEvent(
             specificationCid: str(d, 'specification_cid', 'specificationCid'),
             maxIterations: bn(d, 'max_iterations', 'maxIterations'),
             initialDeposit: bn(d, 'initial_deposit', 'initialDeposit'),
+            computeNode: (d.compute_node ?? d.computeNode) as Address,
+            totalShares: bn(d, 'total_shares', 'totalShares'),
+            taskIndexStart: bn(d, 'task_index_start', 'taskIndexStart'),
           },
         };
       }
@@ -611,6 +633,7 @@ function parseAnchorEvent(
             contributor: d.contributor as Address,
             depositAmount: bn(d, 'deposit_amount', 'depositAmount'),
             sharesMinted: bn(d, 'shares_minted', 'sharesMinted'),
+            ownerFeeShares: bn(d, 'owner_fee_shares', 'ownerFeeShares'),
             totalShares: bn(d, 'total_shares', 'totalShares'),
           },
         };
@@ -684,6 +707,8 @@ function parseAnchorEvent(
             taskSlotId: tid,
             computeNode: (d.compute_node ?? d.computeNode) as Address,
             maxTaskCost: bn(d, 'max_task_cost', 'maxTaskCost'),
+            maxCallCount: bn(d, 'max_call_count', 'maxCallCount'),
+            taskIndex: bn(d, 'task_index', 'taskIndex'),
           },
         };
       }
@@ -704,6 +729,8 @@ function parseAnchorEvent(
             taskSlotId: tid,
             inputCid: str(d, 'input_cid', 'inputCid'),
             outputCid: str(d, 'output_cid', 'outputCid'),
+            callCount: bn(d, 'call_count', 'callCount'),
+            checkpointCid: optStr(d.checkpoint_cid ?? d.checkpointCid),
           },
         };
       }
@@ -1,4 +1,4 @@
-import { address, type Address } from '@solana/kit';
+import { address, unwrapOption, type Address } from '@solana/kit';
 import type { Instruction, TransactionMessage, TransactionMessageWithFeePayer, TransactionMessageWithBlockhashLifetime } from '@solana/kit';
 import { AccountRole } from '@solana/kit';
 import { buildTransaction, type TransactionSigner } from './utils.js';
@@ -10,6 +10,8 @@ import {
   deriveSessionAddress,
   deriveTaskAddress,
   deriveNodeInfoAddress,
+  deriveNodeTreasuryAddress,
+  deriveContributionAddress,
 } from './dacPdas.js';
 import {
   getActivateNodeInstruction,
@@ -46,6 +48,7 @@ import type {
   CreateAgentParams,
   CreateSessionParams,
   SetSessionParams,
+  TaskTypeParam,
   ContributeToSessionParams,
   WithdrawFromSessionParams,
   SubmitTaskParams,
@@ -53,6 +56,14 @@ import type {
   ActivateNodeParams,
 } from './dacService.js';
 
+function toTaskTypeArgs(taskType: TaskTypeParam) {
+  return (taskType.type === 'Completion')
+    ? { completion: taskType.modelId }
+    : (taskType.type === 'Custom')
+      ? { custom: taskType.moduleId }
+      : { humanInLoop: true };
+}
+
 /**
  * Create transaction service factory
  */
@@ -90,6 +101,8 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
         allocateTasks: params.allocateTasks,
         approvedCodeMeasurements: params.approvedCodeMeasurements,
         requiredValidations: params.requiredValidations,
+        genesisNonce: params.genesisNonce ?? null,
+        defaultTaskType: params.defaultTaskType ? toTaskTypeArgs(params.defaultTaskType) : null,
       } as InitializeNetworkInput;
 
       const instruction = getInitializeNetworkInstruction(input, {
@@ -115,9 +128,24 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
     },
 
     async registerNode(params: RegisterNodeParams): Promise<{ transactionMessage: TransactionMessageType; nodeInfoAddress: Address; nodeTreasuryAddress: Address }> {
+      const networkConfigAccount = await fetchMaybeNetworkConfig(rpc, params.networkConfig);
+      if (!networkConfigAccount.exists || !networkConfigAccount.data) {
+        throw new Error('Network config not found');
+      }
+
+      // Each registration gets a fresh treasury seeded by the network registration count
+      const nodeInfoAddress = await deriveNodeInfoAddress(programAddress, params.nodePubkey);
+      const nodeTreasuryAddress = await deriveNodeTreasuryAddress(
+        programAddress,
+        nodeInfoAddress,
+        networkConfigAccount.data.nodeRegistrationCount
+      );
+
       const input: RegisterNodeAsyncInput = {
         owner: address(params.owner.address) as any,
         networkConfig: params.networkConfig,
+        nodeInfo: nodeInfoAddress,
+        nodeTreasury: nodeTreasuryAddress,
         nodePubkey: params.nodePubkey,
         nodeType: params.nodeType,
       };
@@ -126,9 +154,6 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
         programAddress,
       });
 
-      const nodeInfoAddress = address(instruction.accounts[2].address);
-      const nodeTreasuryAddress = address(instruction.accounts[3].address);
-
       const { transactionMessage } = await buildTransactionWithRpc(params.owner, [instruction]);
 
       return { transactionMessage, nodeInfoAddress, nodeTreasuryAddress };
@@ -182,6 +207,8 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
         task: taskAddress,
         isOwned: params.isOwned,
         isConfidential: params.isConfidential,
+        allowedOwner: params.allowedOwner ?? null,
+        fundingThreshold: params.fundingThreshold ?? null,
       };
 
       const instruction = getCreateSessionInstruction(input, { programAddress });
@@ -203,22 +230,24 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
         throw new Error(`Task account does not exist for slotId ${params.taskSlotId.toString()}.`);
       }
 
-      const taskType = (params.taskType.type === 'Completion')
-        ? { completion: params.taskType.modelId }
-        : (params.taskType.type === 'Custom')
-          ? { custom: params.taskType.moduleId }
-          : { humanInLoop: true };
+      const taskType = toTaskTypeArgs(params.taskType);
       const input: SetSessionAsyncInput = {
         owner: address(params.owner.address) as any,
         session: sessionAddress,
         task: taskAddress,
         agent: agentAddress,
         networkConfig: params.networkConfig,
-        specificationCid: params.specificationCid,
-        maxIterations: params.maxIterations,
-        initialDeposit: params.initialDeposit,
-        computeNode: params.computeNode,
-        taskType: taskType as any,
+        args: {
+          specificationCid: params.specificationCid,
+          maxIterations: params.maxIterations,
+          initialDeposit: params.initialDeposit,
+          computeNode: params.computeNode,
+          taskType: taskType as any,
+          maxOutputSize: params.maxOutputSize ?? null,
+          ownerFeeBps: params.ownerFeeBps ?? null,
+          estimatedComputeUnits: params.estimatedComputeUnits ?? null,
+          allowedComputeNodes: params.allowedComputeNodes ?? null,
+        },
       };
 
       const instruction = await getSetSessionInstructionAsync(input, { programAddress });
@@ -228,11 +257,22 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
 
     async contributeToSession(params: ContributeToSessionParams): Promise<TransactionMessageType> {
       const sessionAddress = await deriveSessionAddress(programAddress, params.networkConfig, params.sessionSlotId);
+      const sessionAccount = await fetchMaybeSession(rpc, sessionAddress);
+      if (!sessionAccount.exists) {
+        throw new Error(`Session account does not exist for slotId ${params.sessionSlotId.toString()}.`);
+      }
+      // The owner fee is credited to the owner's contribution, skipped on the owner's own deposits
+      const { owner, ownerFeeBps } = sessionAccount.data;
+      const ownerContribution = (unwrapOption(ownerFeeBps) ?? 0) > 0 && owner !== params.contributor.address
+        ? await deriveContributionAddress(programAddress, sessionAddress, owner)
+        : undefined;
       const input: ContributeToSessionAsyncInput = {
         contributor: address(params.contributor.address) as any,
         session: sessionAddress,
+        ownerContribution,
         networkConfig: params.networkConfig,
         depositAmount: params.depositAmount,
+        minShares: params.minShares ?? null,
       };
       const instruction = await getContributeToSessionInstructionAsync(input, { programAddress });
       const { transactionMessage } = await buildTransactionWithRpc(params.contributor, [instruction]);
@@ -278,8 +318,33 @@ export function createTransactionService(deps: DacServiceDeps): ITransactionServ
       const input: UpdateNetworkConfigAsyncInput = {
         authority: address(params.authority.address) as any,
         networkConfig: networkConfigAddress,
-        cidConfig: params.cidConfig ?? null,
-        newCodeMeasurement: params.newCodeMeasurement ?? null,
+        args: {
+          cidConfig: params.cidConfig ?? null,
+          newCodeMeasurement: params.newCodeMeasurement ?? null,
+          confidentialPublicFallback: params.confidentialPublicFallback ?? null,
+          rejectDuplicateResults: params.rejectDuplicateResults ?? null,
+          agentRequiredValidations: params.agentRequiredValidations ?? null,
+          validationStallSlots: params.validationStallSlots ?? null,
+          paymentMint: params.paymentMint ?? null,
+          allowedCidPrefixes: params.allowedCidPrefixes ?? null,
+          abandonmentSlots: params.abandonmentSlots ?? null,
+          rejectionReward: params.rejectionReward ?? null,
+          agentCreationAllowlist: params.agentCreationAllowlist ?? null,
+          maxSessionsPerAgent: params.maxSessionsPerAgent ?? null,
+          validationWindowSlots: params.validationWindowSlots ?? null,
+          minSlotsBetweenClaims: params.minSlotsBetweenClaims ?? null,
+          validatorShareBps: params.validatorShareBps ?? null,
+          protocolShareBps: params.protocolShareBps ?? null,
+          agentValidatorsMustBeConfidential: params.agentValidatorsMustBeConfidential ?? null,
+          settlementHoldSlots: params.settlementHoldSlots ?? null,
+          minSlotsBetweenIterations: params.minSlotsBetweenIterations ?? null,
+          debugValidationLogs: params.debugValidationLogs ?? null,
+          maxVaultBalance: params.maxVaultBalance ?? null,
+          processingWindowSlots: params.processingWindowSlots ?? null,
+          minMeasurementVersion: params.minMeasurementVersion ?? null,
+          paused: params.paused ?? null,
+          maxOwnershipBps: params.maxOwnershipBps ?? null,
+        },
       };
 
       const instruction = await getUpdateNetworkConfigInstructionAsync(input, {
@@ -16,7 +16,7 @@ export type {
 } from './generated/dac/accounts/index.js';
 export type { CodeMeasurement, CodeMeasurementArgs } from './generated/dac/types/codeMeasurement.js';
 export { NodeType, NodeStatus, AgentStatus, TaskStatus, SessionStatus } from './generated/dac/types/index.js';
-export { DAC_PROGRAM_ID, deriveNetworkConfigAddress, deriveAgentAddress, deriveSessionAddress, deriveTaskAddress, deriveContributionAddress, deriveSessionVaultAddress } from './dac/dacPdas.js';
+export { DAC_PROGRAM_ID, SHARE_PRECISION, deriveNetworkConfigAddress, deriveAgentAddress, deriveSessionAddress, deriveTaskAddress, deriveContributionAddress, deriveSessionVaultAddress } from './dac/dacPdas.js';
 export { getNodeStatusName, getAgentStatusName, getTaskStatusName, getSessionStatusName } from './dac/statusUtils.js';
 
 // Instruction builders (for useWalletUiSignAndSend)
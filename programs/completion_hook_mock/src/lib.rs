@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+declare_id!("3qYy7zFCpHJojtBQdNToBWq4w3Ljhx1twvtvWZyXpjzx");
+
+/// Stand-in for an integrator program, it only logs the completion it is called with
+#[program]
+pub mod completion_hook_mock {
+    use super::*;
+
+    pub fn on_session_completed(
+        ctx: Context<OnSessionCompleted>,
+        session_slot_id: u64,
+        owner: Pubkey,
+        final_iteration: u64,
+        total_paid: u64,
+    ) -> Result<()> {
+        msg!(
+            "Completion hook: session {} ({}) owned by {} completed at iteration {}, {} paid",
+            session_slot_id,
+            ctx.accounts.session.key(),
+            owner,
+            final_iteration,
+            total_paid
+        );
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct OnSessionCompleted<'info> {
+    /// CHECK: the completed dac session, only logged
+    pub session: UncheckedAccount<'info>,
+}
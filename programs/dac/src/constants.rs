@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+/// Fixed-point scale of the session share price (price = vault value per share * SHARE_PRECISION)
+#[constant]
+pub const SHARE_PRECISION: u64 = 1_000_000_000;
+
+/// Denominator of basis point fees
+#[constant]
+pub const BASIS_POINTS: u64 = 10_000;
+
+/// Upper bound on the session owner fee taken from third-party contributions (10%)
+#[constant]
+pub const MAX_OWNER_FEE_BPS: u16 = 1_000;
+
+/// Slots a session action proposal stays open for votes (~1 day)
+#[constant]
+pub const SESSION_PROPOSAL_SLOTS: u64 = 216_000;
+
+/// Longest `specification_cid` a session stores, matching its account space
+#[constant]
+pub const MAX_SPECIFICATION_CID_LEN: usize = 128;
+
+/// Instruction discriminator a session's completion hook program is invoked with, the Anchor
+/// discriminator of `on_session_completed`
+pub const COMPLETION_HOOK_DISCRIMINATOR: [u8; 8] = [243, 170, 241, 120, 41, 16, 157, 4];
@@ -66,4 +66,142 @@ pub enum ErrorCode {
     InvalidSession,
     #[msg("Invalid session status")]
     InvalidSessionStatus,
+    #[msg("Result is identical to the previously validated result")]
+    DuplicateResult,
+    #[msg("Owner shares are locked until the first iteration completes")]
+    WithdrawalLocked,
+    #[msg("Task validation has not stalled yet")]
+    ValidationNotStalled,
+    #[msg("Token accounts are required when the network uses a payment mint")]
+    MissingTokenAccount,
+    #[msg("Invalid token account")]
+    InvalidTokenAccount,
+    #[msg("Payment mint can only be changed before any session is created")]
+    PaymentMintLocked,
+    #[msg("Attested output size exceeds the session maximum")]
+    OutputTooLarge,
+    #[msg("Session has not been abandoned long enough to sweep")]
+    SessionNotAbandoned,
+    #[msg("Account is not the node owner")]
+    InvalidNodeOwner,
+    #[msg("Input CID does not match the input chained by the previous step")]
+    InputCidMismatch,
+    #[msg("Validator list is full")]
+    TooManyValidators,
+    #[msg("Too many agent creators in the allow-list")]
+    TooManyAgentCreators,
+    #[msg("Creator is not allowed to create agents")]
+    AgentCreatorNotAllowed,
+    #[msg("Max task cost exceeds the session ceiling")]
+    TaskCostTooHigh,
+    #[msg("Agent has reached its maximum number of active sessions")]
+    SessionLimitReached,
+    #[msg("Ed25519 instruction must carry exactly one signature")]
+    BadEd25519SignatureCount,
+    #[msg("Owner fee exceeds the maximum allowed")]
+    OwnerFeeTooHigh,
+    #[msg("Owner contribution account is required to collect the owner fee")]
+    OwnerContributionRequired,
+    #[msg("Token denominated sessions can't be merged")]
+    TokenSessionMergeUnsupported,
+    #[msg("Merge must migrate every contribution of the source session")]
+    IncompleteSessionMerge,
+    #[msg("Completed session still has outstanding shares")]
+    SessionHasOutstandingShares,
+    #[msg("Task index is behind the end of the previous session")]
+    TaskIndexRegression,
+    #[msg("Validation deadline for this result has passed")]
+    ValidationDeadlinePassed,
+    #[msg("Node claimed a task too recently")]
+    ClaimRateLimited,
+    #[msg("Session is paused")]
+    SessionPaused,
+    #[msg("Session already has an open proposal")]
+    SessionProposalActive,
+    #[msg("Proposal is executed or expired")]
+    ProposalClosed,
+    #[msg("Contributor holds no shares to vote with")]
+    NoVotingShares,
+    #[msg("Contributor already voted on this proposal")]
+    AlreadyVoted,
+    #[msg("Proposal has reached its maximum number of voters")]
+    TooManyProposalVoters,
+    #[msg("Session still has tasks in flight")]
+    SessionTasksInFlight,
+    #[msg("Validator and protocol payment shares exceed 100%")]
+    InvalidPaymentSplit,
+    #[msg("Validator and protocol payment shares are not supported for token payments")]
+    TokenPaymentSplitUnsupported,
+    #[msg("Pending task result was not cleared")]
+    StalePendingResult,
+    #[msg("Crowdfunded sessions are not supported for token payments")]
+    TokenCrowdfundingUnsupported,
+    #[msg("Session has not reached its funding threshold")]
+    FundingThresholdNotReached,
+    #[msg("Crowdfunded session is set from its contributions, without a deposit or owner fee")]
+    CrowdfundedSessionDeposit,
+    #[msg("Settlement holds are not supported for token payments")]
+    TokenSettlementHoldUnsupported,
+    #[msg("Settlement is still within its hold period")]
+    SettlementHeld,
+    #[msg("Settlement hold period has passed")]
+    SettlementHoldExpired,
+    #[msg("Task slot is already allocated")]
+    TaskSlotInUse,
+    #[msg("Session iteration started too soon after the previous one")]
+    IterationRateLimited,
+    #[msg("Required validations must be between 1 and the validators a task can hold")]
+    InvalidRequiredValidations,
+    #[msg("Task result reports more calls than the claim allowed")]
+    CallCountExceeded,
+    #[msg("Task cost must be greater than zero")]
+    TaskCostTooLow,
+    #[msg("Model is not allowed on this network")]
+    NotAllowedModel,
+    #[msg("Contribution would mint fewer shares than the minimum requested")]
+    SlippageExceeded,
+    #[msg("Network is paused")]
+    NetworkPaused,
+    #[msg("Compute node cannot validate its own task")]
+    SelfValidationForbidden,
+    #[msg("Approved node pool is full")]
+    TooManyNodes,
+    #[msg("Payment exceeds the max task cost locked at claim")]
+    PaymentExceedsPolicy,
+    #[msg("Contribution would exceed the network's ownership cap")]
+    OwnershipCapExceeded,
+    #[msg("Compute node is not on the session's allowed list")]
+    ComputeNodeNotAllowed,
+    #[msg("Too many allowed compute nodes for a session")]
+    TooManyAllowedComputeNodes,
+    #[msg("Pending task result changed after it was submitted for validation")]
+    PendingResultChanged,
+    #[msg("Compute node is at its concurrent task capacity")]
+    NodeAtCapacity,
+    #[msg("Task account passed more than once")]
+    DuplicateTaskAccount,
+    #[msg("Session is governed by its contributors")]
+    SessionGovernedByContributors,
+    #[msg("Validation deadline has not passed yet")]
+    ValidationWindowOpen,
+    #[msg("Batched validator did not sign the transaction")]
+    MissingValidatorSignature,
+    #[msg("Completion hook program cannot be the dac program")]
+    InvalidCompletionHook,
+    #[msg("Session completion hook program account is missing")]
+    CompletionHookMissing,
+    #[msg("Contribution would push the session vault past the network's cap")]
+    VaultCapExceeded,
+    #[msg("Session owner has closed it to new contributions")]
+    ContributionsClosed,
+    #[msg("Node still holds claimed tasks")]
+    NodeTasksInFlight,
+    #[msg("Shares backing a vote on an open proposal can't be withdrawn")]
+    VotedSharesLocked,
+    #[msg("Account is already in the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Compute node may still submit the task it claimed")]
+    ProcessingWindowOpen,
+    #[msg("Code measurement version is below the network's minimum")]
+    CodeMeasurementOutdated,
 }
@@ -1,11 +1,23 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{NodeStatus, NodeType, SessionAction, TASK_TYPE_COUNT};
+use crate::utils::SemanticVersion;
+
 #[event]
 pub struct TaskClaimed {
     pub session_slot_id: u64,
     pub task_slot_id: u64,
     pub compute_node: Pubkey,
     pub max_task_cost: u64,
+    pub max_call_count: u64,
+    pub task_index: u64,
+}
+
+#[event]
+pub struct ValidatorsAssigned {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub validators: Vec<Pubkey>,
 }
 
 #[event]
@@ -14,6 +26,8 @@ pub struct TaskResultSubmitted {
     pub task_slot_id: u64,
     pub input_cid: String,
     pub output_cid: String,
+    pub call_count: u64,
+    pub checkpoint_cid: Option<String>, // checkpoint the execution resumed from, if any
 }
 
 #[event]
@@ -27,6 +41,39 @@ pub struct TaskValidationSubmitted {
     pub current_iteration: u64,
     pub vault_balance: u64,
     pub locked_for_tasks: u64,
+    pub total_paid_this_session: u64,
+    pub iterations_remaining: Option<u64>, // None when the session has no iteration limit
+}
+
+#[event]
+pub struct TaskForceResolved {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub approved: bool,
+    pub approvals: u32,
+    pub rejections: u32,
+    pub payment_amount: u64,
+}
+
+#[event]
+pub struct SessionLocksReconciled {
+    pub session_slot_id: u64,
+    pub previous_locked_for_tasks: u64,
+    pub locked_for_tasks: u64,
+}
+
+#[event]
+pub struct SessionLimitsUpdated {
+    pub session_slot_id: u64,
+    pub previous_max_task_cost_ceiling: Option<u64>,
+    pub max_task_cost_ceiling: Option<u64>,
+}
+
+#[event]
+pub struct TaskInputSeeded {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub input_cid: String,
 }
 
 #[event]
@@ -37,6 +84,9 @@ pub struct SessionSet {
     pub specification_cid: String,
     pub max_iterations: u64,
     pub initial_deposit: u64,
+    pub compute_node: Pubkey,
+    pub total_shares: u64,
+    pub task_index_start: u64,
 }
 
 #[event]
@@ -45,6 +95,7 @@ pub struct ContributionMade {
     pub contributor: Pubkey,
     pub deposit_amount: u64,
     pub shares_minted: u64,
+    pub owner_fee_shares: u64,
     pub total_shares: u64,
 }
 
@@ -55,6 +106,30 @@ pub struct SessionCompleted {
     pub vault_balance: u64,
 }
 
+#[event]
+pub struct AbandonedSessionSwept {
+    pub session_slot_id: u64,
+    pub amount: u64,
+    pub last_activity_slot: u64,
+}
+
+#[event]
+pub struct NodeRewardsWithdrawn {
+    pub owner: Pubkey,
+    pub node_count: u32,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SessionsMerged {
+    pub source_session_slot_id: u64,
+    pub destination_session_slot_id: u64,
+    pub amount: u64,
+    pub migrated_shares: u64,
+    pub minted_shares: u64,
+    pub total_shares: u64,
+}
+
 #[event]
 pub struct NodeValidated {
     pub node: Pubkey,
@@ -77,3 +152,248 @@ pub struct AgentCreated {
     pub owner: Pubkey,
     pub agent_config_cid: String,
 }
+
+#[event]
+pub struct NetworkStats {
+    pub agent_count: u64,
+    pub session_count: u64,
+    pub task_count: u64,
+    pub approved_public_nodes: u32,
+    pub approved_confidential_nodes: u32,
+    pub required_validations: u32,
+}
+
+#[event]
+pub struct ApprovedStatus {
+    pub node: Pubkey,
+    pub public_approved: bool,
+    pub confidential_approved: bool,
+}
+
+#[event]
+pub struct ValidationTally {
+    pub task_slot_id: u64,
+    pub task_index: u64,
+    pub approved: u32,
+    pub rejected: u32,
+    pub pending: u32,
+    pub required_validations: u32,
+    pub approval_threshold_reached: bool,
+    pub rejection_threshold_reached: bool,
+}
+
+#[event]
+pub struct CodeMeasurementListed {
+    pub measurement: [u8; 32],
+    pub version: SemanticVersion,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum PoolMembershipChange {
+    Added,
+    Removed,
+}
+
+#[event]
+pub struct NodePoolMembershipChanged {
+    pub node: Pubkey,
+    pub node_type: NodeType,
+    pub change: PoolMembershipChange,
+}
+
+#[event]
+pub struct SessionActionProposed {
+    pub session_slot_id: u64,
+    pub proposer: Pubkey,
+    pub action: SessionAction,
+    pub shares: u64,
+}
+
+#[event]
+pub struct SessionActionVoted {
+    pub session_slot_id: u64,
+    pub voter: Pubkey,
+    pub shares: u64,
+    pub votes_shares: u64,
+    pub total_shares: u64,
+}
+
+#[event]
+pub struct SessionActionExecuted {
+    pub session_slot_id: u64,
+    pub action: SessionAction,
+    pub votes_shares: u64,
+    pub total_shares: u64,
+}
+
+#[event]
+pub struct TaskSettlementHeld {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub task_index: u64,
+    pub node: Pubkey,
+    pub amount: u64,
+    pub release_slot: u64,
+}
+
+#[event]
+pub struct TaskSettlementReleased {
+    pub task: Pubkey,
+    pub task_index: u64,
+    pub node: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TaskSettlementDisputed {
+    pub session_slot_id: u64,
+    pub task: Pubkey,
+    pub task_index: u64,
+    pub node: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AgentMemoryUpdated {
+    pub agent_slot_id: u64,
+    pub session_slot_id: u64,
+    pub task_index: u64,
+    pub memory_cid: String,
+}
+
+#[event]
+pub struct NetworkConfigMigrated {
+    pub previous_space: u64,
+    pub space: u64,
+}
+
+#[event]
+pub struct TasksExtended {
+    pub first_task_slot_id: u64,
+    pub count: u64,
+    pub task_count: u64,
+}
+
+#[event]
+pub struct NodeSummary {
+    pub node: Pubkey,
+    pub node_type: NodeType,
+    pub status: NodeStatus,
+    pub total_tasks_completed: u64,
+    pub total_earned: u64,
+    pub earned_by_type: [u64; TASK_TYPE_COUNT], // by TaskType::variant_index
+    pub approved: bool, // in the approved pool for its node type
+}
+
+#[event]
+pub struct GenesisVerified {
+    pub genesis_hash: [u8; 32],
+    pub computed_hash: [u8; 32], // re-derived from the stored authority and genesis nonce
+    pub genesis_nonce: Option<[u8; 32]>,
+    pub matches: bool,
+}
+
+#[event]
+pub struct TaskCheckpointed {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub task_index: u64,
+    pub compute_node: Pubkey,
+    pub checkpoint_cid: String,
+}
+
+#[event]
+pub struct TaskInvalidated {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub compute_node: Pubkey,
+    pub released_amount: u64,
+    pub locked_for_tasks: u64,
+}
+
+#[event]
+pub struct NodeCapacitySet {
+    pub node: Pubkey,
+    pub max_concurrent_tasks: u32,
+    pub current_tasks: u32,
+}
+
+#[event]
+pub struct SessionExposure {
+    pub session_slot_id: u64,
+    pub locked_for_tasks: u64,
+    pub tasks_locked: u64,
+    pub task_count: u32,
+    pub balanced: bool,
+}
+
+#[event]
+pub struct TaskAssignmentReceipt {
+    pub network_config: Pubkey,
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub task_index: u64,
+    pub compute_node: Pubkey,
+    pub validators: Vec<Pubkey>,
+    pub max_task_cost: u64,
+    pub claim_slot: u64,
+}
+
+#[event]
+pub struct SessionCancelled {
+    pub session_slot_id: u64,
+    pub owner: Pubkey,
+    pub total_shares: u64,
+}
+
+#[event]
+pub struct CancelledTaskReclaimed {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub compute_node: Pubkey,
+    pub released_amount: u64,
+    pub locked_for_tasks: u64,
+}
+
+#[event]
+pub struct BatchValidationSubmitted {
+    pub session_slot_id: u64,
+    pub task_slot_id: u64,
+    pub validators: Vec<Pubkey>,
+}
+
+#[event]
+pub struct CompletionHookSet {
+    pub session_slot_id: u64,
+    pub previous_completion_hook_program: Option<Pubkey>,
+    pub completion_hook_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct NodeTreasuryBalance {
+    pub node: Pubkey,
+    pub node_treasury: Pubkey,
+    pub balance: u64,
+    pub claimable: u64,
+}
+
+#[event]
+pub struct SessionCreated {
+    pub session_slot_id: u64,
+    pub owner: Pubkey, // Pubkey::default() for an unowned session
+    pub payer: Pubkey,
+    pub sponsored: bool, // payer and owner differ
+}
+
+#[event]
+pub struct SessionContributionsToggled {
+    pub session_slot_id: u64,
+    pub accepting_contributions: bool,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub previous_space: u64,
+    pub space: u64,
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
+use crate::events::{NodePoolMembershipChanged, PoolMembershipChange};
 use crate::state::{NetworkConfig, NodeInfo, NodeStatus, NodeType};
 
 #[derive(Accounts)]
@@ -26,6 +27,15 @@ pub struct ActivateNode<'info> {
 
 impl<'info> ActivateNode<'info> {
     pub fn activate_node(&mut self) -> Result<()> {
+        // Already activated, e.g. a client retrying after an ambiguous timeout
+        if self.node_info.status == NodeStatus::Active
+            && self
+                .network_config
+                .is_node_approved(self.node_info.node_type, &self.node_info.node_pubkey)
+        {
+            return Ok(());
+        }
+
         require!(
             self.node_info.status == NodeStatus::AwaitingValidation,
             ErrorCode::InvalidNodeStatus
@@ -49,15 +59,21 @@ impl<'info> ActivateNode<'info> {
 
         self.node_info.status = NodeStatus::Active;
 
-        match self.node_info.node_type {
-            NodeType::Public => {
-                self.network_config
-                    .add_public_node(self.node_info.node_pubkey)?;
-            }
-            NodeType::Confidential => {
-                self.network_config
-                    .add_confidential_node(self.node_info.node_pubkey)?;
-            }
+        let added = match self.node_info.node_type {
+            NodeType::Public => self
+                .network_config
+                .add_public_node(self.node_info.node_pubkey)?,
+            NodeType::Confidential => self
+                .network_config
+                .add_confidential_node(self.node_info.node_pubkey)?,
+        };
+
+        if added {
+            emit!(NodePoolMembershipChanged {
+                node: self.node_info.node_pubkey,
+                node_type: self.node_info.node_type,
+                change: PoolMembershipChange::Added,
+            });
         }
 
         Ok(())
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TaskInvalidated;
+use crate::state::{NetworkConfig, NodeInfo, NodeStatus, Session, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct AuthorityInvalidateTask<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+}
+
+impl<'info> AuthorityInvalidateTask<'info> {
+    /// Drop the in-flight execution of a node the authority has since rejected, unlocking its
+    /// session funds and putting the task back up for a new claim
+    pub fn authority_invalidate_task(&mut self) -> Result<()> {
+        require!(
+            matches!(
+                self.task.status,
+                TaskStatus::Processing | TaskStatus::AwaitingValidation
+            ),
+            ErrorCode::InvalidTaskStatus
+        );
+        require!(
+            self.session.task == self.task.key()
+                && self.task.session_slot_id == Some(self.session.session_slot_id),
+            ErrorCode::InvalidSession
+        );
+        require!(
+            self.task.compute_node == Some(self.node_info.node_pubkey),
+            ErrorCode::InvalidComputeNodePubkey
+        );
+        require!(
+            self.node_info.status == NodeStatus::Rejected,
+            ErrorCode::InvalidNodeStatus
+        );
+
+        let released_amount = self.task.max_task_cost;
+        self.session.release_task_lock(released_amount)?;
+        self.node_info.finish_task()?;
+
+        self.task.discard_pending_result();
+        self.task.checkpoint_cid = None;
+        self.task.validations.clear();
+        self.task.validation_deadline_slot = 0;
+        self.task.processing_deadline_slot = 0;
+        self.task.status = TaskStatus::Ready;
+        self.session.record_activity()?;
+
+        emit!(TaskInvalidated {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            compute_node: self.node_info.node_pubkey,
+            released_amount,
+            locked_for_tasks: self.session.locked_for_tasks,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCode;
+use crate::events::NodeRewardsWithdrawn;
+use crate::state::NodeInfo;
+
+#[derive(Accounts)]
+pub struct BatchWithdrawNodeRewards<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BatchWithdrawNodeRewards<'info> {
+    /// Sweep the excess over rent from (node_info, node_treasury) pairs in remaining accounts,
+    /// all of which must belong to the signing owner
+    pub fn batch_withdraw_node_rewards(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 2 == 0,
+            ErrorCode::MissingAccount
+        );
+
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let mut total_amount: u64 = 0;
+
+        for pair in remaining_accounts.chunks(2) {
+            let (node_info_account, treasury_account) = (&pair[0], &pair[1]);
+
+            let node_info: Account<NodeInfo> = Account::try_from(node_info_account)?;
+            let (node_info_pda, _) = Pubkey::find_program_address(
+                &[b"node_info", node_info.node_pubkey.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(node_info_pda, node_info.key(), ErrorCode::InvalidPDAAccount);
+            require_keys_eq!(
+                node_info.owner,
+                self.owner.key(),
+                ErrorCode::InvalidNodeOwner
+            );
+            require_keys_eq!(
+                node_info.node_treasury,
+                treasury_account.key(),
+                ErrorCode::InvalidPDAAccount
+            );
+
+            let amount = treasury_account
+                .lamports()
+                .saturating_sub(rent_exempt_minimum);
+            if amount == 0 {
+                continue;
+            }
+
+            let node_info_key = node_info.key();
+            let registration_epoch_bytes = node_info.registration_epoch.to_le_bytes();
+            let (_, treasury_bump) = Pubkey::find_program_address(
+                &[
+                    b"node_treasury",
+                    node_info_key.as_ref(),
+                    registration_epoch_bytes.as_ref(),
+                ],
+                &crate::ID,
+            );
+            let treasury_seeds = &[
+                b"node_treasury",
+                node_info_key.as_ref(),
+                registration_epoch_bytes.as_ref(),
+                &[treasury_bump],
+            ];
+            let treasury_signer = &[&treasury_seeds[..]];
+
+            let cpi_accounts = system_program::Transfer {
+                from: treasury_account.clone(),
+                to: self.owner.to_account_info(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                cpi_accounts,
+                treasury_signer,
+            );
+            system_program::transfer(cpi_context, amount)?;
+
+            total_amount = total_amount
+                .checked_add(amount)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        emit!(NodeRewardsWithdrawn {
+            owner: self.owner.key(),
+            node_count: (remaining_accounts.len() / 2) as u32,
+            amount: total_amount,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SessionCancelled;
+use crate::state::{Agent, NetworkConfig, Session, SessionStatus};
+
+#[derive(Accounts)]
+pub struct CancelSession<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> CancelSession<'info> {
+    /// Owned sessions answer to their owner, who stops them directly. Unowned sessions are
+    /// governed by their contributors and only a `SessionAction::Cancel` vote stops them.
+    /// An in-flight task keeps its lock until it settles or is reclaimed.
+    pub fn cancel_session(&mut self) -> Result<()> {
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            !self.session.is_governed(),
+            ErrorCode::SessionGovernedByContributors
+        );
+        require_keys_eq!(
+            self.session.owner,
+            self.owner.key(),
+            ErrorCode::InvalidSessionOwner
+        );
+
+        self.session.status = SessionStatus::Cancelled;
+        self.session.record_activity()?;
+        self.agent.close_session()?;
+
+        emit!(SessionCancelled {
+            session_slot_id: self.session.session_slot_id,
+            owner: self.owner.key(),
+            total_shares: self.session.total_shares,
+        });
+
+        Ok(())
+    }
+}
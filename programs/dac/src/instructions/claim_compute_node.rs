@@ -28,6 +28,7 @@ impl<'info> ClaimPublicNode<'info> {
             self.node_info.status == NodeStatus::PendingClaim,
             ErrorCode::InvalidNodeStatus
         );
+        self.network_config.validate_cid(&node_info_cid)?;
 
         self.node_info.node_info_cid = Some(node_info_cid);
         self.node_info.status = NodeStatus::AwaitingValidation;
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
+use crate::events::{NodePoolMembershipChanged, PoolMembershipChange};
 use crate::state::{NetworkConfig, NodeInfo, NodeStatus, NodeType};
 
 #[derive(Accounts)]
@@ -42,13 +43,26 @@ impl<'info> ClaimConfidentialNode<'info> {
                 .is_measurement_approved(&code_measurement),
             ErrorCode::CodeMeasurementNotApproved
         );
+        require!(
+            self.network_config
+                .is_measurement_recent_enough(&code_measurement),
+            ErrorCode::CodeMeasurementOutdated
+        );
 
         self.node_info.code_measurement = Some(code_measurement);
         self.node_info.tee_signing_pubkey = Some(tee_signing_pubkey);
         self.node_info.status = NodeStatus::Active;
 
-        self.network_config
-            .add_confidential_node(self.node_info.node_pubkey)?;
+        if self
+            .network_config
+            .add_confidential_node(self.node_info.node_pubkey)?
+        {
+            emit!(NodePoolMembershipChanged {
+                node: self.node_info.node_pubkey,
+                node_type: NodeType::Confidential,
+                change: PoolMembershipChange::Added,
+            });
+        }
 
         Ok(())
     }
@@ -1,16 +1,26 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
 
 use crate::errors::ErrorCode;
-use crate::events::TaskClaimed;
+use crate::events::{TaskAssignmentReceipt, TaskClaimed, ValidatorsAssigned};
 use crate::state::{
-    NetworkConfig, Session, SessionStatus, Task, TaskStatus, ValidationStatus, Validator,
+    Agent, NetworkConfig, NodeInfo, Session, SessionStatus, Task, TaskStatus, ValidationStatus,
+    Validator,
 };
+use crate::utils::verify_session_token_vault;
 
 #[derive(Accounts)]
 pub struct ClaimTask<'info> {
     #[account(mut)]
     pub compute_node: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"node_info", compute_node.key().as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+
     #[account(
         mut,
         seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
@@ -25,6 +35,13 @@ pub struct ClaimTask<'info> {
     )]
     pub session: Account<'info, Session>,
 
+    #[account(
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
     #[account(
         mut,
         seeds = [b"session_vault", session.key().as_ref()],
@@ -37,23 +54,61 @@ pub struct ClaimTask<'info> {
         bump = network_config.bump,
     )]
     pub network_config: Account<'info, NetworkConfig>,
+
+    /// Only used when the network has a payment mint
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
 }
 
 impl<'info> ClaimTask<'info> {
     pub fn claim_task(&mut self, max_task_cost: u64, max_call_count: u64) -> Result<()> {
+        let clock = Clock::get()?;
+        // A result nobody validated in time can be claimed again
+        let reclaim = self.task.status == TaskStatus::AwaitingValidation
+            && self.task.validation_expired(clock.slot);
         require!(
-            self.task.status == TaskStatus::Pending,
+            self.task.status == TaskStatus::Pending || reclaim,
             ErrorCode::InvalidTaskStatus
         );
         require!(
             self.session.status == SessionStatus::Active,
             ErrorCode::InvalidSessionStatus
         );
+        require!(!self.network_config.paused, ErrorCode::NetworkPaused);
+        require!(!self.session.paused, ErrorCode::SessionPaused);
+        require!(max_task_cost > 0, ErrorCode::TaskCostTooLow);
+        require!(
+            self.session.task == self.task.key()
+                && self.task.session_slot_id == Some(self.session.session_slot_id),
+            ErrorCode::InvalidSession
+        );
         require!(
             self.task.compute_node == Some(self.compute_node.key()),
             ErrorCode::InvalidComputeNodePubkey
         );
+        require!(
+            self.session.allowed_compute_nodes.is_empty()
+                || self
+                    .session
+                    .allowed_compute_nodes
+                    .contains(&self.compute_node.key()),
+            ErrorCode::ComputeNodeNotAllowed
+        );
+        require!(
+            self.node_info.node_type == self.session.required_node_type(),
+            ErrorCode::InvalidNodeType
+        );
         require!(self.session.total_shares > 0, ErrorCode::Overflow);
+        if let Some(ceiling) = self.session.max_task_cost_ceiling {
+            require!(max_task_cost <= ceiling, ErrorCode::TaskCostTooHigh);
+        }
+        self.session
+            .check_iteration_gap(clock.slot, self.network_config.min_slots_between_iterations)?;
+        self.node_info
+            .record_claim(clock.slot, self.network_config.min_slots_between_claims)?;
+        // A reclaim continues the execution the node already holds
+        if !reclaim {
+            self.node_info.start_task()?;
+        }
 
         let pool = if self.session.is_confidential {
             &self.network_config.approved_confidential_nodes
@@ -61,36 +116,78 @@ impl<'info> ClaimTask<'info> {
             &self.network_config.approved_public_nodes
         };
         let compute_pubkey = self.compute_node.key();
-        let candidates: Vec<Pubkey> = pool
+        let mut candidates: Vec<Pubkey> = pool
             .iter()
             .copied()
             .filter(|p| *p != compute_pubkey)
             .collect();
         let required = self.network_config.required_validations;
+
+        // Fall back to public validators when confidential ones can't reach quorum
+        if self.session.is_confidential
+            && self.network_config.confidential_public_fallback
+            && candidates.len() < required as usize
+        {
+            candidates.extend(
+                self.network_config
+                    .approved_public_nodes
+                    .iter()
+                    .copied()
+                    .filter(|p| *p != compute_pubkey),
+            );
+        }
         require!(
             candidates.len() >= required as usize,
             ErrorCode::NotEnoughValidators
         );
 
-        let clock = Clock::get()?;
-        let start_idx = (clock.slot as usize) % candidates.len();
         self.task.validations.clear();
-        for i in 0..required {
-            let idx = (start_idx + i as usize) % candidates.len();
-            self.task.validations.push(Validator {
-                pubkey: candidates[idx],
-                status: ValidationStatus::Pending,
-            });
+        if candidates.len() == required as usize {
+            // The whole pool is needed, no rotation to compute
+            self.task
+                .validations
+                .extend(candidates.iter().map(|pubkey| Validator {
+                    pubkey: *pubkey,
+                    status: ValidationStatus::Pending,
+                }));
+        } else {
+            let start_idx = (clock.slot as usize) % candidates.len();
+            for i in 0..required {
+                let idx = (start_idx + i as usize) % candidates.len();
+                self.task.validations.push(Validator {
+                    pubkey: candidates[idx],
+                    status: ValidationStatus::Pending,
+                });
+            }
+        }
+
+        if reclaim {
+            // The expired execution's lock and result are dropped before the new claim locks
+            self.session.release_task_lock(self.task.max_task_cost)?;
+            self.task.discard_pending_result();
+            self.task.validation_deadline_slot = 0;
         }
 
-        let rent = Rent::get()?;
-        let rent_exempt_minimum = rent.minimum_balance(0);
-        let available_balance = self
-            .vault
-            .lamports()
+        let vault_balance = match self.network_config.payment_mint {
+            Some(mint) => {
+                let session_token_vault = self
+                    .session_token_vault
+                    .as_ref()
+                    .ok_or(ErrorCode::MissingTokenAccount)?;
+                verify_session_token_vault(session_token_vault, &self.session.key(), &mint)?;
+                session_token_vault.amount
+            }
+            None => {
+                let rent = Rent::get()?;
+                let rent_exempt_minimum = rent.minimum_balance(0);
+                self.vault
+                    .lamports()
+                    .checked_sub(rent_exempt_minimum)
+                    .ok_or(ErrorCode::Underflow)?
+            }
+        };
+        let available_balance = vault_balance
             .checked_sub(self.session.locked_for_tasks)
-            .ok_or(ErrorCode::Underflow)?
-            .checked_sub(rent_exempt_minimum)
             .ok_or(ErrorCode::Underflow)?;
 
         require!(
@@ -104,20 +201,48 @@ impl<'info> ClaimTask<'info> {
             .checked_add(max_task_cost)
             .ok_or(ErrorCode::Overflow)?;
 
+        // Snapshot the payment basis so later contributions or withdrawals can't shift it
+        self.task.claim_available_balance = available_balance;
+
         self.task.max_task_cost = max_task_cost;
         self.task.max_call_count = max_call_count;
         self.task.status = TaskStatus::Processing;
+        self.task.processing_deadline_slot = match self.network_config.processing_window_slots {
+            0 => 0,
+            window => clock.slot.checked_add(window).ok_or(ErrorCode::Overflow)?,
+        };
         self.task.task_index = self
             .task
             .task_index
             .checked_add(1)
             .ok_or(ErrorCode::Overflow)?;
+        self.session.record_activity()?;
 
         emit!(TaskClaimed {
             session_slot_id: self.session.session_slot_id,
             task_slot_id: self.task.task_slot_id,
             compute_node: compute_pubkey,
             max_task_cost,
+            max_call_count,
+            task_index: self.task.task_index,
+        });
+
+        emit!(ValidatorsAssigned {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            validators: self.task.validations.iter().map(|v| v.pubkey).collect(),
+        });
+
+        // The whole assignment in one record an orchestrator can keep instead of reading the task
+        emit!(TaskAssignmentReceipt {
+            network_config: self.network_config.key(),
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            task_index: self.task.task_index,
+            compute_node: compute_pubkey,
+            validators: self.task.validations.iter().map(|v| v.pubkey).collect(),
+            max_task_cost,
+            claim_slot: clock.slot,
         });
 
         Ok(())
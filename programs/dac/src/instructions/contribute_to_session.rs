@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
 
+use crate::constants::BASIS_POINTS;
 use crate::errors::ErrorCode;
 use crate::events::ContributionMade;
 use crate::state::{Contribution, Session, SessionStatus};
+use crate::utils::{
+    session_token_accounts, share_price, shares_for_amount, transfer_to_session_token_vault,
+};
 use crate::NetworkConfig;
 
 #[derive(Accounts)]
@@ -34,12 +39,25 @@ pub struct ContributeToSession<'info> {
     )]
     pub contribution: Account<'info, Contribution>,
 
+    /// Session owner's contribution, credited with the owner fee when one is set
+    #[account(mut)]
+    pub owner_contribution: Option<Account<'info, Contribution>>,
+
     #[account(
         seeds = [b"dac_network_config", network_config.authority.as_ref()],
         bump = network_config.bump,
     )]
     pub network_config: Account<'info, NetworkConfig>,
 
+    /// Only used when the network has a payment mint
+    #[account(mut)]
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub contributor_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -47,38 +65,103 @@ impl<'info> ContributeToSession<'info> {
     pub fn contribute_to_session(
         &mut self,
         deposit_amount: u64,
+        min_shares: Option<u64>,
         bumps: &ContributeToSessionBumps,
     ) -> Result<()> {
         require!(
-            self.session.status == SessionStatus::Active,
+            self.session.status == SessionStatus::Active || self.session.is_crowdfunding(),
             ErrorCode::InvalidSessionStatus
         );
         require!(deposit_amount > 0, ErrorCode::Overflow);
+        // The owner closes the session to others, never to their own top-ups
+        require!(
+            self.session.accepting_contributions || self.contributor.key() == self.session.owner,
+            ErrorCode::ContributionsClosed
+        );
+        // Both accounts are written back on exit, the stale copy would win
+        if let Some(owner_contribution) = &self.owner_contribution {
+            require_keys_neq!(
+                owner_contribution.key(),
+                self.contribution.key(),
+                ErrorCode::InvalidPDAAccount
+            );
+        }
+
+        let token_accounts = session_token_accounts(
+            self.network_config.payment_mint,
+            &self.session.key(),
+            &self.session_token_vault,
+            &self.contributor_token_account,
+            &self.token_program,
+        )?;
 
         let share_price = if self.session.total_shares == 0 {
-            1.0_f64
+            share_price(0, 0)?
         } else {
-            let rent = Rent::get()?;
-            let rent_exempt_minimum = rent.minimum_balance(0);
-            let available_balance = self
-                .vault
-                .lamports()
-                .checked_sub(self.session.locked_for_tasks)
-                .ok_or(ErrorCode::Underflow)?
-                .checked_sub(rent_exempt_minimum)
-                .ok_or(ErrorCode::Underflow)?;
-            (available_balance as f64) / (self.session.total_shares as f64)
+            let available_balance = match &token_accounts {
+                Some(token_accounts) => token_accounts
+                    .vault
+                    .amount
+                    .checked_sub(self.session.locked_for_tasks)
+                    .ok_or(ErrorCode::Underflow)?,
+                None => {
+                    let rent = Rent::get()?;
+                    let rent_exempt_minimum = rent.minimum_balance(0);
+                    self.vault
+                        .lamports()
+                        .checked_sub(self.session.locked_for_tasks)
+                        .ok_or(ErrorCode::Underflow)?
+                        .checked_sub(rent_exempt_minimum)
+                        .ok_or(ErrorCode::Underflow)?
+                }
+            };
+            share_price(available_balance, self.session.total_shares)?
         };
 
-        let shares_to_mint = (deposit_amount as f64 / share_price) as u64;
-        require!(shares_to_mint > 0, ErrorCode::Overflow);
+        let shares_to_mint = shares_for_amount(deposit_amount, share_price)?;
+        let owner_fee_shares = self.owner_fee_shares(shares_to_mint)?;
+        let contributor_shares = shares_to_mint
+            .checked_sub(owner_fee_shares)
+            .ok_or(ErrorCode::Underflow)?;
+        require!(contributor_shares > 0, ErrorCode::Overflow);
+        // The share price can move between signing and landing
+        require!(
+            contributor_shares >= min_shares.unwrap_or(0),
+            ErrorCode::SlippageExceeded
+        );
 
-        let cpi_accounts = system_program::Transfer {
-            from: self.contributor.to_account_info(),
-            to: self.vault.to_account_info(),
+        let vault_balance = match &token_accounts {
+            Some(token_accounts) => token_accounts.vault.amount,
+            None => self
+                .vault
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0)),
         };
-        let cpi_context = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
-        system_program::transfer(cpi_context, deposit_amount)?;
+        self.check_vault_cap(vault_balance, deposit_amount)?;
+
+        if let Some(token_accounts) = &token_accounts {
+            transfer_to_session_token_vault(
+                token_accounts,
+                self.contributor.to_account_info(),
+                deposit_amount,
+            )?;
+        } else {
+            // The first contribution to a crowdfunded session also funds the vault's rent
+            let transfer_amount = if self.vault.lamports() == 0 {
+                deposit_amount
+                    .checked_add(Rent::get()?.minimum_balance(0))
+                    .ok_or(ErrorCode::Overflow)?
+            } else {
+                deposit_amount
+            };
+            let cpi_accounts = system_program::Transfer {
+                from: self.contributor.to_account_info(),
+                to: self.vault.to_account_info(),
+            };
+            let cpi_context =
+                CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_context, transfer_amount)?;
+        }
 
         let session_key = self.session.key();
         let contributor_key = self.contributor.key();
@@ -86,8 +169,9 @@ impl<'info> ContributeToSession<'info> {
         if self.contribution.session == Pubkey::default() {
             self.contribution.session = session_key;
             self.contribution.contributor = contributor_key;
-            self.contribution.shares = shares_to_mint;
+            self.contribution.shares = contributor_shares;
             self.contribution.refund_amount = 0;
+            self.contribution.is_owner = false;
             self.contribution.bump = bumps.contribution;
         } else {
             require_keys_eq!(
@@ -104,24 +188,97 @@ impl<'info> ContributeToSession<'info> {
             self.contribution.shares = self
                 .contribution
                 .shares
-                .checked_add(shares_to_mint)
+                .checked_add(contributor_shares)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        if owner_fee_shares > 0 {
+            let owner = self.session.owner;
+            let owner_contribution = self
+                .owner_contribution
+                .as_mut()
+                .ok_or(ErrorCode::OwnerContributionRequired)?;
+            require_keys_eq!(
+                owner_contribution.session,
+                session_key,
+                ErrorCode::InvalidPDAAccount
+            );
+            require_keys_eq!(
+                owner_contribution.contributor,
+                owner,
+                ErrorCode::InvalidPDAAccount
+            );
+
+            owner_contribution.shares = owner_contribution
+                .shares
+                .checked_add(owner_fee_shares)
                 .ok_or(ErrorCode::Overflow)?;
         }
 
+        // Every contribution is part of the session total, so this is the add that overflows
+        // first, failing the whole contribution rather than saturating
         self.session.total_shares = self
             .session
             .total_shares
             .checked_add(shares_to_mint)
             .ok_or(ErrorCode::Overflow)?;
+        self.check_ownership_cap()?;
+        self.session.record_activity()?;
 
         emit!(ContributionMade {
             session_slot_id: self.session.session_slot_id,
             contributor: self.contributor.key(),
             deposit_amount,
-            shares_minted: shares_to_mint,
+            shares_minted: contributor_shares,
+            owner_fee_shares,
             total_shares: self.session.total_shares,
         });
 
         Ok(())
     }
+
+    /// The vault, rent aside, may not hold more than the network's `max_vault_balance`
+    fn check_vault_cap(&self, vault_balance: u64, deposit_amount: u64) -> Result<()> {
+        let Some(max_vault_balance) = self.network_config.max_vault_balance else {
+            return Ok(());
+        };
+
+        let new_balance = vault_balance
+            .checked_add(deposit_amount)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            new_balance <= max_vault_balance,
+            ErrorCode::VaultCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Outside contributors may not hold more than `max_ownership_bps` of the session's shares
+    fn check_ownership_cap(&self) -> Result<()> {
+        let max_ownership_bps = self.network_config.max_ownership_bps;
+        if max_ownership_bps == 0 || self.contribution.is_owner {
+            return Ok(());
+        }
+
+        require!(
+            (self.contribution.shares as u128) * (BASIS_POINTS as u128)
+                <= (self.session.total_shares as u128) * (max_ownership_bps as u128),
+            ErrorCode::OwnershipCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Part of the minted shares owed to the session owner, nothing on the owner's own deposits
+    fn owner_fee_shares(&self, shares_to_mint: u64) -> Result<u64> {
+        let owner_fee_bps = match self.session.owner_fee_bps {
+            Some(bps) if bps > 0 && self.contributor.key() != self.session.owner => bps,
+            _ => return Ok(0),
+        };
+
+        let fee_shares = (shares_to_mint as u128)
+            .checked_mul(owner_fee_bps as u128)
+            .and_then(|v| v.checked_div(BASIS_POINTS as u128))
+            .ok_or(ErrorCode::Overflow)?;
+        u64::try_from(fee_shares).map_err(|_| ErrorCode::Overflow.into())
+    }
 }
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
 use crate::events::AgentCreated;
 use crate::state::{Agent, AgentStatus, NetworkConfig};
 
@@ -37,6 +38,13 @@ impl<'info> CreateAgent<'info> {
         agent_config_cid: String,
         bumps: &CreateAgentBumps,
     ) -> Result<()> {
+        require!(
+            self.network_config
+                .can_create_agent(&self.agent_owner.key()),
+            ErrorCode::AgentCreatorNotAllowed
+        );
+        self.network_config.validate_cid(&agent_config_cid)?;
+
         let agent_slot_id = self.network_config.next_agent_slot_id();
 
         self.agent.set_inner(Agent {
@@ -47,6 +55,7 @@ impl<'info> CreateAgent<'info> {
             status: AgentStatus::Pending,
             approved_validators: Vec::new(),
             rejected_validators: Vec::new(),
+            active_session_count: 0,
             bump: bumps.agent,
         });
 
@@ -1,14 +1,17 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::events::SessionCreated;
 use crate::state::{NetworkConfig, Session, SessionStatus, Task, TaskStatus};
-use crate::TaskType;
+use crate::utils::init_dynamic_pda;
 
 #[derive(Accounts)]
 pub struct CreateSession<'info> {
+    /// Funds the session and task rent, a sponsor when it isn't the owner
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    #[account(mut)]
+    /// Becomes the session owner when `is_owned`, never charged here
     pub owner: Signer<'info>,
 
     #[account(
@@ -31,10 +34,9 @@ pub struct CreateSession<'info> {
     )]
     pub session: Account<'info, Session>,
 
+    /// CHECK: Created in the handler once the slot is known to be free
     #[account(
-        init,
-        payer = payer,
-        space = 8 + Task::INIT_SPACE,
+        mut,
         seeds = [
             b"task",
             network_config.key().as_ref(),
@@ -42,7 +44,7 @@ pub struct CreateSession<'info> {
         ],
         bump,
     )]
-    pub task: Account<'info, Task>,
+    pub task: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -52,21 +54,75 @@ impl<'info> CreateSession<'info> {
         &mut self,
         is_owned: bool,
         is_confidential: bool,
+        allowed_owner: Option<Pubkey>,
+        funding_threshold: Option<u64>,
         bumps: &CreateSessionBumps,
     ) -> Result<()> {
+        // Owned sessions are already bound to their owner
+        require!(
+            !is_owned || allowed_owner.is_none(),
+            ErrorCode::InvalidSessionOwner
+        );
+        // A crowdfunded session is claimed by its contributors, not by a designated owner
+        if let Some(funding_threshold) = funding_threshold {
+            require!(
+                !is_owned && allowed_owner.is_none(),
+                ErrorCode::InvalidSessionOwner
+            );
+            require!(funding_threshold > 0, ErrorCode::DepositTooSmall);
+            require!(
+                self.network_config.payment_mint.is_none(),
+                ErrorCode::TokenCrowdfundingUnsupported
+            );
+        }
+
         let session_slot_id = self.network_config.next_session_slot_id();
         let task_slot_id = self.network_config.next_task_slot_id();
 
+        // A task_count out of step with the allocated slots would hand over a live task
+        require!(self.task.data_is_empty(), ErrorCode::TaskSlotInUse);
+        let task_account = self.task.to_account_info();
+        let task_slot_bytes = task_slot_id.to_le_bytes();
+        let network_config_key = self.network_config.key();
+        let seeds = &[
+            b"task".as_ref(),
+            network_config_key.as_ref(),
+            task_slot_bytes.as_ref(),
+        ];
+        let task_bump = init_dynamic_pda(
+            &self.payer,
+            &task_account,
+            seeds,
+            8 + Task::INIT_SPACE,
+            &crate::ID,
+            &self.system_program,
+        )?;
+
+        // A sponsor only pays rent, ownership always goes to the owner signer
         let owner = if is_owned {
             self.owner.key()
         } else {
             Pubkey::default()
         };
 
+        // Contributions arrive before set_session, so the vault bump is needed up front
+        let vault_bump = if funding_threshold.is_some() {
+            Pubkey::find_program_address(
+                &[b"session_vault", self.session.key().as_ref()],
+                &crate::ID,
+            )
+            .1
+        } else {
+            0
+        };
+
         self.session.set_inner(Session {
             session_slot_id,
             owner,
+            allowed_owner,
+            funding_threshold,
             task: self.task.key(),
+            agent: Pubkey::default(),
             status: SessionStatus::Pending,
             is_confidential,
             max_iterations: 0,
@@ -75,34 +131,62 @@ impl<'info> CreateSession<'info> {
             task_index_end: 0,
             total_shares: 0,
             locked_for_tasks: 0,
+            total_paid: 0,
             specification_cid: "".to_string(),
             state_cid: None,
-            vault_bump: 0,
+            max_output_size: None,
+            max_task_cost_ceiling: None,
+            owner_fee_bps: None,
+            allowed_compute_nodes: Vec::new(),
+            completion_hook_program: None,
+            paused: false,
+            accepting_contributions: true,
+            last_activity_slot: Clock::get()?.slot,
+            last_iteration_slot: None,
+            vault_bump,
             bump: bumps.session,
         });
 
-        self.task.set_inner(Task {
+        let task = Task {
             task_slot_id,
             session_slot_id: Some(session_slot_id),
             status: TaskStatus::Ready,
             compute_node: None,
-            task_type: TaskType::Completion(0),
+            task_type: self.network_config.default_task_type.clone(),
             chain_proof: [0u8; 32],
             task_index: 0,
             max_task_cost: 0,
             max_call_count: 0,
+            estimated_compute_units: 0,
             call_count: 0,
             input_cid: None,
             output_cid: None,
             pending_input_cid: None,
             pending_output_cid: None,
+            next_input_cid: None,
+            pending_next_input_cid: None,
+            pending_result_digest: [0; 32],
+            checkpoint_cid: None,
             validations: Vec::new(),
-            bump: bumps.task,
-        });
+            validation_started_slot: 0,
+            validation_deadline_slot: 0,
+            output_size: 0,
+            claim_available_balance: 0,
+            processing_deadline_slot: 0,
+            bump: task_bump,
+        };
+        task.try_serialize(&mut *task_account.try_borrow_mut_data()?)?;
 
         self.network_config.increment_session_count()?;
         self.network_config.increment_task_count()?;
 
+        emit!(SessionCreated {
+            session_slot_id,
+            owner,
+            payer: self.payer.key(),
+            sponsored: self.payer.key() != self.owner.key(),
+        });
+
         Ok(())
     }
 }
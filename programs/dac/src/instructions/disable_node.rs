@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::{NodePoolMembershipChanged, PoolMembershipChange};
+use crate::state::{NetworkConfig, NodeInfo, NodeStatus};
+
+#[derive(Accounts)]
+pub struct DisableNode<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+}
+
+impl<'info> DisableNode<'info> {
+    /// Take an active node out of service and out of its approved pool
+    pub fn disable_node(&mut self) -> Result<()> {
+        require!(
+            self.node_info.status == NodeStatus::Active,
+            ErrorCode::InvalidNodeStatus
+        );
+        // A disabled node can't settle what it claimed, so its tasks would be stuck
+        require!(
+            self.node_info.current_tasks == 0,
+            ErrorCode::NodeTasksInFlight
+        );
+
+        self.node_info.status = NodeStatus::Disabled;
+
+        if self
+            .network_config
+            .remove_node(self.node_info.node_type, &self.node_info.node_pubkey)
+        {
+            emit!(NodePoolMembershipChanged {
+                node: self.node_info.node_pubkey,
+                node_type: self.node_info.node_type,
+                change: PoolMembershipChange::Removed,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TaskSettlementDisputed;
+use crate::state::{NetworkConfig, NodeInfo, Session, TaskSettlement};
+
+#[derive(Accounts)]
+pub struct DisputeTaskSettlement<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"session_vault", session.key().as_ref()],
+        bump = session.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = session @ ErrorCode::InvalidSession,
+        has_one = node_info @ ErrorCode::InvalidPDAAccount,
+        has_one = payer @ ErrorCode::InvalidPDAAccount,
+        seeds = [b"task_settlement", task_settlement.task.as_ref(), task_settlement.task_index.to_le_bytes().as_ref()],
+        bump = task_settlement.bump,
+    )]
+    pub task_settlement: Account<'info, TaskSettlement>,
+
+    /// CHECK: Session vault that paid the escrow rent, checked against the settlement
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+impl<'info> DisputeTaskSettlement<'info> {
+    /// Claw a held payment back into the session vault before the node can sweep it.
+    /// The validated result stands, only the payment is returned to the contributors.
+    pub fn dispute_task_settlement(&mut self) -> Result<()> {
+        require!(
+            self.task_settlement.is_held(Clock::get()?.slot),
+            ErrorCode::SettlementHoldExpired
+        );
+
+        let amount = self.task_settlement.amount;
+        self.task_settlement.sub_lamports(amount)?;
+        self.vault.add_lamports(amount)?;
+
+        // The session may have been set again since, its running total then no longer holds it
+        self.session.total_paid = self.session.total_paid.saturating_sub(amount);
+        self.session.record_activity()?;
+
+        emit!(TaskSettlementDisputed {
+            session_slot_id: self.session.session_slot_id,
+            task: self.task_settlement.task,
+            task_index: self.task_settlement.task_index,
+            node: self.node_info.node_pubkey,
+            amount,
+        });
+
+        Ok(())
+    }
+}
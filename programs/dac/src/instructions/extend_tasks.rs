@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TasksExtended;
+use crate::instructions::InitializeNetwork;
+use crate::state::NetworkConfig;
+
+#[derive(Accounts)]
+pub struct ExtendTasks<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"dac_network_config", authority.key().as_ref()],
+        bump = network_config.bump,
+        constraint = network_config.authority == authority.key() @ ErrorCode::InvalidAuthority
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExtendTasks<'info> {
+    /// Pre-allocate `count` more task slots after the current `task_count`, for allocations
+    /// too large to fit in the initialize_network transaction. The new task PDAs are passed
+    /// in remaining accounts in slot order.
+    pub fn extend_tasks(
+        &mut self,
+        count: u64,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let first_task_slot_id = self.network_config.task_count;
+        InitializeNetwork::pre_allocate_tasks(
+            remaining_accounts,
+            &self.authority,
+            &self.network_config,
+            first_task_slot_id,
+            count,
+            &self.system_program,
+        )?;
+
+        self.network_config.task_count = first_task_slot_id
+            .checked_add(count)
+            .ok_or(ErrorCode::Overflow)?;
+
+        emit!(TasksExtended {
+            first_task_slot_id,
+            count,
+            task_count: self.network_config.task_count,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::{SessionCompleted, TaskForceResolved};
+use crate::state::{
+    Agent, NetworkConfig, NodeInfo, Session, SessionStatus, Task, TaskStatus, ValidationStatus,
+};
+use crate::utils::{
+    invoke_completion_hook, session_vault_balance, settle_task_payment, split_completion_hook,
+    TaskPaymentAccounts,
+};
+
+#[derive(Accounts)]
+pub struct ForceResolveTask<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"session_vault", session.key().as_ref()],
+        bump = session.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(
+        mut,
+        seeds = [b"node_info", node_info.node_pubkey.key().as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"node_treasury", node_info.key().as_ref(), node_info.registration_epoch.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub node_treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury", network_config.key().as_ref()],
+        bump,
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    /// Only used when the network has a payment mint
+    #[account(mut)]
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub node_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Escrow PDA created for the compute node payment when the network holds settlements
+    #[account(mut)]
+    pub task_settlement: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ForceResolveTask<'info> {
+    /// Resolve a task whose validators stopped voting, for the network authority only: the
+    /// session owner would be ruling on a payment out of their own vault. A majority of the votes cast approves the result and settles the locked
+    /// max_task_cost like a validated approval, otherwise the task goes back to Ready.
+    /// Remaining accounts are the ones an approval reaching the threshold takes: the approving
+    /// validators' (node_info, treasury) pairs, then the session's completion hook program.
+    pub fn force_resolve_task(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let resolver = self.resolver.key();
+        require_keys_eq!(
+            resolver,
+            self.network_config.authority,
+            ErrorCode::InvalidAuthority
+        );
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            self.task.status == TaskStatus::AwaitingValidation,
+            ErrorCode::InvalidTaskStatus
+        );
+        require!(
+            self.session.task == self.task.key(),
+            ErrorCode::InvalidSession
+        );
+        require!(
+            self.task.compute_node == Some(self.node_info.node_pubkey),
+            ErrorCode::InvalidComputeNodePubkey
+        );
+
+        let stall_slots = self.network_config.validation_stall_slots;
+        let stalled_at = self
+            .task
+            .validation_started_slot
+            .checked_add(stall_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            stall_slots > 0 && Clock::get()?.slot >= stalled_at,
+            ErrorCode::ValidationNotStalled
+        );
+
+        let approvals = self.task.count_validations(ValidationStatus::Approved);
+        let rejections = self.task.count_validations(ValidationStatus::Rejected);
+        let approved = approvals > rejections;
+
+        self.session.release_task_lock(self.task.max_task_cost)?;
+        self.node_info.finish_task()?;
+
+        let payment_amount = if approved {
+            self.task.max_task_cost
+        } else {
+            0
+        };
+
+        if approved {
+            self.task.commit_pending_result()?;
+
+            let (validator_accounts, completion_hook) =
+                split_completion_hook(&self.session, remaining_accounts);
+            settle_task_payment(
+                &mut TaskPaymentAccounts {
+                    network_config: &self.network_config,
+                    session: &self.session,
+                    vault: &self.vault,
+                    task: &self.task,
+                    node_info: &mut self.node_info,
+                    node_treasury: &self.node_treasury,
+                    protocol_treasury: &self.protocol_treasury,
+                    session_token_vault: &mut self.session_token_vault,
+                    node_token_account: &self.node_token_account,
+                    token_program: &self.token_program,
+                    task_settlement: &self.task_settlement,
+                    system_program: &self.system_program,
+                },
+                payment_amount,
+                validator_accounts,
+                None,
+            )?;
+            self.session.record_payment(payment_amount)?;
+
+            if self
+                .session
+                .complete_iteration(false, self.task.task_index)?
+            {
+                self.task.status = TaskStatus::Ready;
+                self.agent.close_session()?;
+                invoke_completion_hook(&self.session, completion_hook)?;
+
+                emit!(SessionCompleted {
+                    session_slot_id: self.session.session_slot_id,
+                    final_iteration: self.session.current_iteration,
+                    vault_balance: self.vault_balance(),
+                });
+            } else {
+                self.task.status = TaskStatus::Pending;
+            }
+        } else {
+            self.task.discard_pending_result();
+            self.task.status = TaskStatus::Ready;
+        }
+
+        self.task.validations.clear();
+        self.session.record_activity()?;
+
+        emit!(TaskForceResolved {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            approved,
+            approvals,
+            rejections,
+            payment_amount,
+        });
+
+        Ok(())
+    }
+
+    fn vault_balance(&self) -> u64 {
+        session_vault_balance(&self.network_config, &self.vault, &self.session_token_vault)
+    }
+}
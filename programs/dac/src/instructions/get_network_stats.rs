@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::events::NetworkStats;
+use crate::state::NetworkConfig;
+
+#[derive(Accounts)]
+pub struct GetNetworkStats<'info> {
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> GetNetworkStats<'info> {
+    pub fn get_network_stats(&self) -> Result<()> {
+        emit!(NetworkStats {
+            agent_count: self.network_config.agent_count,
+            session_count: self.network_config.session_count,
+            task_count: self.network_config.task_count,
+            approved_public_nodes: self.network_config.approved_public_nodes.len() as u32,
+            approved_confidential_nodes: self.network_config.approved_confidential_nodes.len()
+                as u32,
+            required_validations: self.network_config.required_validations,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::events::NodeSummary;
+use crate::state::{NetworkConfig, NodeInfo};
+
+#[derive(Accounts)]
+pub struct GetNodeSummary<'info> {
+    #[account(
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> GetNodeSummary<'info> {
+    /// Everything a scheduler needs to classify a node, in one event
+    pub fn get_node_summary(&self) -> Result<()> {
+        let node_info = &self.node_info;
+
+        emit!(NodeSummary {
+            node: node_info.node_pubkey,
+            node_type: node_info.node_type,
+            status: node_info.status,
+            total_tasks_completed: node_info.total_tasks_completed,
+            total_earned: node_info.total_earned,
+            earned_by_type: node_info.earned_by_type,
+            approved: self
+                .network_config
+                .is_node_approved(node_info.node_type, &node_info.node_pubkey),
+        });
+
+        Ok(())
+    }
+}
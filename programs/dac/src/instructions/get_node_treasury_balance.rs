@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::events::NodeTreasuryBalance;
+use crate::state::NodeInfo;
+
+#[derive(Accounts)]
+pub struct GetNodeTreasuryBalance<'info> {
+    #[account(
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+
+    #[account(address = node_info.node_treasury)]
+    pub node_treasury: SystemAccount<'info>,
+}
+
+impl<'info> GetNodeTreasuryBalance<'info> {
+    /// What `batch_withdraw_node_rewards` would sweep from the node's treasury right now
+    pub fn get_node_treasury_balance(&self) -> Result<()> {
+        let balance = self.node_treasury.lamports();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+
+        emit!(NodeTreasuryBalance {
+            node: self.node_info.node_pubkey,
+            node_treasury: self.node_treasury.key(),
+            balance,
+            claimable: balance.saturating_sub(rent_exempt_minimum),
+        });
+
+        Ok(())
+    }
+}
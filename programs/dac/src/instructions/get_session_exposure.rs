@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SessionExposure;
+use crate::state::{NetworkConfig, Session, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct GetSessionExposure<'info> {
+    #[account(
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> GetSessionExposure<'info> {
+    /// Sum the cost locked by the session's in-flight tasks, passed as remaining accounts,
+    /// next to the session's own `locked_for_tasks` counter
+    pub fn get_session_exposure(
+        &self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let network_config_key = self.network_config.key();
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(remaining_accounts.len());
+        let mut tasks_locked: u64 = 0;
+        let mut task_count: u32 = 0;
+
+        for task_account in remaining_accounts {
+            let task: Account<Task> = Account::try_from(task_account)?;
+            let (task_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"task",
+                    network_config_key.as_ref(),
+                    task.task_slot_id.to_le_bytes().as_ref(),
+                ],
+                &crate::ID,
+            );
+            require_keys_eq!(task_pda, task.key(), ErrorCode::InvalidPDAAccount);
+            require!(
+                task.session_slot_id == Some(self.session.session_slot_id),
+                ErrorCode::InvalidSession
+            );
+            // A repeated task would be counted twice
+            require!(!seen.contains(&task.key()), ErrorCode::DuplicateTaskAccount);
+            seen.push(task.key());
+
+            if matches!(
+                task.status,
+                TaskStatus::Processing | TaskStatus::AwaitingValidation
+            ) {
+                tasks_locked = tasks_locked
+                    .checked_add(task.max_task_cost)
+                    .ok_or(ErrorCode::Overflow)?;
+                task_count += 1;
+            }
+        }
+
+        emit!(SessionExposure {
+            session_slot_id: self.session.session_slot_id,
+            locked_for_tasks: self.session.locked_for_tasks,
+            tasks_locked,
+            task_count,
+            balanced: tasks_locked == self.session.locked_for_tasks,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SHARE_PRECISION;
+
+#[derive(Accounts)]
+pub struct GetSharePrecision {}
+
+impl GetSharePrecision {
+    pub fn get_share_precision(&self) -> Result<u64> {
+        Ok(SHARE_PRECISION)
+    }
+}
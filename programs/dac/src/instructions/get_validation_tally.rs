@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::events::ValidationTally;
+use crate::state::{NetworkConfig, Task, ValidationStatus};
+use crate::utils::check_validation_threshold;
+
+#[derive(Accounts)]
+pub struct GetValidationTally<'info> {
+    #[account(
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> GetValidationTally<'info> {
+    /// Votes on the task's current result. Validations are cleared once a threshold settles
+    /// the result, so a settled task reports an empty tally.
+    pub fn get_validation_tally(&self) -> Result<()> {
+        let required_validations = self.network_config.required_validations;
+        let approved = self.task.count_validations(ValidationStatus::Approved);
+        let rejected = self.task.count_validations(ValidationStatus::Rejected);
+
+        emit!(ValidationTally {
+            task_slot_id: self.task.task_slot_id,
+            task_index: self.task.task_index,
+            approved,
+            rejected,
+            pending: self.task.count_validations(ValidationStatus::Pending),
+            required_validations,
+            approval_threshold_reached: check_validation_threshold(approved, required_validations)?,
+            rejection_threshold_reached: check_validation_threshold(
+                rejected,
+                required_validations
+            )?,
+        });
+
+        Ok(())
+    }
+}
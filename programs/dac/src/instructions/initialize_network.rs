@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::{CodeMeasurement, NetworkConfig, Task, TaskStatus};
+use crate::state::{
+    CodeMeasurement, NetworkConfig, Task, TaskStatus, MAX_CODE_MEASUREMENTS, MAX_TASK_VALIDATORS,
+};
 use crate::utils::init_dynamic_pda;
 use crate::TaskType;
 
@@ -30,6 +32,8 @@ impl<'info> InitializeNetwork<'info> {
         allocate_tasks: u64,
         approved_code_measurements: Vec<CodeMeasurement>,
         required_validations: u32,
+        genesis_nonce: Option<[u8; 32]>,
+        default_task_type: Option<TaskType>,
         remaining_accounts: &[AccountInfo<'info>],
         bumps: &InitializeNetworkBumps,
     ) -> Result<()> {
@@ -39,11 +43,19 @@ impl<'info> InitializeNetwork<'info> {
         );
 
         require!(
-            approved_code_measurements.len() <= 10,
+            approved_code_measurements.len() <= MAX_CODE_MEASUREMENTS,
             ErrorCode::TooManyCodeMeasurements
         );
 
-        let genesis_hash = self.network_config.compute_genesis_hash()?;
+        // Claims assign this many validators to a task
+        require!(
+            required_validations > 0 && required_validations as usize <= MAX_TASK_VALIDATORS,
+            ErrorCode::InvalidRequiredValidations
+        );
+
+        let genesis_hash =
+            NetworkConfig::compute_genesis_hash(&self.authority.key(), genesis_nonce)?;
+        let default_task_type = default_task_type.unwrap_or(TaskType::Completion(0));
 
         self.network_config.set_inner(NetworkConfig {
             authority: self.authority.key(),
@@ -58,13 +70,39 @@ impl<'info> InitializeNetwork<'info> {
             session_count: 0,
             approved_code_measurements: approved_code_measurements,
             bump: bumps.network_config,
+            agent_required_validations: required_validations,
+            confidential_public_fallback: false,
+            reject_duplicate_results: false,
+            agent_validators_must_be_confidential: false,
+            validation_stall_slots: 0,
+            validation_window_slots: 0,
+            min_slots_between_claims: 0,
+            min_slots_between_iterations: 0,
+            settlement_hold_slots: 0,
+            abandonment_slots: 0,
+            rejection_reward: 0,
+            validator_share_bps: 0,
+            protocol_share_bps: 0,
+            max_sessions_per_agent: 0,
+            payment_mint: None,
+            default_task_type,
+            allowed_cid_prefixes: Vec::new(),
+            agent_creation_allowlist: Vec::new(),
+            node_registration_count: 0,
+            genesis_nonce,
+            debug_validation_logs: false,
+            max_vault_balance: None,
+            processing_window_slots: 0,
+            min_measurement_version: None,
+            paused: false,
+            max_ownership_bps: 0,
         });
 
         Self::pre_allocate_tasks(
-            &remaining_accounts,
+            remaining_accounts,
             &self.authority,
-            self.network_config.key(),
-            genesis_hash,
+            &self.network_config,
+            0,
             allocate_tasks,
             &self.system_program,
         )?;
@@ -72,17 +110,24 @@ impl<'info> InitializeNetwork<'info> {
         Ok(())
     }
 
-    fn pre_allocate_tasks(
+    /// Create `allocate_tasks` ready task slots from `first_task_id`, their PDAs passed in
+    /// remaining accounts in slot order. Each starts from the network's genesis hash and
+    /// default task type
+    pub(crate) fn pre_allocate_tasks(
         remaining_accounts: &[AccountInfo<'info>],
         authority: &Signer<'info>,
-        network_config_key: Pubkey,
-        genesis_hash: [u8; 32],
+        network_config: &Account<'info, NetworkConfig>,
+        first_task_id: u64,
         allocate_tasks: u64,
         system_program: &Program<'info, System>,
     ) -> Result<()> {
-        for task_id in 0..allocate_tasks {
+        let network_config_key = network_config.key();
+        let end_task_id = first_task_id
+            .checked_add(allocate_tasks)
+            .ok_or(ErrorCode::Overflow)?;
+        for (index, task_id) in (first_task_id..end_task_id).enumerate() {
             let task_account_info = remaining_accounts
-                .get(task_id as usize)
+                .get(index)
                 .ok_or(ErrorCode::MissingAccount)?;
 
             let seeds = &[b"task", network_config_key.as_ref(), &task_id.to_le_bytes()];
@@ -101,17 +146,27 @@ impl<'info> InitializeNetwork<'info> {
                 session_slot_id: None,
                 status: TaskStatus::Ready,
                 compute_node: None,
-                task_type: TaskType::Completion(0),
-                chain_proof: genesis_hash,
+                task_type: network_config.default_task_type.clone(),
+                chain_proof: network_config.genesis_hash,
                 task_index: 0,
                 max_task_cost: 0,
                 max_call_count: 0,
+                estimated_compute_units: 0,
                 call_count: 0,
                 input_cid: None,
                 output_cid: None,
                 pending_input_cid: None,
                 pending_output_cid: None,
+                next_input_cid: None,
+                pending_next_input_cid: None,
+                pending_result_digest: [0; 32],
+                checkpoint_cid: None,
                 validations: Vec::new(),
+                validation_started_slot: 0,
+                validation_deadline_slot: 0,
+                output_size: 0,
+                claim_available_balance: 0,
+                processing_deadline_slot: 0,
                 bump,
             };
 
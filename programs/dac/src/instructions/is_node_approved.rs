@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::events::ApprovedStatus;
+use crate::state::{NetworkConfig, NodeType};
+
+#[derive(Accounts)]
+pub struct IsNodeApproved<'info> {
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> IsNodeApproved<'info> {
+    pub fn is_node_approved(&self, node: Pubkey) -> Result<()> {
+        emit!(ApprovedStatus {
+            node,
+            public_approved: self.network_config.is_node_approved(NodeType::Public, &node),
+            confidential_approved: self
+                .network_config
+                .is_node_approved(NodeType::Confidential, &node),
+        });
+
+        Ok(())
+    }
+}
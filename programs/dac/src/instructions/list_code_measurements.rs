@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::events::CodeMeasurementListed;
+use crate::state::NetworkConfig;
+
+#[derive(Accounts)]
+pub struct ListCodeMeasurements<'info> {
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> ListCodeMeasurements<'info> {
+    /// Emits one event per approved measurement, oldest first. Storage keeps the newest at
+    /// index 0, so the list is walked in reverse to report them in insertion order.
+    pub fn list_code_measurements(&self) -> Result<()> {
+        for code_measurement in self.network_config.approved_code_measurements.iter().rev() {
+            emit!(CodeMeasurementListed {
+                measurement: code_measurement.measurement,
+                version: code_measurement.version,
+            });
+        }
+
+        Ok(())
+    }
+}
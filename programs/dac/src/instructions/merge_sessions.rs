@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SessionsMerged;
+use crate::state::{Contribution, Session, SessionStatus};
+use crate::utils::{
+    amount_for_shares, init_dynamic_pda, share_price, shares_for_amount, transfer_from_vault,
+};
+use crate::NetworkConfig;
+
+#[derive(Accounts)]
+pub struct MergeSessions<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), source_session.session_slot_id.to_le_bytes().as_ref()],
+        bump = source_session.bump,
+    )]
+    pub source_session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"session_vault", source_session.key().as_ref()],
+        bump = source_session.vault_bump,
+    )]
+    pub source_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), destination_session.session_slot_id.to_le_bytes().as_ref()],
+        bump = destination_session.bump,
+    )]
+    pub destination_session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"session_vault", destination_session.key().as_ref()],
+        bump = destination_session.vault_bump,
+    )]
+    pub destination_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MergeSessions<'info> {
+    /// Move the whole source vault into the destination and re-mint every source contribution
+    /// at the destination share price. The destination must be Active so contributors can
+    /// withdraw the merged funds. Remaining accounts are (source_contribution,
+    /// destination_contribution) pairs covering all of the source shares, missing destination
+    /// contributions are created at the owner's expense.
+    pub fn merge_sessions(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require_keys_neq!(
+            self.source_session.key(),
+            self.destination_session.key(),
+            ErrorCode::InvalidSession
+        );
+        require!(
+            self.source_session.status == SessionStatus::Completed
+                && self.source_session.locked_for_tasks == 0,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            self.destination_session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+        for session in [&self.source_session, &self.destination_session] {
+            require_keys_eq!(session.owner, self.owner.key(), ErrorCode::InvalidSessionOwner);
+        }
+        require!(
+            self.network_config.payment_mint.is_none(),
+            ErrorCode::TokenSessionMergeUnsupported
+        );
+        require!(
+            remaining_accounts.len() % 2 == 0,
+            ErrorCode::MissingAccount
+        );
+
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+        let source_balance = self
+            .source_vault
+            .lamports()
+            .checked_sub(rent_exempt_minimum)
+            .ok_or(ErrorCode::Underflow)?;
+        // Priced like withdraw_from_session, funds locked by an in-flight task don't count
+        let destination_balance = self
+            .destination_vault
+            .lamports()
+            .checked_sub(self.destination_session.locked_for_tasks)
+            .ok_or(ErrorCode::Underflow)?
+            .checked_sub(rent_exempt_minimum)
+            .ok_or(ErrorCode::Underflow)?;
+
+        let source_price = share_price(source_balance, self.source_session.total_shares)?;
+        let destination_price = if self.destination_session.total_shares == 0 {
+            share_price(0, 0)?
+        } else {
+            share_price(destination_balance, self.destination_session.total_shares)?
+        };
+
+        let source_key = self.source_session.key();
+        let destination_key = self.destination_session.key();
+        let mut migrated_shares: u64 = 0;
+        let mut minted_shares: u64 = 0;
+
+        for pair in remaining_accounts.chunks(2) {
+            let (source_account, destination_account) = (&pair[0], &pair[1]);
+
+            let mut source_contribution: Account<Contribution> = Account::try_from(source_account)?;
+            require_keys_eq!(
+                source_contribution.session,
+                source_key,
+                ErrorCode::InvalidPDAAccount
+            );
+            let shares = source_contribution.shares;
+            if shares == 0 {
+                continue;
+            }
+
+            let amount = amount_for_shares(shares, source_price)?;
+            let new_shares = shares_for_amount(amount, destination_price)?;
+            let contributor = source_contribution.contributor;
+
+            if destination_account.data_is_empty() {
+                let seeds = &[
+                    b"contribution".as_ref(),
+                    destination_key.as_ref(),
+                    contributor.as_ref(),
+                ];
+                let bump = init_dynamic_pda(
+                    &self.owner,
+                    destination_account,
+                    seeds,
+                    8 + Contribution::INIT_SPACE,
+                    &crate::ID,
+                    &self.system_program,
+                )?;
+
+                let contribution = Contribution {
+                    session: destination_key,
+                    contributor,
+                    shares: new_shares,
+                    refund_amount: 0,
+                    is_owner: false,
+                    bump,
+                };
+                contribution.try_serialize(&mut *destination_account.try_borrow_mut_data()?)?;
+            } else {
+                let mut destination_contribution: Account<Contribution> =
+                    Account::try_from(destination_account)?;
+                require_keys_eq!(
+                    destination_contribution.session,
+                    destination_key,
+                    ErrorCode::InvalidPDAAccount
+                );
+                require_keys_eq!(
+                    destination_contribution.contributor,
+                    contributor,
+                    ErrorCode::InvalidPDAAccount
+                );
+                destination_contribution.shares = destination_contribution
+                    .shares
+                    .checked_add(new_shares)
+                    .ok_or(ErrorCode::Overflow)?;
+                destination_contribution.exit(&crate::ID)?;
+            }
+
+            // Written back right away so a repeated pair finds no shares left
+            source_contribution.shares = 0;
+            source_contribution.exit(&crate::ID)?;
+
+            migrated_shares = migrated_shares
+                .checked_add(shares)
+                .ok_or(ErrorCode::Overflow)?;
+            minted_shares = minted_shares
+                .checked_add(new_shares)
+                .ok_or(ErrorCode::Overflow)?;
+        }
+
+        // Every source share must follow its lamports
+        require!(
+            migrated_shares == self.source_session.total_shares,
+            ErrorCode::IncompleteSessionMerge
+        );
+
+        if source_balance > 0 {
+            transfer_from_vault(
+                &self.source_vault,
+                self.destination_vault.to_account_info(),
+                &source_key,
+                self.source_session.vault_bump,
+                &self.system_program,
+                source_balance,
+            )?;
+        }
+
+        self.source_session.total_shares = 0;
+        self.destination_session.total_shares = self
+            .destination_session
+            .total_shares
+            .checked_add(minted_shares)
+            .ok_or(ErrorCode::Overflow)?;
+        self.source_session.record_activity()?;
+        self.destination_session.record_activity()?;
+
+        emit!(SessionsMerged {
+            source_session_slot_id: self.source_session.session_slot_id,
+            destination_session_slot_id: self.destination_session.session_slot_id,
+            amount: source_balance,
+            migrated_shares,
+            minted_shares,
+            total_shares: self.destination_session.total_shares,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCode;
+use crate::events::AccountMigrated;
+use crate::state::{
+    Agent, AgentStatus, Contribution, NodeInfo, NodeStatus, NodeType, Session, SessionStatus, Task,
+    TaskStatus, TaskType, Validator, TASK_TYPE_COUNT,
+};
+
+/// Session as first deployed, every field added since is appended after `bump`
+#[derive(AnchorDeserialize)]
+struct LegacySession {
+    session_slot_id: u64,
+    owner: Pubkey,
+    task: Pubkey,
+    status: SessionStatus,
+    is_confidential: bool,
+    max_iterations: u64,
+    current_iteration: u64,
+    task_index_start: u64,
+    task_index_end: u64,
+    total_shares: u64,
+    locked_for_tasks: u64,
+    specification_cid: String,
+    state_cid: Option<String>,
+    vault_bump: u8,
+    bump: u8,
+}
+
+/// NodeInfo as first deployed
+#[derive(AnchorDeserialize)]
+struct LegacyNodeInfo {
+    owner: Pubkey,
+    node_pubkey: Pubkey,
+    node_type: NodeType,
+    status: NodeStatus,
+    node_info_cid: Option<String>,
+    code_measurement: Option<[u8; 32]>,
+    tee_signing_pubkey: Option<Pubkey>,
+    node_treasury: Pubkey,
+    total_earned: u64,
+    total_tasks_completed: u64,
+    approved_validators: Vec<Pubkey>,
+    rejected_validators: Vec<Pubkey>,
+    bump: u8,
+}
+
+/// Task as first deployed
+#[derive(AnchorDeserialize)]
+struct LegacyTask {
+    task_slot_id: u64,
+    session_slot_id: Option<u64>,
+    status: TaskStatus,
+    compute_node: Option<Pubkey>,
+    task_type: TaskType,
+    chain_proof: [u8; 32],
+    task_index: u64,
+    max_task_cost: u64,
+    max_call_count: u64,
+    call_count: u64,
+    input_cid: Option<String>,
+    output_cid: Option<String>,
+    pending_input_cid: Option<String>,
+    pending_output_cid: Option<String>,
+    validations: Vec<Validator>,
+    bump: u8,
+}
+
+/// Agent as first deployed
+#[derive(AnchorDeserialize)]
+struct LegacyAgent {
+    agent_slot_id: u64,
+    owner: Pubkey,
+    status: AgentStatus,
+    agent_config_cid: String,
+    agent_memory_cid: Option<String>,
+    approved_validators: Vec<Pubkey>,
+    rejected_validators: Vec<Pubkey>,
+    bump: u8,
+}
+
+/// Contribution as first deployed
+#[derive(AnchorDeserialize)]
+struct LegacyContribution {
+    session: Pubkey,
+    contributor: Pubkey,
+    shares: u64,
+    refund_amount: u64,
+    bump: u8,
+}
+
+impl LegacySession {
+    /// The agent was never recorded on a legacy session, so it stays unset and the paths that
+    /// check it refuse the session. Inactivity is counted from the migration.
+    fn migrate(self, slot: u64) -> Session {
+        Session {
+            session_slot_id: self.session_slot_id,
+            owner: self.owner,
+            task: self.task,
+            status: self.status,
+            is_confidential: self.is_confidential,
+            max_iterations: self.max_iterations,
+            current_iteration: self.current_iteration,
+            task_index_start: self.task_index_start,
+            task_index_end: self.task_index_end,
+            total_shares: self.total_shares,
+            locked_for_tasks: self.locked_for_tasks,
+            specification_cid: self.specification_cid,
+            state_cid: self.state_cid,
+            vault_bump: self.vault_bump,
+            bump: self.bump,
+            allowed_owner: None,
+            funding_threshold: None,
+            agent: Pubkey::default(),
+            total_paid: 0,
+            max_output_size: None,
+            max_task_cost_ceiling: None,
+            owner_fee_bps: None,
+            allowed_compute_nodes: Vec::new(),
+            completion_hook_program: None,
+            paused: false,
+            accepting_contributions: true,
+            last_activity_slot: slot,
+            last_iteration_slot: None,
+        }
+    }
+}
+
+impl From<LegacyNodeInfo> for NodeInfo {
+    /// The legacy lifetime earnings can't be split by task type, per-type tallies start at 0
+    fn from(legacy: LegacyNodeInfo) -> Self {
+        Self {
+            owner: legacy.owner,
+            node_pubkey: legacy.node_pubkey,
+            node_type: legacy.node_type,
+            status: legacy.status,
+            node_info_cid: legacy.node_info_cid,
+            code_measurement: legacy.code_measurement,
+            tee_signing_pubkey: legacy.tee_signing_pubkey,
+            node_treasury: legacy.node_treasury,
+            total_earned: legacy.total_earned,
+            total_tasks_completed: legacy.total_tasks_completed,
+            approved_validators: legacy.approved_validators,
+            rejected_validators: legacy.rejected_validators,
+            bump: legacy.bump,
+            registration_epoch: 0,
+            earned_by_type: [0; TASK_TYPE_COUNT],
+            total_validations: 0,
+            last_claim_slot: None,
+            max_concurrent_tasks: 0,
+            current_tasks: 0,
+            agreed_validations: 0,
+        }
+    }
+}
+
+impl LegacyTask {
+    /// A result awaiting validation keeps its votes: its digest is taken from the pending
+    /// fields and the stall window starts at the migration. A claim made before the migration
+    /// pays up to the cost it locked.
+    fn migrate(self, slot: u64) -> Task {
+        let awaiting_validation = self.status == TaskStatus::AwaitingValidation;
+        let mut task = Task {
+            task_slot_id: self.task_slot_id,
+            session_slot_id: self.session_slot_id,
+            status: self.status,
+            compute_node: self.compute_node,
+            task_type: self.task_type,
+            chain_proof: self.chain_proof,
+            task_index: self.task_index,
+            max_task_cost: self.max_task_cost,
+            max_call_count: self.max_call_count,
+            call_count: self.call_count,
+            input_cid: self.input_cid,
+            output_cid: self.output_cid,
+            pending_input_cid: self.pending_input_cid,
+            pending_output_cid: self.pending_output_cid,
+            validations: self.validations,
+            bump: self.bump,
+            estimated_compute_units: 0,
+            next_input_cid: None,
+            pending_next_input_cid: None,
+            pending_result_digest: [0; 32],
+            checkpoint_cid: None,
+            validation_started_slot: if awaiting_validation { slot } else { 0 },
+            validation_deadline_slot: 0,
+            output_size: 0,
+            claim_available_balance: self.max_task_cost,
+            processing_deadline_slot: 0,
+        };
+        if awaiting_validation {
+            task.pending_result_digest = task.hash_pending_result();
+        }
+        task
+    }
+}
+
+impl From<LegacyAgent> for Agent {
+    fn from(legacy: LegacyAgent) -> Self {
+        Self {
+            agent_slot_id: legacy.agent_slot_id,
+            owner: legacy.owner,
+            status: legacy.status,
+            agent_config_cid: legacy.agent_config_cid,
+            agent_memory_cid: legacy.agent_memory_cid,
+            approved_validators: legacy.approved_validators,
+            rejected_validators: legacy.rejected_validators,
+            bump: legacy.bump,
+            active_session_count: 0,
+        }
+    }
+}
+
+impl From<LegacyContribution> for Contribution {
+    /// Owner shares were never locked before, so legacy contributions stay unlocked
+    fn from(legacy: LegacyContribution) -> Self {
+        Self {
+            session: legacy.session,
+            contributor: legacy.contributor,
+            shares: legacy.shares,
+            refund_amount: legacy.refund_amount,
+            bump: legacy.bump,
+            is_owner: false,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Deserialized by hand from its legacy layout, which is too short for `Account<T>`.
+    /// Only program owned accounts whose discriminator names a migratable type are rewritten.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateAccount<'info> {
+    /// Rewrite a Session, NodeInfo, Task, Agent or Contribution still in its first deployed
+    /// layout into the current one, growing the account and topping up rent from the payer.
+    /// Like migrate_network_config, the legacy fields are read explicitly and every appended
+    /// field gets a default, so stale bytes past the old data are never read. The rewrite
+    /// keeps every existing value, which is why anyone may pay for it.
+    pub fn migrate_account(&mut self) -> Result<()> {
+        let info = self.account.to_account_info();
+        let previous_space = info.data_len();
+        let slot = Clock::get()?.slot;
+
+        let (migrated, space) = {
+            let data = info.try_borrow_data()?;
+            require!(data.len() >= 8, ErrorCode::InvalidPDAAccount);
+            let (discriminator, mut legacy) = data.split_at(8);
+            let mut migrated = Vec::new();
+            let space = if discriminator == Session::DISCRIMINATOR {
+                LegacySession::deserialize(&mut legacy)?
+                    .migrate(slot)
+                    .try_serialize(&mut migrated)?;
+                8 + Session::INIT_SPACE
+            } else if discriminator == NodeInfo::DISCRIMINATOR {
+                NodeInfo::from(LegacyNodeInfo::deserialize(&mut legacy)?)
+                    .try_serialize(&mut migrated)?;
+                8 + NodeInfo::INIT_SPACE
+            } else if discriminator == Task::DISCRIMINATOR {
+                LegacyTask::deserialize(&mut legacy)?
+                    .migrate(slot)
+                    .try_serialize(&mut migrated)?;
+                8 + Task::INIT_SPACE
+            } else if discriminator == Agent::DISCRIMINATOR {
+                Agent::from(LegacyAgent::deserialize(&mut legacy)?).try_serialize(&mut migrated)?;
+                8 + Agent::INIT_SPACE
+            } else if discriminator == Contribution::DISCRIMINATOR {
+                Contribution::from(LegacyContribution::deserialize(&mut legacy)?)
+                    .try_serialize(&mut migrated)?;
+                8 + Contribution::INIT_SPACE
+            } else {
+                return err!(ErrorCode::InvalidPDAAccount);
+            };
+            // An account already in the current layout would be misread as a legacy one
+            require!(previous_space < space, ErrorCode::AccountAlreadyMigrated);
+            (migrated, space)
+        };
+
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(space);
+        let top_up = rent_exempt_minimum.saturating_sub(info.lamports());
+        if top_up > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: self.payer.to_account_info(),
+                to: info.clone(),
+            };
+            let cpi_context = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_context, top_up)?;
+        }
+        info.resize(space)?;
+        let mut data = info.try_borrow_mut_data()?;
+        data.fill(0);
+        data[..migrated.len()].copy_from_slice(&migrated);
+
+        emit!(AccountMigrated {
+            account: info.key(),
+            previous_space: previous_space as u64,
+            space: space as u64,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCode;
+use crate::events::NetworkConfigMigrated;
+use crate::state::{CodeMeasurement, NetworkConfig, TaskType};
+
+/// NetworkConfig as first deployed, every field added since is appended after `bump`
+#[derive(AnchorDeserialize)]
+struct LegacyNetworkConfig {
+    authority: Pubkey,
+    cid_config: String,
+    genesis_hash: [u8; 32],
+    task_count: u64,
+    required_validations: u32,
+    allowed_models: Vec<u64>,
+    approved_confidential_nodes: Vec<Pubkey>,
+    approved_public_nodes: Vec<Pubkey>,
+    agent_count: u64,
+    session_count: u64,
+    approved_code_measurements: Vec<CodeMeasurement>,
+    bump: u8,
+}
+
+impl From<LegacyNetworkConfig> for NetworkConfig {
+    /// Appended fields take the values initialize_network gives a new network
+    fn from(legacy: LegacyNetworkConfig) -> Self {
+        Self {
+            authority: legacy.authority,
+            cid_config: legacy.cid_config,
+            genesis_hash: legacy.genesis_hash,
+            task_count: legacy.task_count,
+            required_validations: legacy.required_validations,
+            allowed_models: legacy.allowed_models,
+            approved_confidential_nodes: legacy.approved_confidential_nodes,
+            approved_public_nodes: legacy.approved_public_nodes,
+            agent_count: legacy.agent_count,
+            session_count: legacy.session_count,
+            approved_code_measurements: legacy.approved_code_measurements,
+            bump: legacy.bump,
+            agent_required_validations: legacy.required_validations,
+            confidential_public_fallback: false,
+            reject_duplicate_results: false,
+            agent_validators_must_be_confidential: false,
+            validation_stall_slots: 0,
+            validation_window_slots: 0,
+            min_slots_between_claims: 0,
+            min_slots_between_iterations: 0,
+            settlement_hold_slots: 0,
+            abandonment_slots: 0,
+            rejection_reward: 0,
+            validator_share_bps: 0,
+            protocol_share_bps: 0,
+            max_sessions_per_agent: 0,
+            payment_mint: None,
+            default_task_type: TaskType::Completion(0),
+            allowed_cid_prefixes: Vec::new(),
+            agent_creation_allowlist: Vec::new(),
+            node_registration_count: 0,
+            genesis_nonce: None,
+            debug_validation_logs: false,
+            max_vault_balance: None,
+            processing_window_slots: 0,
+            min_measurement_version: None,
+            paused: false,
+            max_ownership_bps: 0,
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct MigrateNetworkConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Deserialized by hand once grown, an account from an older layout can be too
+    /// short for `Account<NetworkConfig>`
+    #[account(
+        mut,
+        seeds = [b"dac_network_config", authority.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub network_config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateNetworkConfig<'info> {
+    /// Rewrite a config still in the first deployed layout into the current one, growing the
+    /// account and topping up rent from the authority. The legacy fields are read explicitly
+    /// rather than trusting the bytes past them, which may hold stale data from a longer
+    /// serialization. A config already at the current size is only checked and rewritten.
+    pub fn migrate_network_config(&mut self) -> Result<()> {
+        let info = self.network_config.to_account_info();
+        let previous_space = info.data_len();
+        let space = 8 + NetworkConfig::INIT_SPACE;
+
+        let network_config = if previous_space < space {
+            let data = info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *NetworkConfig::DISCRIMINATOR,
+                ErrorCode::InvalidPDAAccount
+            );
+            let legacy = LegacyNetworkConfig::deserialize(&mut &data[8..])?;
+            NetworkConfig::from(legacy)
+        } else {
+            NetworkConfig::try_deserialize(&mut &info.try_borrow_data()?[..])?
+        };
+        require_keys_eq!(
+            network_config.authority,
+            self.authority.key(),
+            ErrorCode::InvalidAuthority
+        );
+
+        if previous_space < space {
+            let rent_exempt_minimum = Rent::get()?.minimum_balance(space);
+            let top_up = rent_exempt_minimum.saturating_sub(info.lamports());
+            if top_up > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: self.authority.to_account_info(),
+                    to: info.clone(),
+                };
+                let cpi_context =
+                    CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_context, top_up)?;
+            }
+            info.resize(space)?;
+            info.try_borrow_mut_data()?.fill(0);
+        }
+        network_config.try_serialize(&mut *info.try_borrow_mut_data()?)?;
+
+        emit!(NetworkConfigMigrated {
+            previous_space: previous_space as u64,
+            space: info.data_len() as u64,
+        });
+
+        Ok(())
+    }
+}
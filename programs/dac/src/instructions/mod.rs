@@ -1,35 +1,103 @@
 pub mod activate_node;
+pub mod authority_invalidate_task;
+pub mod authority_reject_node;
+pub mod batch_withdraw_node_rewards;
+pub mod cancel_session;
 pub mod claim_compute_node;
 pub mod claim_confidential_node;
 pub mod claim_task;
 pub mod contribute_to_session;
 pub mod create_agent;
 pub mod create_session;
+pub mod disable_node;
+pub mod dispute_task_settlement;
+pub mod extend_tasks;
+pub mod force_resolve_task;
+pub mod get_network_stats;
+pub mod get_node_summary;
+pub mod get_node_treasury_balance;
+pub mod get_session_exposure;
+pub mod get_share_precision;
+pub mod get_validation_tally;
 pub mod initialize_network;
+pub mod is_node_approved;
+pub mod list_code_measurements;
+pub mod merge_sessions;
+pub mod migrate_account;
+pub mod migrate_network_config;
+pub mod propose_session_action;
+pub mod prune_rejected_nodes;
+pub mod reclaim_cancelled_task;
+pub mod reconcile_session_locks;
 pub mod register_node;
+pub mod release_task_settlement;
+pub mod seed_task_input;
+pub mod set_accepting_contributions;
+pub mod set_completion_hook;
+pub mod set_node_capacity;
 pub mod set_session;
 pub mod submit_task;
+pub mod submit_task_checkpoint;
 pub mod submit_task_result;
 pub mod submit_task_validation;
+pub mod sweep_abandoned_session;
+pub mod update_agent_memory;
 pub mod update_network_config;
+pub mod update_session_limits;
 pub mod validate_agent;
 pub mod validate_public_node;
+pub mod verify_genesis;
+pub mod vote_session_action;
 pub mod withdraw_from_session;
 
 pub use activate_node::*;
+pub use authority_invalidate_task::*;
+pub use authority_reject_node::*;
+pub use batch_withdraw_node_rewards::*;
+pub use cancel_session::*;
 pub use claim_compute_node::*;
 pub use claim_confidential_node::*;
 pub use claim_task::*;
 pub use contribute_to_session::*;
 pub use create_agent::*;
 pub use create_session::*;
+pub use disable_node::*;
+pub use dispute_task_settlement::*;
+pub use extend_tasks::*;
+pub use force_resolve_task::*;
+pub use get_network_stats::*;
+pub use get_node_summary::*;
+pub use get_node_treasury_balance::*;
+pub use get_session_exposure::*;
+pub use get_share_precision::*;
+pub use get_validation_tally::*;
 pub use initialize_network::*;
+pub use is_node_approved::*;
+pub use list_code_measurements::*;
+pub use merge_sessions::*;
+pub use migrate_account::*;
+pub use migrate_network_config::*;
+pub use propose_session_action::*;
+pub use prune_rejected_nodes::*;
+pub use reclaim_cancelled_task::*;
+pub use reconcile_session_locks::*;
 pub use register_node::*;
+pub use release_task_settlement::*;
+pub use seed_task_input::*;
+pub use set_accepting_contributions::*;
+pub use set_completion_hook::*;
+pub use set_node_capacity::*;
 pub use set_session::*;
 pub use submit_task::*;
+pub use submit_task_checkpoint::*;
 pub use submit_task_result::*;
 pub use submit_task_validation::*;
+pub use sweep_abandoned_session::*;
+pub use update_agent_memory::*;
 pub use update_network_config::*;
+pub use update_session_limits::*;
 pub use validate_agent::*;
 pub use validate_public_node::*;
+pub use verify_genesis::*;
+pub use vote_session_action::*;
 pub use withdraw_from_session::*;
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SESSION_PROPOSAL_SLOTS;
+use crate::errors::ErrorCode;
+use crate::events::{SessionActionExecuted, SessionActionProposed};
+use crate::state::{
+    Agent, Contribution, NetworkConfig, Session, SessionAction, SessionProposal, SessionStatus,
+};
+
+#[derive(Accounts)]
+pub struct ProposeSessionAction<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"contribution", session.key().as_ref(), proposer.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = 8 + SessionProposal::INIT_SPACE,
+        seeds = [b"session_proposal", session.key().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, SessionProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ProposeSessionAction<'info> {
+    /// Open a contributor vote on pausing or cancelling the session. The proposer's shares
+    /// count as the first vote, so a majority holder executes the action right away.
+    pub fn propose_session_action(
+        &mut self,
+        action: SessionAction,
+        bumps: &ProposeSessionActionBumps,
+    ) -> Result<()> {
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            action != SessionAction::Pause || !self.session.paused,
+            ErrorCode::SessionPaused
+        );
+
+        let slot = Clock::get()?.slot;
+        // A fresh account has no session yet, a previous proposal may be replaced once closed
+        if self.proposal.session != Pubkey::default() {
+            require!(
+                !self.proposal.is_open(slot, SESSION_PROPOSAL_SLOTS),
+                ErrorCode::SessionProposalActive
+            );
+        }
+
+        self.proposal.set_inner(SessionProposal {
+            session: self.session.key(),
+            proposer: self.proposer.key(),
+            action,
+            votes_shares: 0,
+            voters: Vec::new(),
+            created_slot: slot,
+            executed: false,
+            bump: bumps.proposal,
+        });
+
+        let shares = self.contribution.shares;
+        self.proposal.record_vote(self.proposer.key(), shares)?;
+
+        emit!(SessionActionProposed {
+            session_slot_id: self.session.session_slot_id,
+            proposer: self.proposer.key(),
+            action,
+            shares,
+        });
+
+        if self
+            .proposal
+            .try_execute(&mut self.session, &mut self.agent)?
+        {
+            emit!(SessionActionExecuted {
+                session_slot_id: self.session.session_slot_id,
+                action,
+                votes_shares: self.proposal.votes_shares,
+                total_shares: self.session.total_shares,
+            });
+        }
+
+        Ok(())
+    }
+}
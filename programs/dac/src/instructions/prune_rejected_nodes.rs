@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::errors::ErrorCode;
+use crate::state::{NetworkConfig, NodeInfo, NodeStatus};
+
+#[derive(Accounts)]
+pub struct PruneRejectedNodes<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PruneRejectedNodes<'info> {
+    /// Close rejected node infos passed as (node_info, node_treasury, owner) triples in remaining
+    /// accounts, sweeping the treasury and returning the rent to the owner that registered them
+    pub fn prune_rejected_nodes(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % 3 == 0,
+            ErrorCode::MissingAccount
+        );
+
+        for triple in remaining_accounts.chunks(3) {
+            let (node_info_account, treasury_account, owner_account) =
+                (&triple[0], &triple[1], &triple[2]);
+
+            let node_info: Account<NodeInfo> = Account::try_from(node_info_account)?;
+            let (node_info_pda, _) = Pubkey::find_program_address(
+                &[b"node_info", node_info.node_pubkey.as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(node_info_pda, node_info.key(), ErrorCode::InvalidPDAAccount);
+            require!(
+                node_info.status == NodeStatus::Rejected,
+                ErrorCode::InvalidNodeStatus
+            );
+            require_keys_eq!(
+                node_info.owner,
+                owner_account.key(),
+                ErrorCode::InvalidNodeOwner
+            );
+            require_keys_eq!(
+                node_info.node_treasury,
+                treasury_account.key(),
+                ErrorCode::InvalidPDAAccount
+            );
+            require!(node_info.current_tasks == 0, ErrorCode::NodeTasksInFlight);
+
+            // The treasury is never reused after a prune, so it goes to the owner in full
+            let amount = treasury_account.lamports();
+            if amount > 0 {
+                let node_info_key = node_info.key();
+                let registration_epoch_bytes = node_info.registration_epoch.to_le_bytes();
+                let (_, treasury_bump) = Pubkey::find_program_address(
+                    &[
+                        b"node_treasury",
+                        node_info_key.as_ref(),
+                        registration_epoch_bytes.as_ref(),
+                    ],
+                    &crate::ID,
+                );
+                let treasury_seeds = &[
+                    b"node_treasury",
+                    node_info_key.as_ref(),
+                    registration_epoch_bytes.as_ref(),
+                    &[treasury_bump],
+                ];
+                let treasury_signer = &[&treasury_seeds[..]];
+
+                let cpi_accounts = system_program::Transfer {
+                    from: treasury_account.clone(),
+                    to: owner_account.clone(),
+                };
+                let cpi_context = CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    cpi_accounts,
+                    treasury_signer,
+                );
+                system_program::transfer(cpi_context, amount)?;
+            }
+
+            node_info.close(owner_account.clone())?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::CancelledTaskReclaimed;
+use crate::state::{NetworkConfig, NodeInfo, Session, SessionStatus, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct ReclaimCancelledTask<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+}
+
+impl<'info> ReclaimCancelledTask<'info> {
+    /// Release the lock of a task left in flight by a cancelled session so contributors can
+    /// withdraw it. The claimant keeps a task still processing until its processing deadline,
+    /// and a submitted result keeps its lock while validators may still settle it.
+    pub fn reclaim_cancelled_task(&mut self) -> Result<()> {
+        require!(
+            self.session.status == SessionStatus::Cancelled,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            self.session.task == self.task.key()
+                && self.task.session_slot_id == Some(self.session.session_slot_id),
+            ErrorCode::InvalidSession
+        );
+        require!(
+            self.task.compute_node == Some(self.node_info.node_pubkey),
+            ErrorCode::InvalidComputeNodePubkey
+        );
+        match self.task.status {
+            TaskStatus::Processing => require!(
+                self.task.processing_expired(Clock::get()?.slot),
+                ErrorCode::ProcessingWindowOpen
+            ),
+            TaskStatus::AwaitingValidation => require!(
+                self.validation_window_closed()?,
+                ErrorCode::ValidationWindowOpen
+            ),
+            _ => return err!(ErrorCode::InvalidTaskStatus),
+        }
+
+        let released_amount = self.task.max_task_cost;
+        self.session.release_task_lock(released_amount)?;
+        self.node_info.finish_task()?;
+
+        self.task.discard_pending_result();
+        self.task.checkpoint_cid = None;
+        self.task.validations.clear();
+        self.task.validation_deadline_slot = 0;
+        self.task.processing_deadline_slot = 0;
+        self.task.status = TaskStatus::Ready;
+        self.session.record_activity()?;
+
+        emit!(CancelledTaskReclaimed {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            compute_node: self.node_info.node_pubkey,
+            released_amount,
+            locked_for_tasks: self.session.locked_for_tasks,
+        });
+
+        Ok(())
+    }
+
+    /// Without a validation deadline the network's stall window, counted from the result's
+    /// submission, bounds how long validators hold the lock. With neither set it never closes.
+    fn validation_window_closed(&self) -> Result<bool> {
+        let slot = Clock::get()?.slot;
+        if self.task.validation_deadline_slot != 0 {
+            return Ok(self.task.validation_expired(slot));
+        }
+        if self.network_config.validation_stall_slots == 0 {
+            return Ok(false);
+        }
+
+        let stalled_at = self
+            .task
+            .validation_started_slot
+            .checked_add(self.network_config.validation_stall_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(slot >= stalled_at)
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SessionLocksReconciled;
+use crate::state::{NetworkConfig, Session, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct ReconcileSessionLocks<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+}
+
+impl<'info> ReconcileSessionLocks<'info> {
+    /// Recompute locked_for_tasks from the session's in-flight tasks passed as remaining accounts
+    pub fn reconcile_session_locks(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let network_config_key = self.network_config.key();
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(remaining_accounts.len());
+        let mut locked_for_tasks: u64 = 0;
+
+        for task_account_info in remaining_accounts {
+            require!(
+                !seen.contains(task_account_info.key),
+                ErrorCode::InvalidPDAAccount
+            );
+            seen.push(task_account_info.key());
+
+            let task: Account<Task> = Account::try_from(task_account_info)?;
+            let (task_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"task",
+                    network_config_key.as_ref(),
+                    &task.task_slot_id.to_le_bytes(),
+                ],
+                &crate::ID,
+            );
+            require_keys_eq!(task_pda, task.key(), ErrorCode::InvalidPDAAccount);
+            require!(
+                task.session_slot_id == Some(self.session.session_slot_id),
+                ErrorCode::InvalidSession
+            );
+
+            if task.status == TaskStatus::Processing
+                || task.status == TaskStatus::AwaitingValidation
+            {
+                locked_for_tasks = locked_for_tasks
+                    .checked_add(task.max_task_cost)
+                    .ok_or(ErrorCode::Overflow)?;
+            }
+        }
+
+        require!(seen.contains(&self.session.task), ErrorCode::MissingAccount);
+
+        let previous_locked_for_tasks = self.session.locked_for_tasks;
+        self.session.locked_for_tasks = locked_for_tasks;
+
+        emit!(SessionLocksReconciled {
+            session_slot_id: self.session.session_slot_id,
+            previous_locked_for_tasks,
+            locked_for_tasks,
+        });
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 
-use crate::state::{NetworkConfig, NodeInfo, NodeStatus, NodeType};
+use crate::state::{NetworkConfig, NodeInfo, NodeStatus, NodeType, TASK_TYPE_COUNT};
 
 #[derive(Accounts)]
 #[instruction(node_pubkey: Pubkey)]
@@ -25,7 +25,11 @@ pub struct RegisterNode<'info> {
     pub node_info: Account<'info, NodeInfo>,
     #[account(
         mut,
-        seeds = [b"node_treasury", node_info.key().as_ref()],
+        seeds = [
+            b"node_treasury",
+            node_info.key().as_ref(),
+            network_config.next_node_registration_epoch().to_le_bytes().as_ref()
+        ],
         bump,
     )]
     pub node_treasury: SystemAccount<'info>,
@@ -40,6 +44,8 @@ impl<'info> RegisterNode<'info> {
         node_type: NodeType,
         bumps: &RegisterNodeBumps,
     ) -> Result<()> {
+        let registration_epoch = self.network_config.next_node_registration_epoch();
+
         self.node_info.set_inner(NodeInfo {
             owner: self.owner.key(),
             node_pubkey: node_pubkey,
@@ -49,17 +55,26 @@ impl<'info> RegisterNode<'info> {
             code_measurement: None,
             tee_signing_pubkey: None,
             node_treasury: self.node_treasury.key(),
+            registration_epoch,
             total_earned: 0,
             total_tasks_completed: 0,
+            total_validations: 0,
+            last_claim_slot: None,
+            earned_by_type: [0; TASK_TYPE_COUNT],
+            max_concurrent_tasks: 0,
+            current_tasks: 0,
+            agreed_validations: 0,
             approved_validators: Vec::new(),
             rejected_validators: Vec::new(),
             bump: bumps.node_info,
         });
 
         let node_info_key = self.node_info.key();
+        let registration_epoch_bytes = registration_epoch.to_le_bytes();
         let treasury_seeds = &[
             b"node_treasury",
             node_info_key.as_ref(),
+            registration_epoch_bytes.as_ref(),
             &[bumps.node_treasury],
         ];
         let treasury_signer = &[&treasury_seeds[..]];
@@ -81,6 +96,8 @@ impl<'info> RegisterNode<'info> {
             &system_program::ID,
         )?;
 
+        self.network_config.increment_node_registration_count()?;
+
         Ok(())
     }
 }
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TaskSettlementReleased;
+use crate::state::{NodeInfo, TaskSettlement};
+
+#[derive(Accounts)]
+pub struct ReleaseTaskSettlement<'info> {
+    #[account(mut)]
+    pub node: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"node_info", node.key().as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+
+    #[account(
+        mut,
+        address = node_info.node_treasury @ ErrorCode::InvalidPDAAccount,
+    )]
+    pub node_treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = node_info @ ErrorCode::InvalidPDAAccount,
+        has_one = payer @ ErrorCode::InvalidPDAAccount,
+        seeds = [b"task_settlement", task_settlement.task.as_ref(), task_settlement.task_index.to_le_bytes().as_ref()],
+        bump = task_settlement.bump,
+    )]
+    pub task_settlement: Account<'info, TaskSettlement>,
+
+    /// CHECK: Session vault that paid the escrow rent, checked against the settlement
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+impl<'info> ReleaseTaskSettlement<'info> {
+    /// Sweep a held payment into the node treasury once its hold has passed
+    pub fn release_task_settlement(&mut self) -> Result<()> {
+        require!(
+            !self.task_settlement.is_held(Clock::get()?.slot),
+            ErrorCode::SettlementHeld
+        );
+
+        let amount = self.task_settlement.amount;
+        self.task_settlement.sub_lamports(amount)?;
+        self.node_treasury.add_lamports(amount)?;
+
+        self.node_info
+            .record_task_payment(amount, &self.task_settlement.task_type)?;
+
+        emit!(TaskSettlementReleased {
+            task: self.task_settlement.task,
+            task_index: self.task_settlement.task_index,
+            node: self.node.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
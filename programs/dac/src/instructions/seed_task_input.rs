@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TaskInputSeeded;
+use crate::state::{NetworkConfig, Session, SessionStatus, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct SeedTaskInput<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> SeedTaskInput<'info> {
+    /// Set the input the first step of the session must consume, the agent's starting prompt.
+    /// The owner may revise the task's advisory compute estimate alongside it.
+    pub fn seed_task_input(
+        &mut self,
+        input_cid: String,
+        estimated_compute_units: Option<u64>,
+    ) -> Result<()> {
+        require_keys_eq!(
+            self.session.owner,
+            self.owner.key(),
+            ErrorCode::InvalidSessionOwner
+        );
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+        require_keys_eq!(self.session.task, self.task.key(), ErrorCode::InvalidSession);
+        // Later steps consume what the previous one produced
+        require!(
+            self.session.current_iteration == 0,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            self.task.status != TaskStatus::Processing
+                && self.task.status != TaskStatus::AwaitingValidation,
+            ErrorCode::InvalidTaskStatus
+        );
+        self.network_config.validate_cid(&input_cid)?;
+
+        self.task.next_input_cid = Some(input_cid.clone());
+        if let Some(estimated_compute_units) = estimated_compute_units {
+            self.task.estimated_compute_units = estimated_compute_units;
+        }
+
+        emit!(TaskInputSeeded {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            input_cid,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SessionContributionsToggled;
+use crate::state::{NetworkConfig, Session, SessionStatus};
+
+#[derive(Accounts)]
+pub struct SetAcceptingContributions<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> SetAcceptingContributions<'info> {
+    /// Open or close the session to third-party contributions, so the owner isn't diluted
+    /// at a share price they disagree with
+    pub fn set_accepting_contributions(&mut self, accepting_contributions: bool) -> Result<()> {
+        require_keys_eq!(
+            self.session.owner,
+            self.owner.key(),
+            ErrorCode::InvalidSessionOwner
+        );
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        self.session.accepting_contributions = accepting_contributions;
+
+        emit!(SessionContributionsToggled {
+            session_slot_id: self.session.session_slot_id,
+            accepting_contributions,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::CompletionHookSet;
+use crate::state::{NetworkConfig, Session, SessionStatus};
+
+#[derive(Accounts)]
+pub struct SetCompletionHook<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> SetCompletionHook<'info> {
+    /// Replace the program invoked when the session completes, None removes it.
+    /// A failing hook fails the completing validation, so a new hook can't be set while a
+    /// task is in flight, only the current one cleared.
+    pub fn set_completion_hook(&mut self, completion_hook_program: Option<Pubkey>) -> Result<()> {
+        require_keys_eq!(
+            self.session.owner,
+            self.owner.key(),
+            ErrorCode::InvalidSessionOwner
+        );
+        require!(
+            matches!(
+                self.session.status,
+                SessionStatus::Pending | SessionStatus::Active
+            ),
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            completion_hook_program != Some(crate::ID),
+            ErrorCode::InvalidCompletionHook
+        );
+        require!(
+            completion_hook_program.is_none() || self.session.locked_for_tasks == 0,
+            ErrorCode::SessionTasksInFlight
+        );
+
+        let previous_completion_hook_program = self.session.completion_hook_program;
+        self.session.completion_hook_program = completion_hook_program;
+
+        emit!(CompletionHookSet {
+            session_slot_id: self.session.session_slot_id,
+            previous_completion_hook_program,
+            completion_hook_program,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::NodeCapacitySet;
+use crate::state::NodeInfo;
+
+#[derive(Accounts)]
+pub struct SetNodeCapacity<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::InvalidNodeOwner,
+        seeds = [b"node_info", node_info.node_pubkey.as_ref()],
+        bump = node_info.bump,
+    )]
+    pub node_info: Account<'info, NodeInfo>,
+}
+
+impl<'info> SetNodeCapacity<'info> {
+    /// Cap how many claimed tasks the node holds at once, 0 lifts the cap. Lowering it under the
+    /// current load only blocks new claims until enough of them finish.
+    pub fn set_node_capacity(&mut self, max_concurrent_tasks: u32) -> Result<()> {
+        self.node_info.max_concurrent_tasks = max_concurrent_tasks;
+
+        emit!(NodeCapacitySet {
+            node: self.node_info.node_pubkey,
+            max_concurrent_tasks,
+            current_tasks: self.node_info.current_tasks,
+        });
+
+        Ok(())
+    }
+}
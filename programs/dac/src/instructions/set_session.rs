@@ -1,12 +1,33 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
 
+use crate::constants::{MAX_OWNER_FEE_BPS, MAX_SPECIFICATION_CID_LEN};
 use crate::errors::ErrorCode;
 use crate::events::SessionSet;
-use crate::state::{Agent, AgentStatus, Contribution, Session, SessionStatus, Task, TaskStatus};
+use crate::state::{
+    Agent, AgentStatus, Contribution, Session, SessionStatus, Task, TaskStatus,
+    MAX_ALLOWED_COMPUTE_NODES,
+};
+use crate::utils::{
+    session_token_accounts, share_price, shares_for_amount, transfer_to_session_token_vault,
+};
 use crate::NetworkConfig;
 use crate::TaskType;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SetSessionArgs {
+    pub specification_cid: String,
+    pub max_iterations: u64,
+    pub initial_deposit: u64,
+    pub compute_node: Pubkey,
+    pub task_type: TaskType,
+    pub max_output_size: Option<u64>,
+    pub owner_fee_bps: Option<u16>,
+    pub estimated_compute_units: Option<u64>,
+    pub allowed_compute_nodes: Option<Vec<Pubkey>>,
+}
+
 #[derive(Accounts)]
 pub struct SetSession<'info> {
     #[account(mut)]
@@ -27,7 +48,7 @@ pub struct SetSession<'info> {
     pub vault: SystemAccount<'info>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = owner,
         space = 8 + Contribution::INIT_SPACE,
         seeds = [b"contribution", session.key().as_ref(), owner.key().as_ref()],
@@ -43,6 +64,7 @@ pub struct SetSession<'info> {
     pub task: Account<'info, Task>,
 
     #[account(
+        mut,
         seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
         bump = agent.bump,
     )]
@@ -54,27 +76,52 @@ pub struct SetSession<'info> {
     )]
     pub network_config: Account<'info, NetworkConfig>,
 
+    /// Only used when the network has a payment mint
+    #[account(mut)]
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SetSession<'info> {
-    pub fn set_session(
-        &mut self,
-        specification_cid: String,
-        max_iterations: u64,
-        initial_deposit: u64,
-        compute_node: Pubkey,
-        task_type: TaskType,
-        bumps: &SetSessionBumps,
-    ) -> Result<()> {
+    pub fn set_session(&mut self, args: SetSessionArgs, bumps: &SetSessionBumps) -> Result<()> {
+        let SetSessionArgs {
+            specification_cid,
+            max_iterations,
+            initial_deposit,
+            compute_node,
+            task_type,
+            max_output_size,
+            owner_fee_bps,
+            estimated_compute_units,
+            allowed_compute_nodes,
+        } = args;
+
         require!(
-            self.session.status == SessionStatus::Pending,
+            self.session.status == SessionStatus::Pending
+                || self.session.status == SessionStatus::Completed,
             ErrorCode::InvalidSessionStatus
         );
         require!(
             self.session.owner == Pubkey::default() || self.session.owner == self.owner.key(),
             ErrorCode::InvalidSessionOwner
         );
+        // Contributors fund a crowdfunded session up front and it stays unowned once set
+        let crowdfunded = self.session.is_crowdfunding();
+        if self.session.owner == Pubkey::default() {
+            if let Some(allowed_owner) = self.session.allowed_owner {
+                require_keys_eq!(
+                    allowed_owner,
+                    self.owner.key(),
+                    ErrorCode::InvalidSessionOwner
+                );
+            }
+        }
         require!(
             self.task.status == TaskStatus::Ready,
             ErrorCode::InvalidTaskStatus
@@ -83,7 +130,36 @@ impl<'info> SetSession<'info> {
             self.agent.status == AgentStatus::Active,
             ErrorCode::InvalidAgentStatus
         );
-        require!(initial_deposit > 0, ErrorCode::DepositTooSmall);
+        // A reused session keeps running the agent it was first set with
+        require!(
+            self.session.agent == Pubkey::default() || self.session.agent == self.agent.key(),
+            ErrorCode::InvalidSession
+        );
+        if crowdfunded {
+            require!(
+                initial_deposit == 0 && owner_fee_bps.is_none(),
+                ErrorCode::CrowdfundedSessionDeposit
+            );
+        } else {
+            require!(initial_deposit > 0, ErrorCode::DepositTooSmall);
+        }
+        require!(
+            owner_fee_bps.unwrap_or(0) <= MAX_OWNER_FEE_BPS,
+            ErrorCode::OwnerFeeTooHigh
+        );
+        // Checked up front so an oversized CID fails clearly instead of at serialization
+        require!(
+            specification_cid.len() <= MAX_SPECIFICATION_CID_LEN,
+            ErrorCode::InvalidCID
+        );
+        self.network_config.validate_cid(&specification_cid)?;
+        if let TaskType::Completion(model_id) = task_type {
+            require!(
+                self.network_config.allowed_models.is_empty()
+                    || self.network_config.allowed_models.contains(&model_id),
+                ErrorCode::NotAllowedModel
+            );
+        }
 
         let approved = if self.session.is_confidential {
             &self.network_config.approved_confidential_nodes
@@ -94,74 +170,148 @@ impl<'info> SetSession<'info> {
             approved.contains(&compute_node),
             ErrorCode::InvalidComputeNodePubkey
         );
-
-        // Check if vault only has rent lamports (no leftover SOL from previous goal)
-        let rent = Rent::get()?;
-        let rent_exempt_minimum = rent.minimum_balance(0);
-        let vault_balance = self.vault.lamports();
+        let allowed_compute_nodes = allowed_compute_nodes.unwrap_or_default();
         require!(
-            vault_balance == rent_exempt_minimum || vault_balance == 0,
-            ErrorCode::VaultHasLeftoverFunds
+            allowed_compute_nodes.len() <= MAX_ALLOWED_COMPUTE_NODES,
+            ErrorCode::TooManyAllowedComputeNodes
         );
 
-        if self.session.current_iteration > 0 {
+        // Reuse of a completed session, its contributors must have been paid out or merged away
+        if self.session.status == SessionStatus::Completed {
+            require!(
+                self.session.total_shares == 0,
+                ErrorCode::SessionHasOutstandingShares
+            );
+            // The task keeps counting across sessions so its chain proof stays continuous
+            require!(
+                self.task.task_index >= self.session.task_index_end,
+                ErrorCode::TaskIndexRegression
+            );
             self.session.current_iteration = 0;
-            self.session.task_index_start = self.session.task_index_end;
             self.session.task_index_end = 0;
-            self.session.total_shares = 0;
             self.session.locked_for_tasks = 0;
+            self.session.total_paid = 0;
+            self.session.last_iteration_slot = None;
+            self.session.paused = false;
         }
 
-        let session_key = self.session.key();
-        let vault_seeds = &[b"session_vault", session_key.as_ref(), &[bumps.vault]];
-        let vault_signer = &[&vault_seeds[..]];
-
-        if vault_balance == 0 {
-            // Vault doesn't exist
-            let required_lamports = rent_exempt_minimum;
-            let transfer_amount = initial_deposit
-                .checked_add(required_lamports)
-                .ok_or(ErrorCode::Overflow)?;
-
-            let cpi_accounts = system_program::CreateAccount {
-                from: self.owner.to_account_info(),
-                to: self.vault.to_account_info(),
-            };
-            let cpi_context = CpiContext::new_with_signer(
-                self.system_program.to_account_info(),
-                cpi_accounts,
-                vault_signer,
+        let shares = if crowdfunded {
+            // Any contributor may set the session on behalf of the others
+            require!(
+                self.owner_contribution.shares > 0,
+                ErrorCode::NoVotingShares
             );
-
-            system_program::create_account(cpi_context, transfer_amount, 0, &system_program::ID)?;
+            let funded = self
+                .vault
+                .lamports()
+                .checked_sub(Rent::get()?.minimum_balance(0))
+                .ok_or(ErrorCode::FundingThresholdNotReached)?;
+            require!(
+                funded >= self.session.funding_threshold.unwrap_or(0),
+                ErrorCode::FundingThresholdNotReached
+            );
+            self.session.total_shares
         } else {
-            // Vault exists with only rent
-            let cpi_accounts = system_program::Transfer {
-                from: self.owner.to_account_info(),
-                to: self.vault.to_account_info(),
-            };
-            let cpi_context = CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
-            system_program::transfer(cpi_context, initial_deposit)?;
-        }
+            let token_accounts = session_token_accounts(
+                self.network_config.payment_mint,
+                &self.session.key(),
+                &self.session_token_vault,
+                &self.owner_token_account,
+                &self.token_program,
+            )?;
 
-        // Mint shares for owner's initial deposit
-        // First deposit always uses share_price = 1.0
-        let share_price = 1.0_f64;
-        let shares = (initial_deposit as f64 / share_price) as u64;
-        require!(shares > 0, ErrorCode::Overflow);
-
-        self.owner_contribution.set_inner(Contribution {
-            session: self.session.key(),
-            contributor: self.owner.key(),
-            shares,
-            refund_amount: 0,
-            bump: bumps.owner_contribution,
-        });
+            if let Some(token_accounts) = &token_accounts {
+                require!(
+                    token_accounts.vault.amount == 0,
+                    ErrorCode::VaultHasLeftoverFunds
+                );
+                transfer_to_session_token_vault(
+                    token_accounts,
+                    self.owner.to_account_info(),
+                    initial_deposit,
+                )?;
+            } else {
+                // Check if vault only has rent lamports (no leftover SOL from previous goal)
+                let rent = Rent::get()?;
+                let rent_exempt_minimum = rent.minimum_balance(0);
+                let vault_balance = self.vault.lamports();
+                require!(
+                    vault_balance == rent_exempt_minimum || vault_balance == 0,
+                    ErrorCode::VaultHasLeftoverFunds
+                );
+
+                let session_key = self.session.key();
+                let vault_seeds = &[b"session_vault", session_key.as_ref(), &[bumps.vault]];
+                let vault_signer = &[&vault_seeds[..]];
 
-        self.session.owner = self.owner.key();
+                if vault_balance == 0 {
+                    // Vault doesn't exist
+                    let required_lamports = rent_exempt_minimum;
+                    let transfer_amount = initial_deposit
+                        .checked_add(required_lamports)
+                        .ok_or(ErrorCode::Overflow)?;
+
+                    let cpi_accounts = system_program::CreateAccount {
+                        from: self.owner.to_account_info(),
+                        to: self.vault.to_account_info(),
+                    };
+                    let cpi_context = CpiContext::new_with_signer(
+                        self.system_program.to_account_info(),
+                        cpi_accounts,
+                        vault_signer,
+                    );
+
+                    system_program::create_account(
+                        cpi_context,
+                        transfer_amount,
+                        0,
+                        &system_program::ID,
+                    )?;
+                } else {
+                    // Vault exists with only rent
+                    let cpi_accounts = system_program::Transfer {
+                        from: self.owner.to_account_info(),
+                        to: self.vault.to_account_info(),
+                    };
+                    let cpi_context =
+                        CpiContext::new(self.system_program.to_account_info(), cpi_accounts);
+                    system_program::transfer(cpi_context, initial_deposit)?;
+                }
+            }
+
+            // Mint shares for owner's initial deposit
+            // First deposit always uses share_price = 1.0
+            let shares = shares_for_amount(initial_deposit, share_price(0, 0)?)?;
+            require!(shares > 0, ErrorCode::Overflow);
+
+            self.owner_contribution.set_inner(Contribution {
+                session: self.session.key(),
+                contributor: self.owner.key(),
+                shares,
+                refund_amount: 0,
+                is_owner: true,
+                bump: bumps.owner_contribution,
+            });
+
+            shares
+        };
+
+        self.agent
+            .open_session(self.network_config.max_sessions_per_agent)?;
+
+        if !crowdfunded {
+            self.session.owner = self.owner.key();
+            // A completed crowdfunded session reused by an owner is owner-controlled from now on
+            self.session.funding_threshold = None;
+        }
         self.session.task = self.task.key();
+        self.session.agent = self.agent.key();
         self.session.specification_cid = specification_cid;
         self.session.max_iterations = max_iterations;
+        self.session.max_output_size = max_output_size;
+        self.session.owner_fee_bps = owner_fee_bps;
+        self.session.allowed_compute_nodes = allowed_compute_nodes;
+        self.session.record_activity()?;
         self.session.total_shares = shares;
         self.session.status = SessionStatus::Active;
         self.session.vault_bump = bumps.vault;
@@ -170,14 +320,21 @@ impl<'info> SetSession<'info> {
         self.task.compute_node = Some(compute_node);
         self.task.status = TaskStatus::Ready;
         self.task.task_type = task_type;
+        self.task.estimated_compute_units = estimated_compute_units.unwrap_or(0);
+        // A new session starts its own chain of steps
+        self.task.next_input_cid = None;
+        self.task.checkpoint_cid = None;
 
         emit!(SessionSet {
             session_slot_id: self.session.session_slot_id,
-            owner: self.owner.key(),
+            owner: self.session.owner,
             task_slot_id: self.task.task_slot_id,
             specification_cid: self.session.specification_cid.clone(),
             max_iterations: self.session.max_iterations,
             initial_deposit,
+            compute_node,
+            total_shares: self.session.total_shares,
+            task_index_start: self.session.task_index_start,
         });
 
         Ok(())
@@ -43,6 +43,8 @@ impl<'info> SubmitTask<'info> {
         require!(self.session.status == SessionStatus::Active, ErrorCode::InvalidSessionStatus);
         require!(self.task.session_slot_id == Some(self.session.session_slot_id), ErrorCode::InvalidSession);
 
+        self.network_config.validate_cid(&input_cid)?;
+
         self.task.pending_input_cid = Some(input_cid.clone());
         self.task.status = TaskStatus::Pending;
 
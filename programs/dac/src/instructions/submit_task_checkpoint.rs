@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::TaskCheckpointed;
+use crate::state::{NetworkConfig, Session, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct SubmitTaskCheckpoint<'info> {
+    pub compute_node: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> SubmitTaskCheckpoint<'info> {
+    /// Store intermediate state of the claimed execution without finalizing it, so the node
+    /// can resume from it after a restart. The next submitted result clears it.
+    pub fn submit_task_checkpoint(&mut self, checkpoint_cid: String) -> Result<()> {
+        require!(
+            self.task.status == TaskStatus::Processing,
+            ErrorCode::InvalidTaskStatus
+        );
+        require!(
+            self.task.compute_node == Some(self.compute_node.key()),
+            ErrorCode::InvalidComputeNodePubkey
+        );
+        require!(
+            self.session.task == self.task.key()
+                && self.task.session_slot_id == Some(self.session.session_slot_id),
+            ErrorCode::InvalidSession
+        );
+        self.network_config.validate_cid(&checkpoint_cid)?;
+
+        self.task.checkpoint_cid = Some(checkpoint_cid.clone());
+        self.session.record_activity()?;
+
+        emit!(TaskCheckpointed {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            task_index: self.task.task_index,
+            compute_node: self.compute_node.key(),
+            checkpoint_cid,
+        });
+
+        Ok(())
+    }
+}
@@ -36,6 +36,7 @@ impl<'info> SubmitTaskResult<'info> {
         input_cid: String,
         output_cid: String,
         state_cid: Option<String>,
+        next_input_cid: Option<String>,
         call_count: u64,
     ) -> Result<()> {
         require!(
@@ -47,22 +48,66 @@ impl<'info> SubmitTaskResult<'info> {
             ErrorCode::InvalidComputeNodePubkey
         );
         require!(self.task.session_slot_id == Some(self.session.session_slot_id), ErrorCode::InvalidSession);
+        require!(self.session.task == self.task.key(), ErrorCode::InvalidSession);
+        require!(
+            call_count <= self.task.max_call_count,
+            ErrorCode::CallCountExceeded
+        );
+
+        self.network_config.validate_cid(&input_cid)?;
+        self.network_config.validate_cid(&output_cid)?;
+        if let Some(state_cid) = &state_cid {
+            self.network_config.validate_cid(state_cid)?;
+        }
+        if let Some(next_input_cid) = &next_input_cid {
+            self.network_config.validate_cid(next_input_cid)?;
+        }
+
+        // A chained step must consume the input the previous step produced
+        if let Some(expected_input_cid) = &self.task.next_input_cid {
+            require!(
+                *expected_input_cid == input_cid,
+                ErrorCode::InputCidMismatch
+            );
+        }
+
+        if self.network_config.reject_duplicate_results {
+            require!(
+                self.task.input_cid.as_deref() != Some(input_cid.as_str())
+                    || self.task.output_cid.as_deref() != Some(output_cid.as_str()),
+                ErrorCode::DuplicateResult
+            );
+        }
 
         // Store current execution results as pending (awaiting validation)
         self.task.pending_input_cid = Some(input_cid.clone());
         self.task.pending_output_cid = Some(output_cid.clone());
+        self.task.pending_next_input_cid = next_input_cid;
+        self.task.pending_result_digest = self.task.hash_pending_result();
+        // The result supersedes the checkpoint it was resumed from
+        let checkpoint_cid = self.task.checkpoint_cid.take();
         self.task.call_count = call_count;
         self.task.status = TaskStatus::AwaitingValidation;
+        self.task.processing_deadline_slot = 0;
+        let slot = Clock::get()?.slot;
+        self.task.validation_started_slot = slot;
+        self.task.validation_deadline_slot = match self.network_config.validation_window_slots {
+            0 => 0,
+            window => slot.checked_add(window).ok_or(ErrorCode::Overflow)?,
+        };
 
         if let Some(state_cid_str) = state_cid {
             self.session.state_cid = Some(state_cid_str);
         }
+        self.session.record_activity()?;
 
         emit!(TaskResultSubmitted {
             session_slot_id: self.session.session_slot_id,
             task_slot_id: self.task.task_slot_id,
             input_cid,
             output_cid,
+            call_count,
+            checkpoint_cid,
         });
 
         Ok(())
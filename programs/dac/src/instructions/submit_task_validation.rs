@@ -1,15 +1,19 @@
 use anchor_lang::prelude::borsh::{BorshDeserialize, BorshSerialize};
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
 use sha2::{Digest, Sha256};
 
 use crate::errors::ErrorCode;
-use crate::events::{SessionCompleted, TaskValidationSubmitted};
+use crate::events::{BatchValidationSubmitted, SessionCompleted, TaskValidationSubmitted};
 use crate::state::{
-    NetworkConfig, NodeInfo, NodeStatus, NodeType, Session, SessionStatus, Task, TaskStatus,
-    ValidationStatus,
+    Agent, NetworkConfig, NodeInfo, NodeStatus, NodeType, Session, SessionStatus, Task,
+    TaskStatus, ValidationStatus,
+};
+use crate::utils::{
+    check_validation_threshold, invoke_completion_hook, session_vault_balance, settle_task_payment,
+    split_completion_hook, to_hex, validator_treasuries, verify_tee_signature, TaskPaymentAccounts,
 };
-use crate::utils::{check_validation_threshold, verify_tee_signature};
 
 #[derive(InitSpace, BorshSerialize, BorshDeserialize)]
 pub struct SubmitTaskValidationMessage {
@@ -19,6 +23,7 @@ pub struct SubmitTaskValidationMessage {
     pub validation_proof: [u8; 32],
     pub approved: bool,
     pub session_completed: bool,
+    pub output_size: u64,
 }
 
 #[derive(Accounts)]
@@ -47,6 +52,14 @@ pub struct SubmitTaskValidation<'info> {
     )]
     pub task: Account<'info, Task>,
 
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
     #[account(
         mut,
         seeds = [b"node_info", node_info.node_pubkey.key().as_ref()],
@@ -56,11 +69,18 @@ pub struct SubmitTaskValidation<'info> {
 
     #[account(
         mut,
-        seeds = [b"node_treasury", node_info.key().as_ref()],
+        seeds = [b"node_treasury", node_info.key().as_ref(), node_info.registration_epoch.to_le_bytes().as_ref()],
         bump,
     )]
     pub node_treasury: SystemAccount<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury", network_config.key().as_ref()],
+        bump,
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"node_info", node_validating.key().as_ref()],
@@ -78,11 +98,27 @@ pub struct SubmitTaskValidation<'info> {
     #[account(address = anchor_lang::solana_program::sysvar::instructions::id())]
     pub instruction_sysvar: AccountInfo<'info>,
 
+    /// Only used when the network has a payment mint
+    #[account(mut)]
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub node_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Escrow PDA created for the compute node payment when the network holds settlements
+    #[account(mut)]
+    pub task_settlement: Option<UncheckedAccount<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SubmitTaskValidation<'info> {
-    pub fn submit_confidential_task_validation(&mut self) -> Result<()> {
+    pub fn submit_confidential_task_validation(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
         self.validate_common_requirements()?;
 
         require!(self.session.is_confidential, ErrorCode::InvalidSessionStatus);
@@ -90,10 +126,11 @@ impl<'info> SubmitTaskValidation<'info> {
         let message = self.verify_confidential_validation()?;
 
         if message.approved {
-            self.process_approved_validation(&message)?;
+            self.process_approved_validation(&message, remaining_accounts)?;
         } else {
-            self.process_rejected_validation()?;
+            self.process_rejected_validation(remaining_accounts)?;
         }
+        self.session.record_activity()?;
 
         Ok(())
     }
@@ -103,16 +140,130 @@ impl<'info> SubmitTaskValidation<'info> {
         payment_amount: u64,
         approved: bool,
         goal_completed: bool,
+        remaining_accounts: &'info [AccountInfo<'info>],
     ) -> Result<()> {
         self.validate_common_requirements()?;
+        self.validate_public_requirements()?;
+
+        if approved {
+            let message = SubmitTaskValidationMessage {
+                goal_id: self.session.session_slot_id,
+                task_slot_id: self.task.task_slot_id,
+                payment_amount,
+                validation_proof: [0; 32],
+                approved,
+                session_completed: goal_completed,
+                output_size: 0,
+            };
+            self.process_approved_validation(&message, remaining_accounts)?;
+        } else {
+            self.process_rejected_validation(remaining_accounts)?;
+        }
+        self.session.record_activity()?;
 
-        require!(!self.session.is_confidential, ErrorCode::InvalidSessionStatus);
+        Ok(())
+    }
 
+    /// Apply the approvals of several public validators in one transaction. The first
+    /// `2 * co_signers` remaining accounts are (validator, validator node_info) pairs, every
+    /// validator signing the transaction. The rest are the (node_info, treasury) pairs the
+    /// approval needs once it reaches the threshold.
+    pub fn submit_batch_public_validation(
+        &mut self,
+        payment_amount: u64,
+        goal_completed: bool,
+        co_signers: u8,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        self.validate_common_requirements()?;
+        self.validate_public_requirements()?;
+
+        let vote_account_count = co_signers as usize * 2;
+        require!(
+            remaining_accounts.len() >= vote_account_count,
+            ErrorCode::MissingAccount
+        );
+        let (vote_accounts, validator_accounts) = remaining_accounts.split_at(vote_account_count);
+
+        let mut validators = vec![self.node_validating.key()];
+        for pair in vote_accounts.chunks(2) {
+            let (validator, validator_node_info_account) = (&pair[0], &pair[1]);
+            require!(validator.is_signer, ErrorCode::MissingValidatorSignature);
+            // The coordinator's own vote is applied once, below
+            require_keys_neq!(
+                validator.key(),
+                self.node_validating.key(),
+                ErrorCode::DuplicateValidation
+            );
+
+            let mut validator_node_info: Account<NodeInfo> =
+                Account::try_from(validator_node_info_account)?;
+            let (expected_node_info, _) =
+                Pubkey::find_program_address(&[b"node_info", validator.key().as_ref()], &crate::ID);
+            require_keys_eq!(
+                validator_node_info.key(),
+                expected_node_info,
+                ErrorCode::InvalidPDAAccount
+            );
+            require!(
+                validator_node_info.status == NodeStatus::Active,
+                ErrorCode::InvalidNodeStatus
+            );
+            require!(
+                validator_node_info.node_type == NodeType::Public,
+                ErrorCode::InvalidNodeType
+            );
+
+            let validator_entry = self
+                .task
+                .validations
+                .iter_mut()
+                .find(|v| v.pubkey == validator.key())
+                .ok_or(ErrorCode::ValidatorNotAssigned)?;
+            require!(
+                validator_entry.status == ValidationStatus::Pending,
+                ErrorCode::DuplicateValidation
+            );
+            validator_entry.status = ValidationStatus::Approved;
+
+            // Written back now, settlement reads this node_info again from the fee pairs
+            validator_node_info.record_validation()?;
+            validator_node_info.exit(&crate::ID)?;
+            validators.push(validator.key());
+        }
+
+        emit!(BatchValidationSubmitted {
+            session_slot_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            validators,
+        });
+
+        let message = SubmitTaskValidationMessage {
+            goal_id: self.session.session_slot_id,
+            task_slot_id: self.task.task_slot_id,
+            payment_amount,
+            validation_proof: [0; 32],
+            approved: true,
+            session_completed: goal_completed,
+            output_size: 0,
+        };
+        self.process_approved_validation(&message, validator_accounts)?;
+        self.session.record_activity()?;
+
+        Ok(())
+    }
+
+    /// Checks the submitting validator must pass to vote on a public task
+    fn validate_public_requirements(&self) -> Result<()> {
         require!(
-            self.validator_node_info.node_type == NodeType::Public
-                || self.validator_node_info.node_type == NodeType::Confidential,
+            !self.session.is_confidential || self.network_config.confidential_public_fallback,
+            ErrorCode::InvalidSessionStatus
+        );
+        require!(
+            self.validator_node_info.node_type == NodeType::Public,
             ErrorCode::InvalidNodeType
         );
+
         let validator_pubkey = self.node_validating.key();
         let validator_entry = self
             .task
@@ -125,24 +276,14 @@ impl<'info> SubmitTaskValidation<'info> {
             ErrorCode::DuplicateValidation
         );
 
-        if approved {
-            let message = SubmitTaskValidationMessage {
-                goal_id: self.session.session_slot_id,
-                task_slot_id: self.task.task_slot_id,
-                payment_amount,
-                validation_proof: [0; 32],
-                approved,
-                session_completed: goal_completed,
-            };
-            self.process_approved_validation(&message)?;
-        } else {
-            self.process_rejected_validation()?;
-        }
-
         Ok(())
     }
 
     fn validate_common_requirements(&self) -> Result<()> {
+        require!(
+            self.session.task == self.task.key(),
+            ErrorCode::InvalidSession
+        );
         require!(
             self.validator_node_info.status == NodeStatus::Active,
             ErrorCode::InvalidNodeStatus
@@ -151,18 +292,36 @@ impl<'info> SubmitTaskValidation<'info> {
             self.node_info.status == NodeStatus::Active,
             ErrorCode::InvalidNodeStatus
         );
+        // A cancelled session still settles the result its in-flight task submitted
         require!(
-            self.session.status == SessionStatus::Active,
+            self.session.status == SessionStatus::Active
+                || self.session.status == SessionStatus::Cancelled,
             ErrorCode::InvalidSessionStatus
         );
         require!(
             self.task.status == TaskStatus::AwaitingValidation,
             ErrorCode::InvalidTaskStatus
         );
+        require!(
+            !self.task.validation_expired(Clock::get()?.slot),
+            ErrorCode::ValidationDeadlinePassed
+        );
         require!(
             self.task.compute_node == Some(self.node_info.node_pubkey),
             ErrorCode::InvalidComputeNodePubkey
         );
+        require_keys_neq!(
+            self.node_validating.key(),
+            self.node_info.node_pubkey,
+            ErrorCode::SelfValidationForbidden
+        );
+
+        // Confidential sessions only ever run on confidential nodes, public sessions on public ones.
+        // Validator node types are enforced per validation path.
+        require!(
+            self.node_info.node_type == self.session.required_node_type(),
+            ErrorCode::InvalidNodeType
+        );
 
         Ok(())
     }
@@ -192,6 +351,12 @@ impl<'info> SubmitTaskValidation<'info> {
             ErrorCode::InvalidValidatorMessage
         );
         require!(message.payment_amount > 0, ErrorCode::Overflow);
+        if let Some(max_output_size) = self.session.max_output_size {
+            require!(
+                message.output_size <= max_output_size,
+                ErrorCode::OutputTooLarge
+            );
+        }
 
         // Verify validation_proof matches expected proof
         self.verify_validation_proof(&message)?;
@@ -224,20 +389,43 @@ impl<'info> SubmitTaskValidation<'info> {
             .as_ref()
             .ok_or(ErrorCode::InvalidPDAAccount)?;
 
+        // Bind the proof to this task instance so it can't be replayed on another task
         let mut hasher = Sha256::new();
-        hasher.update(pending_input_cid.as_bytes());
-        hasher.update(pending_output_cid.as_bytes());
+        hasher.update(b"DAC_VALIDATION_PROOF");
+        hasher.update(&self.session.session_slot_id.to_le_bytes());
+        hasher.update(&self.task.task_slot_id.to_le_bytes());
+        hasher.update(self.task.task_index.to_le_bytes());
+        self.task
+            .task_type
+            .hash_validation_result(&mut hasher, pending_input_cid, pending_output_cid);
         let expected_proof: [u8; 32] = hasher.finalize().into();
 
-        require!(
-            message.validation_proof == expected_proof,
-            ErrorCode::InvalidTeeSignature
-        );
+        if message.validation_proof != expected_proof {
+            if self.network_config.debug_validation_logs {
+                msg!(
+                    "Validation proof mismatch: expected {} received {}",
+                    to_hex(&expected_proof),
+                    to_hex(&message.validation_proof)
+                );
+            }
+            return err!(ErrorCode::InvalidTeeSignature);
+        }
 
         Ok(())
     }
 
-    fn process_approved_validation(&mut self, message: &SubmitTaskValidationMessage) -> Result<()> {
+    /// On an approval reaching the threshold, remaining accounts hold the (node_info, treasury)
+    /// pairs of the approving validators when the network pays them a share of the payment,
+    /// followed by the session's completion hook program when it has one
+    fn process_approved_validation(
+        &mut self,
+        message: &SubmitTaskValidationMessage,
+        remaining_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        let (validator_accounts, completion_hook) =
+            split_completion_hook(&self.session, remaining_accounts);
+        self.validator_node_info.record_validation()?;
+
         let validator_pubkey = self.node_validating.key();
         if let Some(v) = self
             .task
@@ -247,12 +435,7 @@ impl<'info> SubmitTaskValidation<'info> {
         {
             v.status = ValidationStatus::Approved;
         }
-        let approved_count = self
-            .task
-            .validations
-            .iter()
-            .filter(|v| v.status == ValidationStatus::Approved)
-            .count() as u32;
+        let approved_count = self.task.count_validations(ValidationStatus::Approved);
         let threshold_reached =
             check_validation_threshold(approved_count, self.network_config.required_validations)?;
 
@@ -260,88 +443,64 @@ impl<'info> SubmitTaskValidation<'info> {
             return Ok(());
         }
 
-        // Update task chain_proof
-        let old_input_cid = self
-            .task
-            .input_cid
-            .as_ref()
-            .map(|s| s.as_bytes())
-            .unwrap_or(&[]);
-        let old_output_cid = self
-            .task
-            .output_cid
-            .as_ref()
-            .map(|s| s.as_bytes())
-            .unwrap_or(&[]);
-
-        let mut hasher = Sha256::new();
-        hasher.update(&self.task.chain_proof);
-        hasher.update(old_input_cid);
-        hasher.update(old_output_cid);
-        hasher.update(&self.task.task_index.to_le_bytes());
-        self.task.chain_proof = hasher.finalize().into();
-
-        // Move pending to validated (these become the historical record)
-        self.task.input_cid = self.task.pending_input_cid.take();
-        self.task.output_cid = self.task.pending_output_cid.take();
+        // Update task chain_proof and move pending to validated (these become the historical record)
+        self.task.commit_pending_result()?;
+        self.task.output_size = message.output_size;
 
         // Release locked funds
-        self.session.locked_for_tasks = self
-            .session
-            .locked_for_tasks
-            .checked_sub(self.task.max_task_cost)
-            .ok_or(ErrorCode::Underflow)?;
+        self.session.release_task_lock(self.task.max_task_cost)?;
+        self.node_info.finish_task()?;
 
-        // Pay compute node
+        // The node claimed the task at this price, validators can't pay past it
         require!(
-            self.vault.lamports() >= message.payment_amount,
-            ErrorCode::InsufficientBalance
+            message.payment_amount <= self.task.max_task_cost,
+            ErrorCode::PaymentExceedsPolicy
         );
-
-        let session_key = self.session.key();
-        let vault_seeds = &[b"session_vault", session_key.as_ref(), &[self.session.vault_bump]];
-        let vault_signer = &[&vault_seeds[..]];
-
-        let cpi_accounts = system_program::Transfer {
-            from: self.vault.to_account_info(),
-            to: self.node_treasury.to_account_info(),
-        };
-        let cpi_context = CpiContext::new_with_signer(
-            self.system_program.to_account_info(),
-            cpi_accounts,
-            vault_signer,
+        // Pay against the balance snapshotted at claim, not the live vault
+        require!(
+            message.payment_amount <= self.task.claim_available_balance,
+            ErrorCode::InsufficientBalance
         );
-
-        system_program::transfer(cpi_context, message.payment_amount)?;
-
-        self.node_info.total_earned = self
-            .node_info
-            .total_earned
-            .checked_add(message.payment_amount)
-            .ok_or(ErrorCode::Overflow)?;
-        self.node_info.total_tasks_completed = self
-            .node_info
-            .total_tasks_completed
-            .checked_add(1)
-            .ok_or(ErrorCode::Overflow)?;
-
-        self.session.current_iteration = self
+        // Written back so the fee weight read below includes this vote's agreement
+        self.validator_node_info.record_agreement()?;
+        self.validator_node_info.exit(&crate::ID)?;
+        settle_task_payment(
+            &mut TaskPaymentAccounts {
+                network_config: &self.network_config,
+                session: &self.session,
+                vault: &self.vault,
+                task: &self.task,
+                node_info: &mut self.node_info,
+                node_treasury: &self.node_treasury,
+                protocol_treasury: &self.protocol_treasury,
+                session_token_vault: &mut self.session_token_vault,
+                node_token_account: &self.node_token_account,
+                token_program: &self.token_program,
+                task_settlement: &self.task_settlement,
+                system_program: &self.system_program,
+            },
+            message.payment_amount,
+            validator_accounts,
+            Some(self.node_validating.key()),
+        )?;
+
+        self.session.record_payment(message.payment_amount)?;
+
+        if self.session.status == SessionStatus::Cancelled {
+            // Paid for the step it let finish, but no further step runs
+            self.task.status = TaskStatus::Ready;
+        } else if self
             .session
-            .current_iteration
-            .checked_add(1)
-            .ok_or(ErrorCode::Overflow)?;
-
-        if message.session_completed
-            || (self.session.max_iterations != 0
-                && self.session.current_iteration >= self.session.max_iterations)
+            .complete_iteration(message.session_completed, self.task.task_index)?
         {
-            self.session.status = SessionStatus::Completed;
             self.task.status = TaskStatus::Ready;
+            self.agent.close_session()?;
+            invoke_completion_hook(&self.session, completion_hook)?;
 
             emit!(SessionCompleted {
                 session_slot_id: self.session.session_slot_id,
                 final_iteration: self.session.current_iteration,
-                vault_balance: self.vault.lamports(),
+                vault_balance: self.vault_balance(),
             });
         } else {
             self.task.status = TaskStatus::Pending;
@@ -358,14 +517,21 @@ impl<'info> SubmitTaskValidation<'info> {
             approved: message.approved,
             session_completed: message.session_completed,
             current_iteration: self.session.current_iteration,
-            vault_balance: self.vault.lamports(),
+            vault_balance: self.vault_balance(),
             locked_for_tasks: self.session.locked_for_tasks,
+            total_paid_this_session: self.session.total_paid,
+            iterations_remaining: self.session.iterations_remaining(),
         });
 
         Ok(())
     }
 
-    fn process_rejected_validation(&mut self) -> Result<()> {
+    fn process_rejected_validation(
+        &mut self,
+        validator_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        self.validator_node_info.record_validation()?;
+
         let validator_pubkey = self.node_validating.key();
         if let Some(v) = self
             .task
@@ -375,12 +541,7 @@ impl<'info> SubmitTaskValidation<'info> {
         {
             v.status = ValidationStatus::Rejected;
         }
-        let rejected_count = self
-            .task
-            .validations
-            .iter()
-            .filter(|v| v.status == ValidationStatus::Rejected)
-            .count() as u32;
+        let rejected_count = self.task.count_validations(ValidationStatus::Rejected);
         let threshold_reached =
             check_validation_threshold(rejected_count, self.network_config.required_validations)?;
 
@@ -388,20 +549,90 @@ impl<'info> SubmitTaskValidation<'info> {
             return Ok(());
         }
 
+        self.validator_node_info.record_agreement()?;
+        self.pay_rejection_rewards(validator_accounts)?;
+
         // Release task lock
-        self.session.locked_for_tasks = self
-            .session
-            .locked_for_tasks
-            .checked_sub(self.task.max_task_cost)
-            .ok_or(ErrorCode::Underflow)?;
+        self.session.release_task_lock(self.task.max_task_cost)?;
+        self.node_info.finish_task()?;
 
         // Clear pending fields (task will be reset for next claim)
-        self.task.pending_input_cid = None;
-        self.task.pending_output_cid = None;
+        self.task.discard_pending_result();
+        // A leaked pending result would be validated against the next claim's work
+        require!(
+            self.task.pending_input_cid.is_none() && self.task.pending_output_cid.is_none(),
+            ErrorCode::StalePendingResult
+        );
         self.task.status = TaskStatus::Ready;
 
         self.task.validations.clear();
 
         Ok(())
     }
+
+    /// Pay the rejection reward from the protocol treasury to every validator that rejected.
+    /// Their (node_info, treasury) pairs are passed in remaining accounts, in the task's
+    /// validation order.
+    fn pay_rejection_rewards(&self, validator_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let rejection_reward = self.network_config.rejection_reward;
+        if rejection_reward == 0 {
+            return Ok(());
+        }
+
+        let rejecting_validators: Vec<Pubkey> = self
+            .task
+            .validations
+            .iter()
+            .filter(|v| v.status == ValidationStatus::Rejected)
+            .map(|v| v.pubkey)
+            .collect();
+        let treasuries = validator_treasuries(
+            &rejecting_validators,
+            validator_accounts,
+            Some(self.node_validating.key()),
+        )?;
+
+        // An underfunded treasury must not block the rejection itself
+        let total_reward = rejection_reward
+            .checked_mul(rejecting_validators.len() as u64)
+            .ok_or(ErrorCode::Overflow)?;
+        let available = self
+            .protocol_treasury
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        if total_reward > available {
+            return Ok(());
+        }
+
+        let network_config_key = self.network_config.key();
+        let (_, treasury_bump) = Pubkey::find_program_address(
+            &[b"protocol_treasury", network_config_key.as_ref()],
+            &crate::ID,
+        );
+        let treasury_seeds = &[
+            b"protocol_treasury",
+            network_config_key.as_ref(),
+            &[treasury_bump],
+        ];
+        let treasury_signer = &[&treasury_seeds[..]];
+
+        for (validator_treasury, _) in treasuries {
+            let cpi_accounts = system_program::Transfer {
+                from: self.protocol_treasury.to_account_info(),
+                to: validator_treasury.clone(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                cpi_accounts,
+                treasury_signer,
+            );
+            system_program::transfer(cpi_context, rejection_reward)?;
+        }
+
+        Ok(())
+    }
+
+    fn vault_balance(&self) -> u64 {
+        session_vault_balance(&self.network_config, &self.vault, &self.session_token_vault)
+    }
 }
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::errors::ErrorCode;
+use crate::events::AbandonedSessionSwept;
+use crate::state::{Agent, NetworkConfig, Session, SessionStatus};
+use crate::utils::{session_token_accounts, transfer_from_session_token_vault};
+
+#[derive(Accounts)]
+pub struct SweepAbandonedSession<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ ErrorCode::InvalidAuthority,
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"session_vault", session.key().as_ref()],
+        bump = session.vault_bump,
+    )]
+    pub vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_treasury", network_config.key().as_ref()],
+        bump,
+    )]
+    pub protocol_treasury: SystemAccount<'info>,
+
+    /// Only used when the network has a payment mint
+    #[account(mut)]
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SweepAbandonedSession<'info> {
+    /// Move the whole vault of a long inactive session to the protocol treasury and close it out
+    pub fn sweep_abandoned_session(&mut self) -> Result<()> {
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+
+        let abandonment_slots = self.network_config.abandonment_slots;
+        let abandoned_at = self
+            .session
+            .last_activity_slot
+            .checked_add(abandonment_slots)
+            .ok_or(ErrorCode::Overflow)?;
+        require!(
+            abandonment_slots > 0 && Clock::get()?.slot >= abandoned_at,
+            ErrorCode::SessionNotAbandoned
+        );
+        // A claimed task still holds its lock and its node's capacity, it has to be resolved first
+        require!(
+            self.session.locked_for_tasks == 0,
+            ErrorCode::SessionTasksInFlight
+        );
+
+        let token_accounts = session_token_accounts(
+            self.network_config.payment_mint,
+            &self.session.key(),
+            &self.session_token_vault,
+            &self.treasury_token_account,
+            &self.token_program,
+        )?;
+
+        let amount = if let Some(token_accounts) = &token_accounts {
+            require_keys_eq!(
+                token_accounts.counterparty.key(),
+                get_associated_token_address(
+                    &self.protocol_treasury.key(),
+                    &token_accounts.vault.mint
+                ),
+                ErrorCode::InvalidTokenAccount
+            );
+
+            let amount = token_accounts.vault.amount;
+            transfer_from_session_token_vault(
+                token_accounts,
+                &self.session,
+                &self.network_config.key(),
+                amount,
+            )?;
+            amount
+        } else {
+            // Drain the vault entirely, including its rent
+            let amount = self.vault.lamports();
+            let session_key = self.session.key();
+            let vault_seeds = &[b"session_vault", session_key.as_ref(), &[self.session.vault_bump]];
+            let vault_signer = &[&vault_seeds[..]];
+
+            let cpi_accounts = system_program::Transfer {
+                from: self.vault.to_account_info(),
+                to: self.protocol_treasury.to_account_info(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                cpi_accounts,
+                vault_signer,
+            );
+            system_program::transfer(cpi_context, amount)?;
+            amount
+        };
+
+        // Outstanding shares are no longer redeemable. Contributions still hold them, so the
+        // session must never be set, merged or withdrawn from again
+        self.session.status = SessionStatus::Swept;
+        self.agent.close_session()?;
+        self.session.total_shares = 0;
+
+        emit!(AbandonedSessionSwept {
+            session_slot_id: self.session.session_slot_id,
+            amount,
+            last_activity_slot: self.session.last_activity_slot,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::AgentMemoryUpdated;
+use crate::state::{Agent, NetworkConfig, Session, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct UpdateAgentMemory<'info> {
+    pub compute_node: Signer<'info>,
+
+    #[account(
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> UpdateAgentMemory<'info> {
+    /// Record the agent's memory snapshot once the node's last iteration has been validated
+    pub fn update_agent_memory(&mut self, memory_cid: String) -> Result<()> {
+        require!(
+            self.task.compute_node == Some(self.compute_node.key()),
+            ErrorCode::InvalidComputeNodePubkey
+        );
+        require!(
+            self.session.task == self.task.key()
+                && self.task.session_slot_id == Some(self.session.session_slot_id),
+            ErrorCode::InvalidSession
+        );
+        // Only between iterations, after a validated result and before the next claim
+        require!(
+            matches!(self.task.status, TaskStatus::Pending | TaskStatus::Ready)
+                && self.task.output_cid.is_some(),
+            ErrorCode::InvalidTaskStatus
+        );
+        self.network_config.validate_cid(&memory_cid)?;
+
+        self.agent.agent_memory_cid = Some(memory_cid.clone());
+
+        emit!(AgentMemoryUpdated {
+            agent_slot_id: self.agent.agent_slot_id,
+            session_slot_id: self.session.session_slot_id,
+            task_index: self.task.task_index,
+            memory_cid,
+        });
+
+        Ok(())
+    }
+}
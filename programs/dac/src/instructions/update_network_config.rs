@@ -1,7 +1,42 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::BASIS_POINTS;
 use crate::errors::ErrorCode;
-use crate::state::{CodeMeasurement, NetworkConfig};
+use crate::state::{
+    CodeMeasurement, NetworkConfig, MAX_AGENT_CREATORS, MAX_AGENT_VALIDATORS, MAX_CID_PREFIXES,
+    MAX_CID_PREFIX_LEN,
+};
+use crate::utils::SemanticVersion;
+
+/// Settings to change, a field left as None keeps its current value
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateNetworkConfigArgs {
+    pub cid_config: Option<String>,
+    pub new_code_measurement: Option<CodeMeasurement>,
+    pub confidential_public_fallback: Option<bool>,
+    pub reject_duplicate_results: Option<bool>,
+    pub agent_required_validations: Option<u32>,
+    pub validation_stall_slots: Option<u64>,
+    pub payment_mint: Option<Pubkey>,
+    pub allowed_cid_prefixes: Option<Vec<String>>,
+    pub abandonment_slots: Option<u64>,
+    pub rejection_reward: Option<u64>,
+    pub agent_creation_allowlist: Option<Vec<Pubkey>>,
+    pub max_sessions_per_agent: Option<u64>,
+    pub validation_window_slots: Option<u64>,
+    pub min_slots_between_claims: Option<u64>,
+    pub validator_share_bps: Option<u16>,
+    pub protocol_share_bps: Option<u16>,
+    pub agent_validators_must_be_confidential: Option<bool>,
+    pub settlement_hold_slots: Option<u64>,
+    pub min_slots_between_iterations: Option<u64>,
+    pub debug_validation_logs: Option<bool>,
+    pub max_vault_balance: Option<u64>,
+    pub processing_window_slots: Option<u64>,
+    pub min_measurement_version: Option<SemanticVersion>,
+    pub paused: Option<bool>,
+    pub max_ownership_bps: Option<u16>,
+}
 
 #[derive(Accounts)]
 pub struct UpdateNetworkConfig<'info> {
@@ -18,11 +53,35 @@ pub struct UpdateNetworkConfig<'info> {
 }
 
 impl<'info> UpdateNetworkConfig<'info> {
-    pub fn update_network_config(
-        &mut self,
-        cid_config: Option<String>,
-        new_code_measurement: Option<CodeMeasurement>,
-    ) -> Result<()> {
+    pub fn update_network_config(&mut self, args: UpdateNetworkConfigArgs) -> Result<()> {
+        let UpdateNetworkConfigArgs {
+            cid_config,
+            new_code_measurement,
+            confidential_public_fallback,
+            reject_duplicate_results,
+            agent_required_validations,
+            validation_stall_slots,
+            payment_mint,
+            allowed_cid_prefixes,
+            abandonment_slots,
+            rejection_reward,
+            agent_creation_allowlist,
+            max_sessions_per_agent,
+            validation_window_slots,
+            min_slots_between_claims,
+            validator_share_bps,
+            protocol_share_bps,
+            agent_validators_must_be_confidential,
+            settlement_hold_slots,
+            min_slots_between_iterations,
+            debug_validation_logs,
+            max_vault_balance,
+            processing_window_slots,
+            min_measurement_version,
+            paused,
+            max_ownership_bps,
+        } = args;
+
         if let Some(new_cid_config) = cid_config {
             require!(new_cid_config.len() <= 128, ErrorCode::InvalidCID);
             self.network_config.cid_config = new_cid_config;
@@ -36,10 +95,136 @@ impl<'info> UpdateNetworkConfig<'info> {
                 .any(|m| m.measurement == measurement.measurement)
             {
                 self.network_config
-                    .add_code_measurement(measurement.measurement, measurement.version);
+                    .add_code_measurement(measurement.measurement, measurement.version)?;
             }
         }
 
+        if let Some(fallback) = confidential_public_fallback {
+            self.network_config.confidential_public_fallback = fallback;
+        }
+
+        if let Some(reject_duplicate_results) = reject_duplicate_results {
+            self.network_config.reject_duplicate_results = reject_duplicate_results;
+        }
+
+        if let Some(agent_required_validations) = agent_required_validations {
+            // An agent holds at most MAX_AGENT_VALIDATORS approvals, more could never be met
+            require!(
+                (1..=MAX_AGENT_VALIDATORS).contains(&(agent_required_validations as usize)),
+                ErrorCode::InvalidRequiredValidations
+            );
+            self.network_config.agent_required_validations = agent_required_validations;
+        }
+
+        if let Some(validation_stall_slots) = validation_stall_slots {
+            self.network_config.validation_stall_slots = validation_stall_slots;
+        }
+
+        // The default pubkey clears the mint, back to SOL payments
+        if let Some(payment_mint) = payment_mint {
+            // Switching denomination would strand balances held by existing session vaults
+            require!(
+                self.network_config.session_count == 0,
+                ErrorCode::PaymentMintLocked
+            );
+            self.network_config.payment_mint =
+                (payment_mint != Pubkey::default()).then_some(payment_mint);
+        }
+
+        if let Some(allowed_cid_prefixes) = allowed_cid_prefixes {
+            require!(
+                allowed_cid_prefixes.len() <= MAX_CID_PREFIXES
+                    && allowed_cid_prefixes
+                        .iter()
+                        .all(|prefix| !prefix.is_empty() && prefix.len() <= MAX_CID_PREFIX_LEN),
+                ErrorCode::InvalidCID
+            );
+            self.network_config.allowed_cid_prefixes = allowed_cid_prefixes;
+        }
+
+        if let Some(abandonment_slots) = abandonment_slots {
+            self.network_config.abandonment_slots = abandonment_slots;
+        }
+
+        if let Some(rejection_reward) = rejection_reward {
+            self.network_config.rejection_reward = rejection_reward;
+        }
+
+        if let Some(agent_creation_allowlist) = agent_creation_allowlist {
+            require!(
+                agent_creation_allowlist.len() <= MAX_AGENT_CREATORS,
+                ErrorCode::TooManyAgentCreators
+            );
+            self.network_config.agent_creation_allowlist = agent_creation_allowlist;
+        }
+
+        if let Some(max_sessions_per_agent) = max_sessions_per_agent {
+            self.network_config.max_sessions_per_agent = max_sessions_per_agent;
+        }
+
+        if let Some(validation_window_slots) = validation_window_slots {
+            self.network_config.validation_window_slots = validation_window_slots;
+        }
+
+        if let Some(min_slots_between_claims) = min_slots_between_claims {
+            self.network_config.min_slots_between_claims = min_slots_between_claims;
+        }
+
+        if let Some(validator_share_bps) = validator_share_bps {
+            self.network_config.validator_share_bps = validator_share_bps;
+        }
+
+        if let Some(protocol_share_bps) = protocol_share_bps {
+            self.network_config.protocol_share_bps = protocol_share_bps;
+        }
+
+        if let Some(agent_validators_must_be_confidential) = agent_validators_must_be_confidential {
+            self.network_config.agent_validators_must_be_confidential =
+                agent_validators_must_be_confidential;
+        }
+
+        if let Some(settlement_hold_slots) = settlement_hold_slots {
+            self.network_config.settlement_hold_slots = settlement_hold_slots;
+        }
+
+        if let Some(min_slots_between_iterations) = min_slots_between_iterations {
+            self.network_config.min_slots_between_iterations = min_slots_between_iterations;
+        }
+
+        if let Some(debug_validation_logs) = debug_validation_logs {
+            self.network_config.debug_validation_logs = debug_validation_logs;
+        }
+
+        // 0 lifts the cap
+        if let Some(max_vault_balance) = max_vault_balance {
+            self.network_config.max_vault_balance =
+                (max_vault_balance > 0).then_some(max_vault_balance);
+        }
+
+        if let Some(processing_window_slots) = processing_window_slots {
+            self.network_config.processing_window_slots = processing_window_slots;
+        }
+
+        if let Some(min_measurement_version) = min_measurement_version {
+            self.network_config.min_measurement_version = Some(min_measurement_version);
+        }
+
+        if let Some(paused) = paused {
+            self.network_config.paused = paused;
+        }
+
+        if let Some(max_ownership_bps) = max_ownership_bps {
+            self.network_config.max_ownership_bps = max_ownership_bps;
+        }
+
+        // The compute node keeps whatever the validator and protocol shares leave
+        require!(
+            self.network_config.validator_share_bps as u64
+                + self.network_config.protocol_share_bps as u64
+                <= BASIS_POINTS,
+            ErrorCode::InvalidPaymentSplit
+        );
+
         Ok(())
     }
 }
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::events::SessionLimitsUpdated;
+use crate::state::{NetworkConfig, Session, SessionStatus, Task, TaskStatus};
+
+#[derive(Accounts)]
+pub struct UpdateSessionLimits<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"task", network_config.key().as_ref(), task.task_slot_id.to_le_bytes().as_ref()],
+        bump = task.bump,
+    )]
+    pub task: Account<'info, Task>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> UpdateSessionLimits<'info> {
+    /// Replace the session's task cost ceiling, None removes it
+    pub fn update_session_limits(&mut self, max_task_cost_ceiling: Option<u64>) -> Result<()> {
+        require_keys_eq!(
+            self.session.owner,
+            self.owner.key(),
+            ErrorCode::InvalidSessionOwner
+        );
+        require!(
+            self.session.status != SessionStatus::Completed,
+            ErrorCode::InvalidSessionStatus
+        );
+        require_keys_eq!(self.session.task, self.task.key(), ErrorCode::InvalidSession);
+        // The running claim was locked against the old ceiling
+        require!(
+            self.task.status != TaskStatus::Processing,
+            ErrorCode::InvalidTaskStatus
+        );
+
+        let previous_max_task_cost_ceiling = self.session.max_task_cost_ceiling;
+        self.session.max_task_cost_ceiling = max_task_cost_ceiling;
+
+        emit!(SessionLimitsUpdated {
+            session_slot_id: self.session.session_slot_id,
+            previous_max_task_cost_ceiling,
+            max_task_cost_ceiling,
+        });
+
+        Ok(())
+    }
+}
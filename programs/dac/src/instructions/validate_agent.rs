@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
-use crate::state::{Agent, AgentStatus, NetworkConfig, NodeInfo, NodeStatus};
+use crate::state::{
+    Agent, AgentStatus, NetworkConfig, NodeInfo, NodeStatus, NodeType, MAX_AGENT_VALIDATORS,
+};
 use crate::utils::check_validation_threshold;
 
 #[derive(Accounts)]
@@ -40,17 +42,30 @@ impl<'info> ValidateAgent<'info> {
             self.node_info.status == NodeStatus::Active,
             ErrorCode::InvalidNodeStatus
         );
+        require!(
+            !self.network_config.agent_validators_must_be_confidential
+                || self.node_info.node_type == NodeType::Confidential,
+            ErrorCode::InvalidNodeType
+        );
 
         require!(
             !self.agent.approved_validators.contains(&self.node.key())
                 && !self.agent.rejected_validators.contains(&self.node.key()),
             ErrorCode::DuplicateValidation
         );
+        // A quorum above the cap can't be reached, fail before the account overflows
+        require!(
+            self.agent.approved_validators.len() < MAX_AGENT_VALIDATORS,
+            ErrorCode::TooManyValidators
+        );
 
         self.agent.approved_validators.push(self.node.key());
         let approved_count = self.agent.approved_validators.len() as u32;
 
-        if check_validation_threshold(approved_count, self.network_config.required_validations)? {
+        if check_validation_threshold(
+            approved_count,
+            self.network_config.agent_required_validations,
+        )? {
             self.agent.status = AgentStatus::Active;
         }
 
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 
 use crate::errors::ErrorCode;
+use crate::events::{NodePoolMembershipChanged, PoolMembershipChange};
 use crate::state::{NetworkConfig, NodeInfo, NodeStatus, NodeType};
 use crate::utils::check_validation_threshold;
 
@@ -76,8 +77,16 @@ impl<'info> ValidatePublicNode<'info> {
             )?;
             if threshold_reached {
                 self.node_info.status = NodeStatus::Active;
-                self.network_config
-                    .add_public_node(self.node_info.node_pubkey)?;
+                if self
+                    .network_config
+                    .add_public_node(self.node_info.node_pubkey)?
+                {
+                    emit!(NodePoolMembershipChanged {
+                        node: self.node_info.node_pubkey,
+                        node_type: NodeType::Public,
+                        change: PoolMembershipChange::Added,
+                    });
+                }
             }
         } else {
             self.node_info
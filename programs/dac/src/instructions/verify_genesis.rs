@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::events::GenesisVerified;
+use crate::state::NetworkConfig;
+
+#[derive(Accounts)]
+pub struct VerifyGenesis<'info> {
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> VerifyGenesis<'info> {
+    /// Re-derive the genesis hash from the stored inputs and report whether it matches
+    pub fn verify_genesis(&self) -> Result<()> {
+        let network_config = &self.network_config;
+        let computed_hash = NetworkConfig::compute_genesis_hash(
+            &network_config.authority,
+            network_config.genesis_nonce,
+        )?;
+
+        emit!(GenesisVerified {
+            genesis_hash: network_config.genesis_hash,
+            computed_hash,
+            genesis_nonce: network_config.genesis_nonce,
+            matches: computed_hash == network_config.genesis_hash,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::SESSION_PROPOSAL_SLOTS;
+use crate::errors::ErrorCode;
+use crate::events::{SessionActionExecuted, SessionActionVoted};
+use crate::state::{Agent, Contribution, NetworkConfig, Session, SessionProposal, SessionStatus};
+
+#[derive(Accounts)]
+pub struct VoteSessionAction<'info> {
+    pub voter: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"session", network_config.key().as_ref(), session.session_slot_id.to_le_bytes().as_ref()],
+        bump = session.bump,
+    )]
+    pub session: Account<'info, Session>,
+
+    #[account(
+        seeds = [b"contribution", session.key().as_ref(), voter.key().as_ref()],
+        bump = contribution.bump,
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        seeds = [b"session_proposal", session.key().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, SessionProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", network_config.key().as_ref(), agent.agent_slot_id.to_le_bytes().as_ref()],
+        bump = agent.bump,
+        constraint = agent.key() == session.agent @ ErrorCode::InvalidSession,
+    )]
+    pub agent: Account<'info, Agent>,
+
+    #[account(
+        seeds = [b"dac_network_config", network_config.authority.as_ref()],
+        bump = network_config.bump,
+    )]
+    pub network_config: Account<'info, NetworkConfig>,
+}
+
+impl<'info> VoteSessionAction<'info> {
+    /// Back the open proposal with the voter's current shares, executing it on quorum
+    pub fn vote_session_action(&mut self) -> Result<()> {
+        require!(
+            self.session.status == SessionStatus::Active,
+            ErrorCode::InvalidSessionStatus
+        );
+        let slot = Clock::get()?.slot;
+        require!(
+            self.proposal.is_open(slot, SESSION_PROPOSAL_SLOTS),
+            ErrorCode::ProposalClosed
+        );
+
+        let shares = self.contribution.shares;
+        self.proposal.record_vote(self.voter.key(), shares)?;
+
+        emit!(SessionActionVoted {
+            session_slot_id: self.session.session_slot_id,
+            voter: self.voter.key(),
+            shares,
+            votes_shares: self.proposal.votes_shares,
+            total_shares: self.session.total_shares,
+        });
+
+        if self
+            .proposal
+            .try_execute(&mut self.session, &mut self.agent)?
+        {
+            emit!(SessionActionExecuted {
+                session_slot_id: self.session.session_slot_id,
+                action: self.proposal.action,
+                votes_shares: self.proposal.votes_shares,
+                total_shares: self.session.total_shares,
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token::{Token, TokenAccount};
 
+use crate::constants::SESSION_PROPOSAL_SLOTS;
 use crate::errors::ErrorCode;
-use crate::state::{Contribution, Session, SessionStatus};
+use crate::state::{Contribution, Session, SessionProposal, SessionStatus};
+use crate::utils::{
+    amount_for_shares, session_token_accounts, share_price, transfer_from_session_token_vault,
+};
 use crate::NetworkConfig;
 
 #[derive(Accounts)]
@@ -31,60 +36,116 @@ pub struct WithdrawFromSession<'info> {
     )]
     pub contribution: Account<'info, Contribution>,
 
+    /// CHECK: The session's proposal PDA, empty when no proposal was ever opened
+    #[account(
+        seeds = [b"session_proposal", session.key().as_ref()],
+        bump,
+    )]
+    pub session_proposal: UncheckedAccount<'info>,
+
     #[account(
         seeds = [b"dac_network_config", network_config.authority.as_ref()],
         bump = network_config.bump,
     )]
     pub network_config: Account<'info, NetworkConfig>,
 
+    /// Only used when the network has a payment mint
+    #[account(mut)]
+    pub session_token_vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub contributor_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> WithdrawFromSession<'info> {
     pub fn withdraw_from_session(&mut self, shares_to_burn: u64) -> Result<()> {
+        let cancelled = self.session.status == SessionStatus::Cancelled;
         require!(
-            self.session.status == SessionStatus::Active,
+            self.session.status == SessionStatus::Active
+                || cancelled
+                || self.session.is_crowdfunding(),
             ErrorCode::InvalidSessionStatus
         );
         require!(shares_to_burn > 0, ErrorCode::Overflow);
+        // Owner's deposit funds the first iteration, so it stays locked until one completes
+        // or contributors cancel the session
+        require!(
+            !self.contribution.is_owner || self.session.current_iteration >= 1 || cancelled,
+            ErrorCode::WithdrawalLocked
+        );
         require!(
             self.contribution.shares >= shares_to_burn,
             ErrorCode::Underflow
         );
+        self.check_vote_lock()?;
 
-        // Exclude rent lamports from share price calculation
-        let rent = Rent::get()?;
-        let rent_exempt_minimum = rent.minimum_balance(0);
-        let available_balance = self
-            .vault
-            .lamports()
-            .checked_sub(self.session.locked_for_tasks)
-            .ok_or(ErrorCode::Underflow)?
-            .checked_sub(rent_exempt_minimum)
-            .ok_or(ErrorCode::Underflow)?;
-        let share_price = (available_balance as f64) / (self.session.total_shares as f64);
+        let token_accounts = session_token_accounts(
+            self.network_config.payment_mint,
+            &self.session.key(),
+            &self.session_token_vault,
+            &self.contributor_token_account,
+            &self.token_program,
+        )?;
 
-        let withdraw_amount = (shares_to_burn as f64 * share_price) as u64;
+        let available_balance = match &token_accounts {
+            Some(token_accounts) => token_accounts
+                .vault
+                .amount
+                .checked_sub(self.session.locked_for_tasks)
+                .ok_or(ErrorCode::Underflow)?,
+            None => {
+                // Exclude rent lamports from share price calculation
+                let rent = Rent::get()?;
+                let rent_exempt_minimum = rent.minimum_balance(0);
+                self.vault
+                    .lamports()
+                    .checked_sub(self.session.locked_for_tasks)
+                    .ok_or(ErrorCode::Underflow)?
+                    .checked_sub(rent_exempt_minimum)
+                    .ok_or(ErrorCode::Underflow)?
+            }
+        };
+        let share_price = share_price(available_balance, self.session.total_shares)?;
+
+        let withdraw_amount = amount_for_shares(shares_to_burn, share_price)?;
         // available_balance already excludes rent and locked_for_tasks
         require!(
             withdraw_amount <= available_balance,
             ErrorCode::InsufficientBalance
         );
 
-        let session_key = self.session.key();
-        let vault_seeds = &[b"session_vault", session_key.as_ref(), &[self.session.vault_bump]];
-        let vault_signer = &[&vault_seeds[..]];
+        if let Some(token_accounts) = &token_accounts {
+            require_keys_eq!(
+                token_accounts.counterparty.owner,
+                self.contributor.key(),
+                ErrorCode::InvalidTokenAccount
+            );
+            transfer_from_session_token_vault(
+                token_accounts,
+                &self.session,
+                &self.network_config.key(),
+                withdraw_amount,
+            )?;
+        } else {
+            let session_key = self.session.key();
+            let vault_seeds = &[b"session_vault", session_key.as_ref(), &[self.session.vault_bump]];
+            let vault_signer = &[&vault_seeds[..]];
 
-        let cpi_accounts = system_program::Transfer {
-            from: self.vault.to_account_info(),
-            to: self.contributor.to_account_info(),
-        };
-        let cpi_context = CpiContext::new_with_signer(
-            self.system_program.to_account_info(),
-            cpi_accounts,
-            vault_signer,
-        );
-        system_program::transfer(cpi_context, withdraw_amount)?;
+            let cpi_accounts = system_program::Transfer {
+                from: self.vault.to_account_info(),
+                to: self.contributor.to_account_info(),
+            };
+            let cpi_context = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                cpi_accounts,
+                vault_signer,
+            );
+            system_program::transfer(cpi_context, withdraw_amount)?;
+        }
 
         // Update contribution shares
         self.contribution.shares = self
@@ -99,7 +160,26 @@ impl<'info> WithdrawFromSession<'info> {
             .total_shares
             .checked_sub(shares_to_burn)
             .ok_or(ErrorCode::Underflow)?;
+        self.session.record_activity()?;
+
+        Ok(())
+    }
+
+    /// Votes count the voter's shares as they were when cast. Withdrawing them while the
+    /// proposal is open would shrink the quorum denominator under a vote that still counts.
+    fn check_vote_lock(&self) -> Result<()> {
+        if self.session_proposal.data_is_empty() {
+            return Ok(());
+        }
+        let proposal =
+            SessionProposal::try_deserialize(&mut &self.session_proposal.try_borrow_data()?[..])?;
 
+        let slot = Clock::get()?.slot;
+        require!(
+            !proposal.is_open(slot, SESSION_PROPOSAL_SLOTS)
+                || !proposal.voters.contains(&self.contributor.key()),
+            ErrorCode::VotedSharesLocked
+        );
         Ok(())
     }
 }
@@ -2,12 +2,14 @@ use anchor_lang::prelude::*;
 
 declare_id!("BaY9vp3RXAQugzAoBojkBEZs9fJKS4dNManN7vwDZSFh");
 
+pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
 pub mod state;
 pub mod utils;
 
+pub use constants::*;
 pub use events::*;
 pub use instructions::*;
 pub use state::*;
@@ -23,12 +25,16 @@ pub mod dac {
         allocate_tasks: u64,
         approved_code_measurements: Vec<CodeMeasurement>,
         required_validations: u32,
+        genesis_nonce: Option<[u8; 32]>,
+        default_task_type: Option<TaskType>,
     ) -> Result<()> {
         ctx.accounts.initialize_network(
             cid_config,
             allocate_tasks,
             approved_code_measurements,
             required_validations,
+            genesis_nonce,
+            default_task_type,
             &ctx.remaining_accounts,
             &ctx.bumps,
         )
@@ -36,11 +42,29 @@ pub mod dac {
 
     pub fn update_network_config(
         ctx: Context<UpdateNetworkConfig>,
-        cid_config: Option<String>,
-        new_code_measurement: Option<CodeMeasurement>,
+        args: UpdateNetworkConfigArgs,
     ) -> Result<()> {
-        ctx.accounts
-            .update_network_config(cid_config, new_code_measurement)
+        ctx.accounts.update_network_config(args)
+    }
+
+    pub fn get_network_stats(ctx: Context<GetNetworkStats>) -> Result<()> {
+        ctx.accounts.get_network_stats()
+    }
+
+    pub fn is_node_approved(ctx: Context<IsNodeApproved>, node: Pubkey) -> Result<()> {
+        ctx.accounts.is_node_approved(node)
+    }
+
+    pub fn list_code_measurements(ctx: Context<ListCodeMeasurements>) -> Result<()> {
+        ctx.accounts.list_code_measurements()
+    }
+
+    pub fn get_validation_tally(ctx: Context<GetValidationTally>) -> Result<()> {
+        ctx.accounts.get_validation_tally()
+    }
+
+    pub fn get_share_precision(ctx: Context<GetSharePrecision>) -> Result<u64> {
+        ctx.accounts.get_share_precision()
     }
 
     pub fn register_node(
@@ -73,6 +97,14 @@ pub mod dac {
         ctx.accounts.activate_node()
     }
 
+    pub fn disable_node(ctx: Context<DisableNode>) -> Result<()> {
+        ctx.accounts.disable_node()
+    }
+
+    pub fn authority_reject_node(ctx: Context<AuthorityRejectNode>) -> Result<()> {
+        ctx.accounts.authority_reject_node()
+    }
+
     pub fn create_agent(ctx: Context<CreateAgent>, agent_config_cid: String) -> Result<()> {
         ctx.accounts.create_agent(agent_config_cid, &ctx.bumps)
     }
@@ -85,31 +117,29 @@ pub mod dac {
         ctx: Context<CreateSession>,
         is_owned: bool,
         is_confidential: bool,
+        allowed_owner: Option<Pubkey>,
+        funding_threshold: Option<u64>,
     ) -> Result<()> {
-        ctx.accounts
-            .create_session(is_owned, is_confidential, &ctx.bumps)
-    }
-
-    pub fn set_session(
-        ctx: Context<SetSession>,
-        specification_cid: String,
-        max_iterations: u64,
-        initial_deposit: u64,
-        compute_node: Pubkey,
-        task_type: TaskType,
-    ) -> Result<()> {
-        ctx.accounts.set_session(
-            specification_cid,
-            max_iterations,
-            initial_deposit,
-            compute_node,
-            task_type,
+        ctx.accounts.create_session(
+            is_owned,
+            is_confidential,
+            allowed_owner,
+            funding_threshold,
             &ctx.bumps,
         )
     }
 
-    pub fn contribute_to_session(ctx: Context<ContributeToSession>, deposit_amount: u64) -> Result<()> {
-        ctx.accounts.contribute_to_session(deposit_amount, &ctx.bumps)
+    pub fn set_session(ctx: Context<SetSession>, args: SetSessionArgs) -> Result<()> {
+        ctx.accounts.set_session(args, &ctx.bumps)
+    }
+
+    pub fn contribute_to_session(
+        ctx: Context<ContributeToSession>,
+        deposit_amount: u64,
+        min_shares: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts
+            .contribute_to_session(deposit_amount, min_shares, &ctx.bumps)
     }
 
     pub fn withdraw_from_session(
@@ -119,6 +149,21 @@ pub mod dac {
         ctx.accounts.withdraw_from_session(shares_to_burn)
     }
 
+    pub fn update_session_limits(
+        ctx: Context<UpdateSessionLimits>,
+        max_task_cost_ceiling: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.update_session_limits(max_task_cost_ceiling)
+    }
+
+    pub fn seed_task_input(
+        ctx: Context<SeedTaskInput>,
+        input_cid: String,
+        estimated_compute_units: Option<u64>,
+    ) -> Result<()> {
+        ctx.accounts.seed_task_input(input_cid, estimated_compute_units)
+    }
+
     pub fn claim_task(
         ctx: Context<ClaimTask>,
         max_task_cost: u64,
@@ -139,25 +184,184 @@ pub mod dac {
         input_cid: String,
         output_cid: String,
         state_cid: Option<String>,
+        next_input_cid: Option<String>,
         call_count: u64,
     ) -> Result<()> {
-        ctx.accounts
-            .submit_task_result(input_cid, output_cid, state_cid, call_count)
+        ctx.accounts.submit_task_result(
+            input_cid,
+            output_cid,
+            state_cid,
+            next_input_cid,
+            call_count,
+        )
     }
 
     // Note: submit_confidential_task_validation handles TEE-based validation (requires Ed25519 instruction)
-    pub fn submit_confidential_task_validation(ctx: Context<SubmitTaskValidation>) -> Result<()> {
-        ctx.accounts.submit_confidential_task_validation()
+    pub fn submit_confidential_task_validation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTaskValidation<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .submit_confidential_task_validation(ctx.remaining_accounts)
     }
 
     // Note: submit_public_task_validation handles common validation (validators provide parameters directly)
-    pub fn submit_public_task_validation(
-        ctx: Context<SubmitTaskValidation>,
+    pub fn submit_public_task_validation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTaskValidation<'info>>,
         payment_amount: u64,
         approved: bool,
         goal_completed: bool,
+    ) -> Result<()> {
+        ctx.accounts.submit_public_task_validation(
+            payment_amount,
+            approved,
+            goal_completed,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn force_resolve_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceResolveTask<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.force_resolve_task(ctx.remaining_accounts)
+    }
+
+    pub fn reconcile_session_locks<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReconcileSessionLocks<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.reconcile_session_locks(ctx.remaining_accounts)
+    }
+
+    pub fn sweep_abandoned_session(ctx: Context<SweepAbandonedSession>) -> Result<()> {
+        ctx.accounts.sweep_abandoned_session()
+    }
+
+    pub fn prune_rejected_nodes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PruneRejectedNodes<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.prune_rejected_nodes(ctx.remaining_accounts)
+    }
+
+    pub fn batch_withdraw_node_rewards<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchWithdrawNodeRewards<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .batch_withdraw_node_rewards(ctx.remaining_accounts)
+    }
+
+    pub fn merge_sessions<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MergeSessions<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.merge_sessions(ctx.remaining_accounts)
+    }
+
+    pub fn propose_session_action(
+        ctx: Context<ProposeSessionAction>,
+        action: SessionAction,
+    ) -> Result<()> {
+        ctx.accounts.propose_session_action(action, &ctx.bumps)
+    }
+
+    pub fn vote_session_action(ctx: Context<VoteSessionAction>) -> Result<()> {
+        ctx.accounts.vote_session_action()
+    }
+
+    pub fn release_task_settlement(ctx: Context<ReleaseTaskSettlement>) -> Result<()> {
+        ctx.accounts.release_task_settlement()
+    }
+
+    pub fn dispute_task_settlement(ctx: Context<DisputeTaskSettlement>) -> Result<()> {
+        ctx.accounts.dispute_task_settlement()
+    }
+
+    pub fn update_agent_memory(ctx: Context<UpdateAgentMemory>, memory_cid: String) -> Result<()> {
+        ctx.accounts.update_agent_memory(memory_cid)
+    }
+
+    pub fn migrate_network_config(ctx: Context<MigrateNetworkConfig>) -> Result<()> {
+        ctx.accounts.migrate_network_config()
+    }
+
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        ctx.accounts.migrate_account()
+    }
+
+    pub fn extend_tasks<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExtendTasks<'info>>,
+        count: u64,
+    ) -> Result<()> {
+        ctx.accounts.extend_tasks(count, ctx.remaining_accounts)
+    }
+
+    pub fn get_node_summary(ctx: Context<GetNodeSummary>) -> Result<()> {
+        ctx.accounts.get_node_summary()
+    }
+
+    pub fn verify_genesis(ctx: Context<VerifyGenesis>) -> Result<()> {
+        ctx.accounts.verify_genesis()
+    }
+
+    pub fn submit_task_checkpoint(
+        ctx: Context<SubmitTaskCheckpoint>,
+        checkpoint_cid: String,
+    ) -> Result<()> {
+        ctx.accounts.submit_task_checkpoint(checkpoint_cid)
+    }
+
+    pub fn authority_invalidate_task(ctx: Context<AuthorityInvalidateTask>) -> Result<()> {
+        ctx.accounts.authority_invalidate_task()
+    }
+
+    pub fn set_node_capacity(
+        ctx: Context<SetNodeCapacity>,
+        max_concurrent_tasks: u32,
+    ) -> Result<()> {
+        ctx.accounts.set_node_capacity(max_concurrent_tasks)
+    }
+
+    pub fn get_session_exposure<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetSessionExposure<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.get_session_exposure(ctx.remaining_accounts)
+    }
+
+    pub fn cancel_session(ctx: Context<CancelSession>) -> Result<()> {
+        ctx.accounts.cancel_session()
+    }
+
+    pub fn reclaim_cancelled_task(ctx: Context<ReclaimCancelledTask>) -> Result<()> {
+        ctx.accounts.reclaim_cancelled_task()
+    }
+
+    pub fn submit_batch_public_validation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitTaskValidation<'info>>,
+        payment_amount: u64,
+        goal_completed: bool,
+        co_signers: u8,
+    ) -> Result<()> {
+        ctx.accounts.submit_batch_public_validation(
+            payment_amount,
+            goal_completed,
+            co_signers,
+            ctx.remaining_accounts,
+        )
+    }
+
+    pub fn set_completion_hook(
+        ctx: Context<SetCompletionHook>,
+        completion_hook_program: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.set_completion_hook(completion_hook_program)
+    }
+
+    pub fn get_node_treasury_balance(ctx: Context<GetNodeTreasuryBalance>) -> Result<()> {
+        ctx.accounts.get_node_treasury_balance()
+    }
+
+    pub fn set_accepting_contributions(
+        ctx: Context<SetAcceptingContributions>,
+        accepting_contributions: bool,
     ) -> Result<()> {
         ctx.accounts
-            .submit_public_task_validation(payment_amount, approved, goal_completed)
+            .set_accepting_contributions(accepting_contributions)
     }
 }
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+
+pub const MAX_AGENT_VALIDATORS: usize = 10;
+
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub enum AgentStatus {
     Pending,
@@ -17,9 +21,34 @@ pub struct Agent {
     pub agent_config_cid: String,
     #[max_len(128)]
     pub agent_memory_cid: Option<String>,
-    #[max_len(10)]
+    #[max_len(MAX_AGENT_VALIDATORS)]
     pub approved_validators: Vec<Pubkey>,
-    #[max_len(10)]
+    #[max_len(MAX_AGENT_VALIDATORS)]
     pub rejected_validators: Vec<Pubkey>,
     pub bump: u8,
+    // Fields added since the first deployment go after bump, see migrate_account
+    pub active_session_count: u64,
+}
+
+impl Agent {
+    /// Take a session slot on this agent, max_sessions of 0 is unlimited
+    pub fn open_session(&mut self, max_sessions: u64) -> Result<()> {
+        require!(
+            max_sessions == 0 || self.active_session_count < max_sessions,
+            ErrorCode::SessionLimitReached
+        );
+        self.active_session_count = self
+            .active_session_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn close_session(&mut self) -> Result<()> {
+        self.active_session_count = self
+            .active_session_count
+            .checked_sub(1)
+            .ok_or(ErrorCode::Underflow)?;
+        Ok(())
+    }
 }
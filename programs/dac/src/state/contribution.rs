@@ -8,4 +8,6 @@ pub struct Contribution {
     pub shares: u64,
     pub refund_amount: u64,
     pub bump: u8,
+    // Fields added since the first deployment go after bump, see migrate_account
+    pub is_owner: bool,
 }
@@ -3,11 +3,15 @@ pub mod contribution;
 pub mod network_config;
 pub mod node_info;
 pub mod session;
+pub mod session_proposal;
 pub mod task;
+pub mod task_settlement;
 
 pub use agent::*;
 pub use contribution::*;
 pub use network_config::*;
 pub use node_info::*;
 pub use session::*;
+pub use session_proposal::*;
 pub use task::*;
+pub use task_settlement::*;
@@ -1,8 +1,15 @@
 use crate::errors::ErrorCode;
-use crate::utils::SemanticVersion;
+use crate::state::{NodeType, TaskType};
+use crate::utils::{is_version_at_least, SemanticVersion};
 use anchor_lang::prelude::*;
 use sha2::{Digest, Sha256};
 
+pub const MAX_CID_PREFIXES: usize = 8;
+pub const MAX_CID_PREFIX_LEN: usize = 16;
+pub const MAX_AGENT_CREATORS: usize = 16;
+pub const MAX_CODE_MEASUREMENTS: usize = 10;
+pub const MAX_APPROVED_NODES: usize = 10;
+
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy)]
 pub struct CodeMeasurement {
     pub measurement: [u8; 32],
@@ -30,14 +37,52 @@ pub struct NetworkConfig {
     pub agent_count: u64,
     pub session_count: u64,
 
-    #[max_len(10)]
+    #[max_len(MAX_CODE_MEASUREMENTS)]
     pub approved_code_measurements: Vec<CodeMeasurement>,
 
     pub bump: u8,
+    // Fields added since the first deployment go after bump, see migrate_network_config
+    pub agent_required_validations: u32,
+    pub confidential_public_fallback: bool, // confidential sessions may use public validators
+    pub reject_duplicate_results: bool, // reject results identical to the last validated one
+    pub agent_validators_must_be_confidential: bool, // only TEE-backed nodes may validate agents
+    pub validation_stall_slots: u64, // 0 disables force resolving stalled validations
+    pub validation_window_slots: u64, // slots validators have after a result, 0 disables the deadline
+    pub min_slots_between_claims: u64, // per node claim rate limit, 0 disables
+    pub min_slots_between_iterations: u64, // per session gap after a completed iteration, 0 disables
+    pub settlement_hold_slots: u64, // compute payments wait in escrow this long, 0 pays immediately
+    pub abandonment_slots: u64, // 0 disables sweeping abandoned session vaults
+    pub rejection_reward: u64, // paid to each validator of a threshold rejection, 0 disables
+    pub validator_share_bps: u16, // part of each task payment split among approving validators
+    pub protocol_share_bps: u16, // part of each task payment sent to the protocol treasury
+    pub max_sessions_per_agent: u64, // concurrent active sessions per agent, 0 is unlimited
+    pub payment_mint: Option<Pubkey>, // None keeps sessions denominated in native SOL
+    pub default_task_type: TaskType, // type of pre-allocated and newly created tasks
+    #[max_len(MAX_CID_PREFIXES, MAX_CID_PREFIX_LEN)]
+    pub allowed_cid_prefixes: Vec<String>, // empty allows any CID
+    #[max_len(MAX_AGENT_CREATORS)]
+    pub agent_creation_allowlist: Vec<Pubkey>, // empty lets anyone create agents
+    pub node_registration_count: u64, // seeds each registration's treasury, never reused
+    pub genesis_nonce: Option<[u8; 32]>, // kept so the genesis hash can be re-derived
+    pub debug_validation_logs: bool, // log expected vs received proofs when a validation fails
+    pub max_vault_balance: Option<u64>, // contributions may not grow a session vault past it
+    pub processing_window_slots: u64, // slots a node has to submit a claimed task, 0 disables the deadline
+    pub min_measurement_version: Option<SemanticVersion>, // oldest measurement version a confidential node may claim with
+    pub paused: bool, // no task can be claimed while set
+    pub max_ownership_bps: u16, // cap on a contributor's part of a session's shares, 0 disables
 }
 
 impl NetworkConfig {
-    pub fn add_code_measurement(&mut self, measurement: [u8; 32], version: SemanticVersion) {
+    pub fn add_code_measurement(
+        &mut self,
+        measurement: [u8; 32],
+        version: SemanticVersion,
+    ) -> Result<()> {
+        require!(
+            self.approved_code_measurements.len() < MAX_CODE_MEASUREMENTS,
+            ErrorCode::TooManyCodeMeasurements
+        );
+
         let new_measurement = CodeMeasurement {
             measurement,
             version,
@@ -45,9 +90,7 @@ impl NetworkConfig {
 
         self.approved_code_measurements.insert(0, new_measurement);
 
-        if self.approved_code_measurements.len() > 10 {
-            self.approved_code_measurements.pop();
-        }
+        Ok(())
     }
 
     pub fn is_measurement_approved(&self, measurement: &[u8; 32]) -> bool {
@@ -56,13 +99,31 @@ impl NetworkConfig {
             .any(|m| &m.measurement == measurement)
     }
 
+    /// Whether an approved measurement also meets the network's minimum version, if any
+    pub fn is_measurement_recent_enough(&self, measurement: &[u8; 32]) -> bool {
+        let Some(minimum) = &self.min_measurement_version else {
+            return true;
+        };
+        self.approved_code_measurements
+            .iter()
+            .any(|m| &m.measurement == measurement && is_version_at_least(&m.version, minimum))
+    }
+
     pub fn get_latest_measurement(&self) -> Option<&CodeMeasurement> {
         self.approved_code_measurements.first()
     }
 
-    pub fn compute_genesis_hash(&self) -> Result<[u8; 32]> {
+    /// A nonce binds the genesis to its authority, letting deployments pick a reproducible genesis
+    pub fn compute_genesis_hash(
+        authority: &Pubkey,
+        genesis_nonce: Option<[u8; 32]>,
+    ) -> Result<[u8; 32]> {
         let mut hasher = Sha256::new();
         hasher.update(b"DAC_GENESIS");
+        if let Some(genesis_nonce) = genesis_nonce {
+            hasher.update(authority.as_ref());
+            hasher.update(genesis_nonce);
+        }
         Ok(hasher.finalize().into())
     }
 
@@ -83,6 +144,18 @@ impl NetworkConfig {
         self.task_count
     }
 
+    pub fn next_node_registration_epoch(&self) -> u64 {
+        self.node_registration_count
+    }
+
+    pub fn increment_node_registration_count(&mut self) -> Result<()> {
+        self.node_registration_count = self
+            .node_registration_count
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
     pub fn increment_session_count(&mut self) -> Result<()> {
         self.session_count = self.session_count.checked_add(1).ok_or(ErrorCode::Overflow)?;
         Ok(())
@@ -93,19 +166,59 @@ impl NetworkConfig {
         Ok(())
     }
 
-    pub fn add_confidential_node(&mut self, node_pubkey: Pubkey) -> Result<()> {
-        self.approved_confidential_nodes.push(node_pubkey);
-        if self.approved_confidential_nodes.len() > 10 {
-            self.approved_confidential_nodes.pop();
+    /// Returns true when the node joined the pool
+    pub fn add_confidential_node(&mut self, node_pubkey: Pubkey) -> Result<bool> {
+        Self::add_approved_node(&mut self.approved_confidential_nodes, node_pubkey)
+    }
+
+    /// Returns true when the node joined the pool
+    pub fn add_public_node(&mut self, node_pubkey: Pubkey) -> Result<bool> {
+        Self::add_approved_node(&mut self.approved_public_nodes, node_pubkey)
+    }
+
+    /// Returns true when the node was in the pool for its type
+    pub fn remove_node(&mut self, node_type: NodeType, node_pubkey: &Pubkey) -> bool {
+        let pool = match node_type {
+            NodeType::Public => &mut self.approved_public_nodes,
+            NodeType::Confidential => &mut self.approved_confidential_nodes,
+        };
+        let len_before = pool.len();
+        pool.retain(|p| p != node_pubkey);
+        pool.len() != len_before
+    }
+
+    // The pools double as the node counts, so a node is only ever listed once
+    fn add_approved_node(pool: &mut Vec<Pubkey>, node_pubkey: Pubkey) -> Result<bool> {
+        if pool.contains(&node_pubkey) {
+            return Ok(false);
         }
+        // A full pool used to drop the node silently, leaving it active but never scheduled
+        require!(pool.len() < MAX_APPROVED_NODES, ErrorCode::TooManyNodes);
+        pool.push(node_pubkey);
+        Ok(true)
+    }
+
+    pub fn validate_cid(&self, cid: &str) -> Result<()> {
+        require!(!cid.is_empty(), ErrorCode::InvalidCID);
+        require!(
+            self.allowed_cid_prefixes.is_empty()
+                || self
+                    .allowed_cid_prefixes
+                    .iter()
+                    .any(|prefix| cid.starts_with(prefix.as_str())),
+            ErrorCode::InvalidCID
+        );
         Ok(())
     }
 
-    pub fn add_public_node(&mut self, node_pubkey: Pubkey) -> Result<()> {
-        self.approved_public_nodes.push(node_pubkey);
-        if self.approved_public_nodes.len() > 10 {
-            self.approved_public_nodes.pop();
+    pub fn can_create_agent(&self, creator: &Pubkey) -> bool {
+        self.agent_creation_allowlist.is_empty() || self.agent_creation_allowlist.contains(creator)
+    }
+
+    pub fn is_node_approved(&self, node_type: NodeType, node_pubkey: &Pubkey) -> bool {
+        match node_type {
+            NodeType::Public => self.approved_public_nodes.contains(node_pubkey),
+            NodeType::Confidential => self.approved_confidential_nodes.contains(node_pubkey),
         }
-        Ok(())
     }
 }
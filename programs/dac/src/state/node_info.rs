@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::ErrorCode;
+use crate::state::{TaskType, TASK_TYPE_COUNT};
+
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub enum NodeType {
     Public,
@@ -34,4 +37,93 @@ pub struct NodeInfo {
     #[max_len(10)]
     pub rejected_validators: Vec<Pubkey>,
     pub bump: u8,
+    // Fields added since the first deployment go after bump, see migrate_account
+    pub registration_epoch: u64, // part of the treasury seed, fresh for every registration
+    pub earned_by_type: [u64; TASK_TYPE_COUNT], // total_earned by TaskType::variant_index
+    pub total_validations: u64, // task validations submitted, whatever their outcome
+    pub last_claim_slot: Option<u64>, // slot of the node's latest task claim
+    pub max_concurrent_tasks: u32, // claims the node may hold at once, 0 is unlimited
+    pub current_tasks: u32, // claimed executions not yet validated, rejected or dropped
+    pub agreed_validations: u64, // validations matching the outcome their task settled on
+}
+
+impl NodeInfo {
+    pub fn record_task_payment(&mut self, amount: u64, task_type: &TaskType) -> Result<()> {
+        self.total_earned = self
+            .total_earned
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        let earned = &mut self.earned_by_type[task_type.variant_index()];
+        *earned = earned.checked_add(amount).ok_or(ErrorCode::Overflow)?;
+        self.total_tasks_completed = self
+            .total_tasks_completed
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Lifetime earnings from tasks of this type's variant
+    pub fn earned_for(&self, task_type: &TaskType) -> u64 {
+        self.earned_by_type[task_type.variant_index()]
+    }
+
+    pub fn record_validation(&mut self) -> Result<()> {
+        self.total_validations = self
+            .total_validations
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    pub fn record_agreement(&mut self) -> Result<()> {
+        self.agreed_validations = self
+            .agreed_validations
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Weight of this node in the validator fee split, by how many of its validations matched
+    /// the outcome their task settled on, so volume alone earns nothing. New validators still
+    /// earn a base share.
+    pub fn agreement_weight(&self) -> Result<u64> {
+        self.agreed_validations
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow.into())
+    }
+
+    /// Take a slot for a new claim, failing when the node already holds its maximum
+    pub fn start_task(&mut self) -> Result<()> {
+        require!(
+            self.max_concurrent_tasks == 0 || self.current_tasks < self.max_concurrent_tasks,
+            ErrorCode::NodeAtCapacity
+        );
+        self.current_tasks = self
+            .current_tasks
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Free the slot of a claimed execution that has ended
+    pub fn finish_task(&mut self) -> Result<()> {
+        self.current_tasks = self
+            .current_tasks
+            .checked_sub(1)
+            .ok_or(ErrorCode::Underflow)?;
+        Ok(())
+    }
+
+    /// Record a claim at `slot`, failing if the previous one was less than
+    /// `min_slots_between_claims` ago
+    pub fn record_claim(&mut self, slot: u64, min_slots_between_claims: u64) -> Result<()> {
+        if let Some(last_claim_slot) = self.last_claim_slot {
+            let next_claim_slot = last_claim_slot
+                .checked_add(min_slots_between_claims)
+                .ok_or(ErrorCode::Overflow)?;
+            require!(slot >= next_claim_slot, ErrorCode::ClaimRateLimited);
+        }
+        self.last_claim_slot = Some(slot);
+        Ok(())
+    }
 }
@@ -1,13 +1,30 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::MAX_SPECIFICATION_CID_LEN;
+use crate::errors::ErrorCode;
+use crate::state::NodeType;
+
+pub const MAX_ALLOWED_COMPUTE_NODES: usize = 8;
+
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub enum SessionStatus {
     Pending,
     Active,
     Completed,
+    Cancelled, // stopped by its owner or a contributor vote, contributors withdraw what is left
+    Swept, // vault taken by sweep_abandoned_session, its shares are void and it is never set again
     //TODO: Add refund status in the future
 }
 
+/// Arguments of the `on_session_completed` call made into a session's completion hook program
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompletionHookArgs {
+    pub session_slot_id: u64,
+    pub owner: Pubkey,
+    pub final_iteration: u64,
+    pub total_paid: u64,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Session {
@@ -22,10 +39,106 @@ pub struct Session {
     pub task_index_end: u64,
     pub total_shares: u64,
     pub locked_for_tasks: u64,
-    #[max_len(128)]
+    #[max_len(MAX_SPECIFICATION_CID_LEN)]
     pub specification_cid: String, // IPFS CID of session specification
     #[max_len(128)]
     pub state_cid: Option<String>, // IPFS CID of session state
     pub vault_bump: u8,
     pub bump: u8,
+    // Fields added since the first deployment go after bump, see migrate_account
+    pub allowed_owner: Option<Pubkey>, // only this pubkey may claim an unowned session
+    pub funding_threshold: Option<u64>, // contributors fund it while pending, any of them sets it
+    pub agent: Pubkey, // set once the session is set, default before
+    pub total_paid: u64, // paid out of the vault for validated tasks since the session was set
+    pub max_output_size: Option<u64>, // max attested output bytes for confidential tasks
+    pub max_task_cost_ceiling: Option<u64>, // upper bound on max_task_cost per claim
+    pub owner_fee_bps: Option<u16>, // share of third-party contributions minted to the owner
+    #[max_len(MAX_ALLOWED_COMPUTE_NODES)]
+    pub allowed_compute_nodes: Vec<Pubkey>, // empty lets any approved node serve the session
+    pub completion_hook_program: Option<Pubkey>, // program called with CompletionHookArgs on completion
+    pub paused: bool, // set by a contributor vote, no new tasks can be claimed
+    pub accepting_contributions: bool, // cleared by the owner to refuse third-party contributions
+    pub last_activity_slot: u64,
+    pub last_iteration_slot: Option<u64>, // slot the latest iteration completed, None since set
+}
+
+impl Session {
+    pub fn required_node_type(&self) -> NodeType {
+        if self.is_confidential {
+            NodeType::Confidential
+        } else {
+            NodeType::Public
+        }
+    }
+
+    /// Unowned session still collecting contributions before it is set collectively
+    pub fn is_crowdfunding(&self) -> bool {
+        self.status == SessionStatus::Pending && self.funding_threshold.is_some()
+    }
+
+    /// Created unowned and crowdfunded, so its contributors set and cancel it by vote
+    /// instead of an owner
+    pub fn is_governed(&self) -> bool {
+        self.funding_threshold.is_some()
+    }
+
+    pub fn record_activity(&mut self) -> Result<()> {
+        self.last_activity_slot = Clock::get()?.slot;
+        Ok(())
+    }
+
+    pub fn release_task_lock(&mut self, amount: u64) -> Result<()> {
+        self.locked_for_tasks = self
+            .locked_for_tasks
+            .checked_sub(amount)
+            .ok_or(ErrorCode::Underflow)?;
+        Ok(())
+    }
+
+    pub fn record_payment(&mut self, amount: u64) -> Result<()> {
+        self.total_paid = self
+            .total_paid
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// None for a session without an iteration limit
+    pub fn iterations_remaining(&self) -> Option<u64> {
+        if self.max_iterations == 0 {
+            None
+        } else {
+            Some(self.max_iterations.saturating_sub(self.current_iteration))
+        }
+    }
+
+    /// Fails until `min_slots_between_iterations` have passed since the last completed iteration
+    pub fn check_iteration_gap(&self, slot: u64, min_slots_between_iterations: u64) -> Result<()> {
+        if let Some(last_iteration_slot) = self.last_iteration_slot {
+            let next_iteration_slot = last_iteration_slot
+                .checked_add(min_slots_between_iterations)
+                .ok_or(ErrorCode::Overflow)?;
+            require!(slot >= next_iteration_slot, ErrorCode::IterationRateLimited);
+        }
+        Ok(())
+    }
+
+    /// Advance to the next iteration, returns true when the session is now completed.
+    /// `task_index` is the index of the step just validated, it closes the session's range.
+    pub fn complete_iteration(&mut self, session_completed: bool, task_index: u64) -> Result<bool> {
+        self.current_iteration = self
+            .current_iteration
+            .checked_add(1)
+            .ok_or(ErrorCode::Overflow)?;
+        self.last_iteration_slot = Some(Clock::get()?.slot);
+
+        let completed = session_completed
+            || (self.max_iterations != 0 && self.current_iteration >= self.max_iterations);
+        if completed {
+            self.status = SessionStatus::Completed;
+            self.task_index_end = task_index;
+        }
+
+        Ok(completed)
+    }
 }
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::ErrorCode;
+use crate::state::{Agent, Session, SessionStatus};
+
+pub const MAX_PROPOSAL_VOTERS: usize = 32;
+
+#[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum SessionAction {
+    Pause,
+    Cancel,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct SessionProposal {
+    pub session: Pubkey,
+    pub proposer: Pubkey,
+    pub action: SessionAction,
+    pub votes_shares: u64, // contribution shares backing the action, snapshotted at vote time
+    #[max_len(MAX_PROPOSAL_VOTERS)]
+    pub voters: Vec<Pubkey>,
+    pub created_slot: u64,
+    pub executed: bool,
+    pub bump: u8,
+}
+
+impl SessionProposal {
+    /// Open until executed or until `duration_slots` have passed since it was created
+    pub fn is_open(&self, slot: u64, duration_slots: u64) -> bool {
+        !self.executed && slot < self.created_slot.saturating_add(duration_slots)
+    }
+
+    pub fn record_vote(&mut self, voter: Pubkey, shares: u64) -> Result<()> {
+        require!(shares > 0, ErrorCode::NoVotingShares);
+        require!(!self.voters.contains(&voter), ErrorCode::AlreadyVoted);
+        require!(
+            self.voters.len() < MAX_PROPOSAL_VOTERS,
+            ErrorCode::TooManyProposalVoters
+        );
+
+        self.voters.push(voter);
+        self.votes_shares = self
+            .votes_shares
+            .checked_add(shares)
+            .ok_or(ErrorCode::Overflow)?;
+        Ok(())
+    }
+
+    /// Strict majority of the session's outstanding shares
+    pub fn has_quorum(&self, total_shares: u64) -> bool {
+        (self.votes_shares as u128) * 2 > total_shares as u128
+    }
+
+    /// Apply the action once quorum is reached, returns true when it was executed
+    pub fn try_execute(&mut self, session: &mut Session, agent: &mut Agent) -> Result<bool> {
+        if !self.has_quorum(session.total_shares) {
+            return Ok(false);
+        }
+
+        match self.action {
+            SessionAction::Pause => session.paused = true,
+            SessionAction::Cancel => {
+                // An in-flight task keeps its lock until it settles or is reclaimed
+                session.status = SessionStatus::Cancelled;
+                agent.close_session()?;
+            }
+        }
+        self.executed = true;
+
+        Ok(true)
+    }
+}
@@ -1,4 +1,11 @@
 use anchor_lang::prelude::*;
+use sha2::{Digest, Sha256};
+
+use crate::errors::ErrorCode;
+
+pub const MAX_TASK_VALIDATORS: usize = 10;
+/// Number of `TaskType` variants, sizes per-type tallies indexed by `TaskType::variant_index`
+pub const TASK_TYPE_COUNT: usize = 3;
 
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
 pub enum TaskStatus {
@@ -15,6 +22,37 @@ pub enum TaskType {
     HumanInLoop,
 }
 
+impl TaskType {
+    /// Position of the variant in per-type tallies, whatever its model or module id
+    pub fn variant_index(&self) -> usize {
+        match self {
+            TaskType::Completion(_) => 0,
+            TaskType::Custom(_) => 1,
+            TaskType::HumanInLoop => 2,
+        }
+    }
+
+    /// Fold a task result into its validation proof the way this task type is checked.
+    /// Every type hashes under its own domain, so a proof never verifies across types.
+    pub fn hash_validation_result(&self, hasher: &mut Sha256, input_cid: &str, output_cid: &str) {
+        match self {
+            TaskType::Completion(model_id) => {
+                hasher.update(b"COMPLETION");
+                hasher.update(model_id.to_le_bytes());
+            }
+            TaskType::Custom(module_id) => {
+                hasher.update(b"CUSTOM");
+                hasher.update(module_id.to_le_bytes());
+            }
+            TaskType::HumanInLoop => {
+                hasher.update(b"HUMAN_IN_LOOP");
+            }
+        }
+        hasher.update(input_cid.as_bytes());
+        hasher.update(output_cid.as_bytes());
+    }
+}
+
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum ValidationStatus {
     Pending,
@@ -49,7 +87,93 @@ pub struct Task {
     pub pending_input_cid: Option<String>,
     #[max_len(128)]
     pub pending_output_cid: Option<String>,
-    #[max_len(10)]
+    #[max_len(MAX_TASK_VALIDATORS)]
     pub validations: Vec<Validator>,
     pub bump: u8,
+    // Fields added since the first deployment go after bump, see migrate_account
+    pub estimated_compute_units: u64, // owner's advisory estimate for nodes deciding to claim, not enforced
+    // Multi-step tasks: input the next step must consume, promoted from the pending one on approval
+    #[max_len(128)]
+    pub next_input_cid: Option<String>,
+    #[max_len(128)]
+    pub pending_next_input_cid: Option<String>,
+    pub pending_result_digest: [u8; 32], // pins the submitted result validators vote on, zero when none
+    // Intermediate state of the running execution, survives a reclaim until a result is submitted
+    #[max_len(128)]
+    pub checkpoint_cid: Option<String>,
+    pub validation_started_slot: u64,
+    pub validation_deadline_slot: u64, // last slot validators may vote, 0 is no deadline
+    pub output_size: u64, // attested byte size of the last validated output, 0 if not attested
+    // Vault snapshot taken at claim, the payment basis for this execution
+    pub claim_available_balance: u64,
+    pub processing_deadline_slot: u64, // last slot the claimant may hold the task, 0 is no deadline
+}
+
+impl Task {
+    pub fn count_validations(&self, status: ValidationStatus) -> u32 {
+        self.validations
+            .iter()
+            .filter(|v| v.status == status)
+            .count() as u32
+    }
+
+    /// Whether validators ran out of time on the submitted result at `slot`
+    pub fn validation_expired(&self, slot: u64) -> bool {
+        self.validation_deadline_slot != 0 && slot > self.validation_deadline_slot
+    }
+
+    /// Whether the claimant ran out of time to submit a result at `slot`
+    pub fn processing_expired(&self, slot: u64) -> bool {
+        self.processing_deadline_slot != 0 && slot > self.processing_deadline_slot
+    }
+
+    /// Digest of the pending result fields, taken at submission and checked again at commit
+    pub fn hash_pending_result(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"DAC_PENDING_RESULT");
+        for cid in [
+            &self.pending_input_cid,
+            &self.pending_output_cid,
+            &self.pending_next_input_cid,
+        ] {
+            let cid = cid.as_deref().unwrap_or("");
+            hasher.update((cid.len() as u32).to_le_bytes());
+            hasher.update(cid.as_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Chain the previous validated result into chain_proof and promote the pending result.
+    /// The promoted result must be exactly the one submitted, whichever validator reached the
+    /// threshold, so the proof doesn't depend on the order of the votes.
+    pub fn commit_pending_result(&mut self) -> Result<()> {
+        require!(
+            self.pending_result_digest == self.hash_pending_result(),
+            ErrorCode::PendingResultChanged
+        );
+
+        let old_input_cid = self.input_cid.as_deref().unwrap_or("");
+        let old_output_cid = self.output_cid.as_deref().unwrap_or("");
+
+        let mut hasher = Sha256::new();
+        hasher.update(self.chain_proof);
+        hasher.update(old_input_cid.as_bytes());
+        hasher.update(old_output_cid.as_bytes());
+        hasher.update(self.task_index.to_le_bytes());
+        self.chain_proof = hasher.finalize().into();
+
+        self.input_cid = self.pending_input_cid.take();
+        self.output_cid = self.pending_output_cid.take();
+        self.next_input_cid = self.pending_next_input_cid.take();
+        self.pending_result_digest = [0; 32];
+        Ok(())
+    }
+
+    /// A rejected step keeps its chained input so the retry consumes the same one
+    pub fn discard_pending_result(&mut self) {
+        self.pending_input_cid = None;
+        self.pending_output_cid = None;
+        self.pending_next_input_cid = None;
+        self.pending_result_digest = [0; 32];
+    }
 }
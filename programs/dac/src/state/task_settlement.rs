@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::state::TaskType;
+
+/// Compute node payment held in escrow until the settlement hold has passed
+#[account]
+#[derive(InitSpace)]
+pub struct TaskSettlement {
+    pub session: Pubkey,
+    pub task: Pubkey,
+    pub task_index: u64,
+    pub node_info: Pubkey, // compute node credited on release
+    pub amount: u64, // escrowed on top of the account's rent
+    pub task_type: TaskType, // type the payment is credited to on release
+    pub release_slot: u64, // disputes must come before it, the node sweeps from it
+    pub payer: Pubkey, // session vault that paid the escrow's rent, refunded on close
+    pub bump: u8,
+}
+
+impl TaskSettlement {
+    pub fn is_held(&self, slot: u64) -> bool {
+        slot < self.release_slot
+    }
+}
@@ -1,11 +1,20 @@
-use anchor_lang::prelude::borsh::BorshDeserialize;
+use anchor_lang::prelude::borsh::{BorshDeserialize, BorshSerialize};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::sysvar::instructions as ix_sysvar;
 use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, Token, TokenAccount};
 use solana_ed25519_program::{Ed25519SignatureOffsets, PUBKEY_SERIALIZED_SIZE};
 use solana_sdk_ids::ed25519_program;
 
+use crate::constants::{BASIS_POINTS, COMPLETION_HOOK_DISCRIMINATOR, SHARE_PRECISION};
 use crate::errors::ErrorCode;
+use crate::events::TaskSettlementHeld;
+use crate::state::{
+    CompletionHookArgs, NetworkConfig, NodeInfo, Session, Task, TaskSettlement, ValidationStatus,
+};
 
 #[derive(InitSpace, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub struct SemanticVersion {
@@ -42,6 +51,13 @@ impl Ord for SemanticVersion {
     }
 }
 
+/// Gate for version checks, e.g. requiring a confidential node's measurement to be recent enough.
+pub fn is_version_at_least(candidate: &SemanticVersion, minimum: &SemanticVersion) -> bool {
+    candidate >= minimum
+}
+
+/// Create a program-owned PDA paid for by `payer`. Lamports already sent to the address are
+/// kept and only topped up to rent, so pre-funding a predictable address can't block it.
 pub fn init_dynamic_pda<'info>(
     payer: &Signer<'info>,
     target_account: &AccountInfo<'info>,
@@ -49,6 +65,28 @@ pub fn init_dynamic_pda<'info>(
     space: usize,
     owner: &Pubkey,
     system_program: &Program<'info, System>,
+) -> Result<u8> {
+    init_dynamic_pda_with_payer(
+        payer.to_account_info(),
+        &[],
+        target_account,
+        seeds,
+        space,
+        owner,
+        system_program,
+    )
+}
+
+/// `init_dynamic_pda` for a payer that is itself a PDA of this program, signing with
+/// `payer_seeds`. Empty seeds mean the payer signed the transaction.
+pub fn init_dynamic_pda_with_payer<'info>(
+    payer: AccountInfo<'info>,
+    payer_seeds: &[&[u8]],
+    target_account: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    owner: &Pubkey,
+    system_program: &Program<'info, System>,
 ) -> Result<u8> {
     let (pda, bump) = Pubkey::find_program_address(seeds, &crate::ID);
     require_keys_eq!(target_account.key(), pda, ErrorCode::InvalidPDAAccount);
@@ -61,22 +99,180 @@ pub fn init_dynamic_pda<'info>(
     let required_lamports = rent.minimum_balance(space);
 
     let bump_seed = &[bump];
-    let mut signer_seeds = seeds.to_vec();
-    signer_seeds.push(bump_seed);
-    let signer_seeds = &[&signer_seeds[..]];
+    let mut target_seeds = seeds.to_vec();
+    target_seeds.push(bump_seed);
+    let mut signer_seeds = vec![&target_seeds[..]];
+    if !payer_seeds.is_empty() {
+        signer_seeds.push(payer_seeds);
+    }
+    let signer_seeds = &signer_seeds[..];
+
+    let current_lamports = target_account.lamports();
+    if current_lamports == 0 {
+        let cpi_accounts = system_program::CreateAccount {
+            from: payer,
+            to: target_account.clone(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        system_program::create_account(cpi_context, required_lamports, space as u64, owner)?;
+        return Ok(bump);
+    }
 
-    let cpi_accounts = system_program::CreateAccount {
-        from: payer.to_account_info(),
-        to: target_account.clone(),
+    // create_account refuses an address holding lamports, so build the account in steps
+    let top_up = required_lamports.saturating_sub(current_lamports);
+    if top_up > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer,
+            to: target_account.clone(),
+        };
+        let cpi_context = CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        system_program::transfer(cpi_context, top_up)?;
+    }
+    let cpi_accounts = system_program::Allocate {
+        account_to_allocate: target_account.clone(),
     };
     let cpi_context =
         CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, signer_seeds);
-
-    system_program::create_account(cpi_context, required_lamports, space as u64, owner)?;
+    system_program::allocate(cpi_context, space as u64)?;
+    let cpi_accounts = system_program::Assign {
+        account_to_assign: target_account.clone(),
+    };
+    let cpi_context =
+        CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, signer_seeds);
+    system_program::assign(cpi_context, owner)?;
 
     Ok(bump)
 }
 
+pub fn transfer_from_vault<'info>(
+    vault: &SystemAccount<'info>,
+    to: AccountInfo<'info>,
+    session_key: &Pubkey,
+    vault_bump: u8,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let vault_seeds = &[b"session_vault", session_key.as_ref(), &[vault_bump]];
+    let vault_signer = &[&vault_seeds[..]];
+
+    let cpi_accounts = system_program::Transfer {
+        from: vault.to_account_info(),
+        to,
+    };
+    let cpi_context =
+        CpiContext::new_with_signer(system_program.to_account_info(), cpi_accounts, vault_signer);
+
+    system_program::transfer(cpi_context, amount)
+}
+
+/// Token accounts used when the network denominates sessions in `payment_mint`
+pub struct SessionTokenAccounts<'a, 'info> {
+    pub vault: &'a Account<'info, TokenAccount>,
+    pub counterparty: &'a Account<'info, TokenAccount>,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// Resolve the optional token accounts of an instruction. Returns None for native SOL networks.
+/// The session vault must be the session's associated token account for the payment mint.
+pub fn session_token_accounts<'a, 'info>(
+    payment_mint: Option<Pubkey>,
+    session_key: &Pubkey,
+    vault: &'a Option<Account<'info, TokenAccount>>,
+    counterparty: &'a Option<Account<'info, TokenAccount>>,
+    token_program: &'a Option<Program<'info, Token>>,
+) -> Result<Option<SessionTokenAccounts<'a, 'info>>> {
+    let Some(mint) = payment_mint else {
+        return Ok(None);
+    };
+
+    let vault = vault.as_ref().ok_or(ErrorCode::MissingTokenAccount)?;
+    let counterparty = counterparty
+        .as_ref()
+        .ok_or(ErrorCode::MissingTokenAccount)?;
+    let token_program = token_program
+        .as_ref()
+        .ok_or(ErrorCode::MissingTokenAccount)?;
+
+    verify_session_token_vault(vault, session_key, &mint)?;
+    require_keys_eq!(counterparty.mint, mint, ErrorCode::InvalidTokenAccount);
+
+    Ok(Some(SessionTokenAccounts {
+        vault,
+        counterparty,
+        token_program,
+    }))
+}
+
+pub fn verify_session_token_vault(
+    vault: &Account<TokenAccount>,
+    session_key: &Pubkey,
+    mint: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        vault.key(),
+        get_associated_token_address(session_key, mint),
+        ErrorCode::InvalidTokenAccount
+    );
+    Ok(())
+}
+
+pub fn transfer_to_session_token_vault<'info>(
+    accounts: &SessionTokenAccounts<'_, 'info>,
+    authority: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = token::Transfer {
+        from: accounts.counterparty.to_account_info(),
+        to: accounts.vault.to_account_info(),
+        authority,
+    };
+    let cpi_context = CpiContext::new(accounts.token_program.to_account_info(), cpi_accounts);
+
+    token::transfer(cpi_context, amount)
+}
+
+/// The session PDA owns its token vault, so it signs transfers out of it
+pub fn transfer_from_session_token_vault<'info>(
+    accounts: &SessionTokenAccounts<'_, 'info>,
+    session: &Account<'info, Session>,
+    network_config_key: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let session_slot_id = session.session_slot_id.to_le_bytes();
+    let session_seeds = &[
+        b"session",
+        network_config_key.as_ref(),
+        session_slot_id.as_ref(),
+        &[session.bump],
+    ];
+    let session_signer = &[&session_seeds[..]];
+
+    let cpi_accounts = token::Transfer {
+        from: accounts.vault.to_account_info(),
+        to: accounts.counterparty.to_account_info(),
+        authority: session.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(
+        accounts.token_program.to_account_info(),
+        cpi_accounts,
+        session_signer,
+    );
+
+    token::transfer(cpi_context, amount)
+}
+
+/// Verify the Ed25519 instruction directly preceding the current one and decode its message.
+/// Adjacency is required, so nothing can sit between the signature check and the instruction
+/// consuming it.
 pub fn verify_tee_signature<T: BorshDeserialize>(
     instruction_sysvar: &AccountInfo,
     expected_tee_pubkey: &Pubkey,
@@ -99,16 +295,30 @@ pub fn verify_tee_signature<T: BorshDeserialize>(
 
     let ed_data = &ed_ix.data;
     require!(ed_data.len() >= 16, ErrorCode::InvalidInstructionSysvar);
+    // Only the first offsets entry is read, so no other signature may ride along
+    require!(ed_data[0] == 1, ErrorCode::BadEd25519SignatureCount);
 
     let offsets: Ed25519SignatureOffsets = bytemuck::try_pod_read_unaligned(&ed_data[2..16])
         .map_err(|_| error!(ErrorCode::InvalidInstructionSysvar))?;
 
+    // The offsets must point into this instruction, which is the data sliced below
+    require!(
+        offsets.signature_instruction_index == u16::MAX
+            && offsets.public_key_instruction_index == u16::MAX
+            && offsets.message_instruction_index == u16::MAX,
+        ErrorCode::InvalidInstructionSysvar
+    );
+
     let pubkey_offset = offsets.public_key_offset as usize;
     let msg_offset = offsets.message_data_offset as usize;
     let msg_len = offsets.message_data_size as usize;
 
-    let validator_pubkey_slice = &ed_data[pubkey_offset..(pubkey_offset + PUBKEY_SERIALIZED_SIZE)];
-    let msg_bytes = &mut &ed_data[msg_offset..(msg_offset + msg_len)];
+    let validator_pubkey_slice = ed_data
+        .get(pubkey_offset..(pubkey_offset + PUBKEY_SERIALIZED_SIZE))
+        .ok_or(ErrorCode::InvalidInstructionSysvar)?;
+    let msg_bytes = &mut ed_data
+        .get(msg_offset..(msg_offset + msg_len))
+        .ok_or(ErrorCode::InvalidInstructionSysvar)?;
 
     require!(
         validator_pubkey_slice == expected_tee_pubkey.as_ref(),
@@ -129,3 +339,453 @@ pub fn check_validation_threshold(
 pub fn increment_validations(current: u32) -> Result<u32> {
     current.checked_add(1).ok_or(ErrorCode::Overflow.into())
 }
+
+/// Share price scaled by SHARE_PRECISION. An empty session starts at 1:1.
+pub fn share_price(available_balance: u64, total_shares: u64) -> Result<u128> {
+    if total_shares == 0 {
+        return Ok(SHARE_PRECISION as u128);
+    }
+    (available_balance as u128)
+        .checked_mul(SHARE_PRECISION as u128)
+        .and_then(|v| v.checked_div(total_shares as u128))
+        .ok_or(ErrorCode::Overflow.into())
+}
+
+pub fn shares_for_amount(amount: u64, share_price: u128) -> Result<u64> {
+    let shares = (amount as u128)
+        .checked_mul(SHARE_PRECISION as u128)
+        .and_then(|v| v.checked_div(share_price))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(shares).map_err(|_| ErrorCode::Overflow.into())
+}
+
+pub fn amount_for_shares(shares: u64, share_price: u128) -> Result<u64> {
+    let amount = (shares as u128)
+        .checked_mul(share_price)
+        .and_then(|v| v.checked_div(SHARE_PRECISION as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    u64::try_from(amount).map_err(|_| ErrorCode::Overflow.into())
+}
+
+/// Lowercase hex for diagnostic logs
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Check the (node_info, treasury) pairs passed for `validators`, whose votes matched the
+/// task's outcome, in the task's validation order. Each is credited with the agreement and
+/// returned with its agreement weight. The deciding validator is credited by the caller
+/// through its own node_info account, which would otherwise overwrite this write.
+pub fn validator_treasuries<'info>(
+    validators: &[Pubkey],
+    validator_accounts: &'info [AccountInfo<'info>],
+    deciding_validator: Option<Pubkey>,
+) -> Result<Vec<(&'info AccountInfo<'info>, u64)>> {
+    require!(
+        validator_accounts.len() == validators.len() * 2,
+        ErrorCode::MissingAccount
+    );
+
+    let mut treasuries = Vec::with_capacity(validators.len());
+    for (validator, pair) in validators.iter().zip(validator_accounts.chunks(2)) {
+        let (validator_node_info, validator_treasury) = (&pair[0], &pair[1]);
+
+        let mut node_info: Account<NodeInfo> = Account::try_from(validator_node_info)?;
+        let (expected_node_info, _) =
+            Pubkey::find_program_address(&[b"node_info", validator.as_ref()], &crate::ID);
+        require_keys_eq!(
+            node_info.key(),
+            expected_node_info,
+            ErrorCode::InvalidPDAAccount
+        );
+        require_keys_eq!(
+            validator_treasury.key(),
+            node_info.node_treasury,
+            ErrorCode::InvalidPDAAccount
+        );
+        if deciding_validator != Some(*validator) {
+            node_info.record_agreement()?;
+            node_info.exit(&crate::ID)?;
+        }
+        treasuries.push((validator_treasury, node_info.agreement_weight()?));
+    }
+
+    Ok(treasuries)
+}
+
+/// Divide a payment into (compute node, each validator, protocol) amounts by the network's
+/// basis point shares. The validator share is split in proportion to `validator_weights`.
+/// Rounding dust stays with the compute node.
+pub fn split_payment(
+    network_config: &NetworkConfig,
+    payment_amount: u64,
+    validator_weights: &[u64],
+) -> Result<(u64, Vec<u64>, u64)> {
+    let validator_share_bps = network_config.validator_share_bps;
+    let protocol_share_bps = network_config.protocol_share_bps;
+    require!(
+        network_config.payment_mint.is_none()
+            || (validator_share_bps == 0 && protocol_share_bps == 0),
+        ErrorCode::TokenPaymentSplitUnsupported
+    );
+
+    let share = |bps: u16| -> Result<u64> {
+        let amount = (payment_amount as u128)
+            .checked_mul(bps as u128)
+            .and_then(|v| v.checked_div(BASIS_POINTS as u128))
+            .ok_or(ErrorCode::Overflow)?;
+        u64::try_from(amount).map_err(|_| ErrorCode::Overflow.into())
+    };
+
+    let validator_fee = share(validator_share_bps)? as u128;
+    let total_weight = validator_weights
+        .iter()
+        .try_fold(0u128, |total, weight| total.checked_add(*weight as u128))
+        .ok_or(ErrorCode::Overflow)?;
+    let mut validator_amounts = Vec::with_capacity(validator_weights.len());
+    let mut validator_total: u64 = 0;
+    for weight in validator_weights {
+        let amount = validator_fee
+            .checked_mul(*weight as u128)
+            .and_then(|v| v.checked_div(total_weight))
+            .ok_or(ErrorCode::Overflow)?;
+        let amount = u64::try_from(amount).map_err(|_| ErrorCode::Overflow)?;
+        validator_total = validator_total
+            .checked_add(amount)
+            .ok_or(ErrorCode::Overflow)?;
+        validator_amounts.push(amount);
+    }
+    let protocol_amount = share(protocol_share_bps)?;
+    let compute_amount = payment_amount
+        .checked_sub(validator_total)
+        .and_then(|v| v.checked_sub(protocol_amount))
+        .ok_or(ErrorCode::Underflow)?;
+
+    Ok((compute_amount, validator_amounts, protocol_amount))
+}
+
+/// Move the compute node's payment into a per-step escrow it can sweep once the
+/// settlement hold has passed, the authority may claw it back before then. The session
+/// vault pays the escrow's rent and gets it back when the escrow closes.
+#[allow(clippy::too_many_arguments)]
+pub fn hold_compute_payment<'info>(
+    settlement_account: AccountInfo<'info>,
+    vault: &SystemAccount<'info>,
+    session: &Account<'info, Session>,
+    task: &Account<'info, Task>,
+    node_info: &Account<'info, NodeInfo>,
+    network_config: &NetworkConfig,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        network_config.payment_mint.is_none(),
+        ErrorCode::TokenSettlementHoldUnsupported
+    );
+
+    let task_key = task.key();
+    let task_index = task.task_index;
+    let task_index_bytes = task_index.to_le_bytes();
+    let seeds = &[
+        b"task_settlement".as_ref(),
+        task_key.as_ref(),
+        task_index_bytes.as_ref(),
+    ];
+    let space = 8 + TaskSettlement::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    require!(
+        vault.lamports() >= amount.checked_add(rent).ok_or(ErrorCode::Overflow)?,
+        ErrorCode::InsufficientBalance
+    );
+    let session_key = session.key();
+    let vault_seeds = &[
+        b"session_vault".as_ref(),
+        session_key.as_ref(),
+        &[session.vault_bump],
+    ];
+    let bump = init_dynamic_pda_with_payer(
+        vault.to_account_info(),
+        vault_seeds,
+        &settlement_account,
+        seeds,
+        space,
+        &crate::ID,
+        system_program,
+    )?;
+
+    let release_slot = Clock::get()?
+        .slot
+        .checked_add(network_config.settlement_hold_slots)
+        .ok_or(ErrorCode::Overflow)?;
+    let settlement = TaskSettlement {
+        session: session.key(),
+        task: task_key,
+        task_index,
+        node_info: node_info.key(),
+        amount,
+        task_type: task.task_type.clone(),
+        release_slot,
+        payer: vault.key(),
+        bump,
+    };
+    settlement.try_serialize(&mut *settlement_account.try_borrow_mut_data()?)?;
+
+    transfer_from_vault(
+        vault,
+        settlement_account,
+        &session_key,
+        session.vault_bump,
+        system_program,
+        amount,
+    )?;
+
+    emit!(TaskSettlementHeld {
+        session_slot_id: session.session_slot_id,
+        task_slot_id: task.task_slot_id,
+        task_index,
+        node: node_info.node_pubkey,
+        amount,
+        release_slot,
+    });
+
+    Ok(())
+}
+
+/// Accounts an approved task's payment moves through, shared by the validation that reaches
+/// the threshold and by force_resolve_task
+pub struct TaskPaymentAccounts<'a, 'info> {
+    pub network_config: &'a Account<'info, NetworkConfig>,
+    pub session: &'a Account<'info, Session>,
+    pub vault: &'a SystemAccount<'info>,
+    pub task: &'a Account<'info, Task>,
+    pub node_info: &'a mut Account<'info, NodeInfo>,
+    pub node_treasury: &'a SystemAccount<'info>,
+    pub protocol_treasury: &'a SystemAccount<'info>,
+    pub session_token_vault: &'a mut Option<Account<'info, TokenAccount>>,
+    pub node_token_account: &'a Option<Account<'info, TokenAccount>>,
+    pub token_program: &'a Option<Program<'info, Token>>,
+    pub task_settlement: &'a Option<UncheckedAccount<'info>>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+/// Settle an approved task: validator and protocol shares out of the vault, the compute
+/// node's part held in escrow or paid to its treasury. `validator_accounts` hold the approving
+/// validators' (node_info, treasury) pairs when the network pays them a share, see
+/// validator_treasuries for `deciding_validator`.
+pub fn settle_task_payment<'info>(
+    accounts: &mut TaskPaymentAccounts<'_, 'info>,
+    payment_amount: u64,
+    validator_accounts: &'info [AccountInfo<'info>],
+    deciding_validator: Option<Pubkey>,
+) -> Result<()> {
+    let network_config = accounts.network_config;
+    let approving_validators: Vec<Pubkey> = accounts
+        .task
+        .validations
+        .iter()
+        .filter(|v| v.status == ValidationStatus::Approved)
+        .map(|v| v.pubkey)
+        .collect();
+    // Token networks fail the split below, before any validator account is required
+    let validators =
+        if network_config.payment_mint.is_none() && network_config.validator_share_bps > 0 {
+            validator_treasuries(&approving_validators, validator_accounts, deciding_validator)?
+        } else {
+            Vec::new()
+        };
+    let weights: Vec<u64> = validators.iter().map(|(_, weight)| *weight).collect();
+    let (compute_amount, validator_amounts, protocol_amount) =
+        split_payment(network_config, payment_amount, &weights)?;
+
+    if network_config.settlement_hold_slots > 0 {
+        let settlement_account = accounts
+            .task_settlement
+            .as_ref()
+            .ok_or(ErrorCode::MissingAccount)?
+            .to_account_info();
+        hold_compute_payment(
+            settlement_account,
+            accounts.vault,
+            accounts.session,
+            accounts.task,
+            accounts.node_info,
+            network_config,
+            accounts.system_program,
+            compute_amount,
+        )?;
+    } else {
+        pay_compute_node(accounts, compute_amount)?;
+        accounts
+            .node_info
+            .record_task_payment(compute_amount, &accounts.task.task_type)?;
+    }
+    for ((treasury, _), amount) in validators.into_iter().zip(validator_amounts) {
+        if amount > 0 {
+            pay_from_session_vault(accounts, treasury.clone(), amount)?;
+        }
+    }
+    if protocol_amount > 0 {
+        pay_from_session_vault(
+            accounts,
+            accounts.protocol_treasury.to_account_info(),
+            protocol_amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Lamport transfer out of the session vault
+fn pay_from_session_vault<'info>(
+    accounts: &TaskPaymentAccounts<'_, 'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        accounts.vault.lamports() >= amount,
+        ErrorCode::InsufficientBalance
+    );
+
+    transfer_from_vault(
+        accounts.vault,
+        to,
+        &accounts.session.key(),
+        accounts.session.vault_bump,
+        accounts.system_program,
+        amount,
+    )
+}
+
+/// Pay the compute node from the session vault, in tokens when the network has a payment mint
+fn pay_compute_node(accounts: &mut TaskPaymentAccounts<'_, '_>, amount: u64) -> Result<()> {
+    let token_accounts = session_token_accounts(
+        accounts.network_config.payment_mint,
+        &accounts.session.key(),
+        accounts.session_token_vault,
+        accounts.node_token_account,
+        accounts.token_program,
+    )?;
+
+    if let Some(token_accounts) = &token_accounts {
+        // Token payments land in the node treasury's associated token account
+        require_keys_eq!(
+            token_accounts.counterparty.key(),
+            get_associated_token_address(&accounts.node_treasury.key(), &token_accounts.vault.mint),
+            ErrorCode::InvalidTokenAccount
+        );
+        require!(
+            token_accounts.vault.amount >= amount,
+            ErrorCode::InsufficientBalance
+        );
+
+        transfer_from_session_token_vault(
+            token_accounts,
+            accounts.session,
+            &accounts.network_config.key(),
+            amount,
+        )?;
+    } else {
+        pay_from_session_vault(accounts, accounts.node_treasury.to_account_info(), amount)?;
+    }
+
+    if let Some(session_token_vault) = accounts.session_token_vault.as_mut() {
+        session_token_vault.reload()?;
+    }
+
+    Ok(())
+}
+
+/// Balance reported in session events, the token vault's on networks with a payment mint
+pub fn session_vault_balance(
+    network_config: &NetworkConfig,
+    vault: &SystemAccount,
+    session_token_vault: &Option<Account<TokenAccount>>,
+) -> u64 {
+    match (&network_config.payment_mint, session_token_vault) {
+        (Some(_), Some(session_token_vault)) => session_token_vault.amount,
+        _ => vault.lamports(),
+    }
+}
+
+/// Take the session's completion hook program off the end of the remaining accounts
+pub fn split_completion_hook<'info>(
+    session: &Session,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> (
+    &'info [AccountInfo<'info>],
+    Option<&'info AccountInfo<'info>>,
+) {
+    match (
+        session.completion_hook_program,
+        remaining_accounts.split_last(),
+    ) {
+        (Some(hook_program), Some((last, rest))) if last.key() == hook_program => {
+            (rest, Some(last))
+        }
+        _ => (remaining_accounts, None),
+    }
+}
+
+/// Call `on_session_completed` on the session's hook program with the completed session.
+/// A failing hook fails the settlement, the owner can clear it with `set_completion_hook`.
+pub fn invoke_completion_hook<'info>(
+    session: &Account<'info, Session>,
+    completion_hook: Option<&'info AccountInfo<'info>>,
+) -> Result<()> {
+    let Some(hook_program) = session.completion_hook_program else {
+        return Ok(());
+    };
+    let hook_program_account = completion_hook.ok_or(ErrorCode::CompletionHookMissing)?;
+    require!(
+        hook_program_account.executable,
+        ErrorCode::InvalidCompletionHook
+    );
+
+    let args = CompletionHookArgs {
+        session_slot_id: session.session_slot_id,
+        owner: session.owner,
+        final_iteration: session.current_iteration,
+        total_paid: session.total_paid,
+    };
+    let mut data = COMPLETION_HOOK_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    let instruction = Instruction {
+        program_id: hook_program,
+        accounts: vec![AccountMeta::new_readonly(session.key(), false)],
+        data,
+    };
+    invoke(
+        &instruction,
+        &[session.to_account_info(), hook_program_account.clone()],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_version_at_least_compares_major_minor_patch() {
+        let minimum = SemanticVersion::new(1, 2, 3);
+
+        assert!(is_version_at_least(&SemanticVersion::new(1, 2, 3), &minimum));
+
+        assert!(is_version_at_least(&SemanticVersion::new(2, 0, 0), &minimum));
+        assert!(!is_version_at_least(&SemanticVersion::new(0, 9, 9), &minimum));
+
+        assert!(is_version_at_least(&SemanticVersion::new(1, 3, 0), &minimum));
+        assert!(!is_version_at_least(&SemanticVersion::new(1, 1, 9), &minimum));
+
+        assert!(is_version_at_least(&SemanticVersion::new(1, 2, 4), &minimum));
+        assert!(!is_version_at_least(&SemanticVersion::new(1, 2, 2), &minimum));
+    }
+
+    #[test]
+    fn to_hex_pads_each_byte() {
+        assert_eq!(to_hex(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+        assert_eq!(to_hex(&[]), "");
+    }
+}
@@ -3,12 +3,32 @@ use crate::setup::test_data::{
     DEFAULT_CONTRIBUTION_AMOUNT, DEFAULT_GOAL_SPECIFICATION_CID, DEFAULT_INITIAL_DEPOSIT,
     DEFAULT_REQUIRED_VALIDATIONS,
 };
-use crate::setup::{Accounts, Instructions, TestFixture};
-use dac_client::types::{CodeMeasurement, SemanticVersion};
+use crate::setup::{Accounts, Helpers, Instructions, SubmitTaskValidationMessage, TestFixture};
+use dac_client::accounts::Task;
+use dac_client::instructions::{
+    ExtendTasksBuilder, GetSharePrecisionBuilder, InitializeNetworkBuilder,
+};
+use dac_client::types::{
+    AccountMigrated, ApprovedStatus, BatchValidationSubmitted, CancelledTaskReclaimed,
+    CodeMeasurement, CodeMeasurementListed, ContributionMade, GenesisVerified,
+    NetworkConfigMigrated, NetworkStats, NodeCapacitySet, NodePoolMembershipChanged,
+    NodeRewardsWithdrawn, NodeSummary, NodeTreasuryBalance, PoolMembershipChange, SemanticVersion,
+    SessionAction, SessionCompleted, SessionCreated, SessionExposure, SessionSet,
+    TaskAssignmentReceipt, TaskCheckpointed, TaskClaimed, TaskInputSeeded, TaskInvalidated,
+    TaskResultSubmitted, TaskValidationSubmitted, ValidationStatus, ValidationTally,
+    ValidatorsAssigned,
+};
 use dac_client::{AgentStatus, NodeStatus, NodeType, SessionStatus, TaskStatus, TaskType};
+use litesvm::types::TransactionResult;
+use litesvm_token::{CreateAssociatedTokenAccount, CreateMint, MintTo};
 use sha2::{Digest, Sha256};
-use solana_sdk::signature::Signer;
-use utils::Utils;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use utils::{
+    create_ed25519_instruction_with_signature, create_ed25519_instruction_with_signatures, Utils,
+};
 
 mod setup;
 
@@ -26,6 +46,7 @@ fn test_initialize_network_without_remaining_accounts() {
         allocate_tasks,
         DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
         DEFAULT_REQUIRED_VALIDATIONS,
+        None,
         &[],
     );
 
@@ -48,6 +69,56 @@ fn test_initialize_network_without_remaining_accounts() {
     }
 }
 
+#[test]
+fn test_initialize_network_genesis_nonce_is_deterministic() {
+    let genesis_nonce = [7u8; 32];
+    let mut first = TestFixture::new();
+    let mut second = TestFixture::new();
+    let mut other_nonce = TestFixture::new();
+
+    // Same authority on separate chains so only the nonce decides the genesis
+    second.authority = first.authority.insecure_clone();
+    other_nonce.authority = first.authority.insecure_clone();
+    let authority = first.authority.insecure_clone();
+    second
+        .svm
+        .airdrop(&authority.pubkey(), LAMPORTS_PER_SOL)
+        .expect("Failed to fund authority");
+    other_nonce
+        .svm
+        .airdrop(&authority.pubkey(), LAMPORTS_PER_SOL)
+        .expect("Failed to fund authority");
+
+    for (fixt, nonce) in [
+        (&mut first, genesis_nonce),
+        (&mut second, genesis_nonce),
+        (&mut other_nonce, [8u8; 32]),
+    ] {
+        let network_config_pda = fixt.find_network_config_pda().0;
+        let result = fixt.initialize_network(
+            &authority,
+            &network_config_pda,
+            DEFAULT_CID_CONFIG.to_string(),
+            0,
+            DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
+            DEFAULT_REQUIRED_VALIDATIONS,
+            Some(nonce),
+            &[],
+        );
+        assert!(result.is_ok(), "Failed to initialize network");
+    }
+
+    let first_genesis = first.get_network_config().genesis_hash;
+
+    assert_eq!(
+        first_genesis,
+        compute_genesis_hash_with_nonce(&authority.pubkey(), genesis_nonce)
+    );
+    assert_eq!(first_genesis, second.get_network_config().genesis_hash);
+    assert_ne!(first_genesis, other_nonce.get_network_config().genesis_hash);
+    assert_ne!(first_genesis, compute_genesis_hash());
+}
+
 #[test]
 fn test_initialize_network_with_remaining_accounts() {
     let mut fixt = TestFixture::new();
@@ -64,6 +135,7 @@ fn test_initialize_network_with_remaining_accounts() {
         allocate_tasks,
         DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
         DEFAULT_REQUIRED_VALIDATIONS,
+        None,
         &remaining_accounts,
     );
 
@@ -165,6 +237,104 @@ fn test_update_network_config() {
     }
 }
 
+#[test]
+fn test_get_network_stats() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_create_agent()
+        .with_create_session(false)
+        .with_create_session(true);
+
+    let result = fixt.get_network_stats(&fixt.payer.insecure_clone());
+
+    match result {
+        Ok(metadata) => {
+            let stats: NetworkStats = Helpers::decode_event(&metadata.logs, "NetworkStats")
+                .expect("NetworkStats event not emitted");
+
+            assert_eq!(stats.agent_count, 2);
+            assert_eq!(stats.session_count, 2);
+            assert_eq!(stats.task_count, DEFAULT_ALLOCATE_TASKS + 2);
+            assert_eq!(stats.approved_public_nodes, 2);
+            assert_eq!(stats.approved_confidential_nodes, 1);
+            assert_eq!(stats.required_validations, DEFAULT_REQUIRED_VALIDATIONS);
+        }
+        Err(e) => panic!("Failed to get network stats: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_get_share_precision() {
+    let mut fixt = TestFixture::new();
+
+    let result = fixt.get_share_precision(&fixt.payer.insecure_clone());
+
+    match result {
+        Ok(metadata) => {
+            let precision = u64::from_le_bytes(
+                metadata.return_data.data[..8]
+                    .try_into()
+                    .expect("Missing return data"),
+            );
+            assert_eq!(precision, SHARE_PRECISION);
+            assert_eq!(precision, 1_000_000_000);
+        }
+        Err(e) => panic!("Failed to get share precision: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_node_counts_match_node_roles() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+
+    let assert_pool_counts = |fixt: &TestFixture, public: usize, confidential: usize| {
+        let network_config = fixt.get_network_config();
+        assert_eq!(network_config.approved_public_nodes.len(), public);
+        assert_eq!(network_config.approved_confidential_nodes.len(), confidential);
+    };
+
+    fixt = fixt
+        .with_register_public_node()
+        .with_register_confidential_node()
+        .with_register_validator_node();
+    assert_pool_counts(&fixt, 0, 0);
+
+    // A claimed confidential node is compute-capable right away and only joins its own pool
+    fixt = fixt.with_claim_confidential_node();
+    assert_pool_counts(&fixt, 0, 1);
+
+    // Public nodes wait for validation before they count
+    fixt = fixt.with_claim_public_node().with_claim_validator_node();
+    assert_pool_counts(&fixt, 0, 1);
+
+    fixt = fixt
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true);
+    assert_pool_counts(&fixt, 2, 1);
+
+    // Re-activating an approved node doesn't list it twice
+    let authority = fixt.authority.insecure_clone();
+    let confidential_node = fixt.confidential_node.pubkey();
+    let result = fixt.activate_node(&authority, &confidential_node);
+    assert!(result.is_ok(), "Activation retry should succeed");
+    assert_pool_counts(&fixt, 2, 1);
+
+    let network_config = fixt.get_network_config();
+    assert!(network_config
+        .approved_confidential_nodes
+        .contains(&confidential_node));
+    assert!(!network_config.approved_public_nodes.contains(&confidential_node));
+}
+
 #[test]
 fn test_register_public_node() {
     let mut fixt = TestFixture::new().with_initialize_network();
@@ -279,6 +449,92 @@ fn test_claim_confidential_node() {
     }
 }
 
+#[test]
+fn test_claim_confidential_node_rejects_reclaim() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node();
+
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let original_tee_pubkey = fixt.tee_signing_keypair.pubkey();
+    let other_tee_pubkey = fixt.create_keypair().pubkey();
+
+    // An unapproved measurement leaves the pending node untouched
+    let result = fixt.claim_confidential_node(&confidential_node, [9u8; 32], other_tee_pubkey);
+    let err = result.expect_err("Should reject an unapproved code measurement");
+    assert!(Helpers::has_error_code(&err.meta.logs, "CodeMeasurementNotApproved"));
+
+    let node_info = fixt.get_node_info(&confidential_node.pubkey());
+    assert_eq!(node_info.status, NodeStatus::PendingClaim);
+    assert_eq!(node_info.code_measurement, None);
+    assert_eq!(node_info.tee_signing_pubkey, None);
+    assert!(fixt.get_network_config().approved_confidential_nodes.is_empty());
+
+    let result = fixt.claim_confidential_node(
+        &confidential_node,
+        DEFAULT_CODE_MEASUREMENT,
+        original_tee_pubkey,
+    );
+    assert!(result.is_ok(), "Failed to claim confidential node");
+
+    let result = fixt.claim_confidential_node(
+        &confidential_node,
+        DEFAULT_CODE_MEASUREMENT,
+        other_tee_pubkey,
+    );
+    let err = result.expect_err("Should reject re-claiming an active node");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidNodeStatus"));
+
+    let node_info = fixt.get_node_info(&confidential_node.pubkey());
+    assert_eq!(node_info.status, NodeStatus::Active);
+    assert_eq!(node_info.tee_signing_pubkey, Some(original_tee_pubkey));
+    assert_eq!(fixt.get_network_config().approved_confidential_nodes.len(), 1);
+}
+
+#[test]
+fn test_claim_confidential_node_requires_minimum_measurement_version() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node();
+
+    let authority = fixt.authority.insecure_clone();
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let tee_pubkey = fixt.tee_signing_keypair.pubkey();
+    let current_measurement = [2u8; 32];
+    let minimum = SemanticVersion {
+        major: 1,
+        minor: 0,
+        patch: 0,
+    };
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.min_measurement_version = Some(minimum.clone());
+        args.new_code_measurement = Some(CodeMeasurement {
+            measurement: current_measurement,
+            version: minimum.clone(),
+        });
+    });
+    assert!(result.is_ok(), "Failed to set minimum measurement version");
+
+    // The default measurement is still approved, at version 0.0.0
+    let err = fixt
+        .claim_confidential_node(&confidential_node, DEFAULT_CODE_MEASUREMENT, tee_pubkey)
+        .expect_err("Should reject a measurement below the minimum version");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "CodeMeasurementOutdated"
+    ));
+
+    let result = fixt.claim_confidential_node(&confidential_node, current_measurement, tee_pubkey);
+    assert!(
+        result.is_ok(),
+        "Failed to claim with a current measurement: {:#?}",
+        result.err()
+    );
+    let node_info = fixt.get_node_info(&confidential_node.pubkey());
+    assert_eq!(node_info.code_measurement, Some(current_measurement));
+}
+
 #[test]
 fn test_activate_node_public() {
     let mut fixt = TestFixture::new()
@@ -307,20 +563,47 @@ fn test_activate_node_confidential() {
         .with_register_confidential_node()
         .with_claim_confidential_node();
 
-    // Confidential nodes are already active after claim, so we need to test edge case
-    // For now, test that it works on a node that's already active (should fail)
+    // Confidential nodes are already active and approved after claim
     let node_info_before = fixt.get_node_info(&fixt.confidential_node.pubkey());
     assert_eq!(node_info_before.status, NodeStatus::Active);
 
-    // Test activating a node that's not in AwaitingValidation (should fail)
+    // Activating an already active node is a no-op
     let result = fixt.activate_node(
         &fixt.authority.insecure_clone(),
         &fixt.confidential_node.pubkey(),
     );
-    assert!(
-        result.is_err(),
-        "Should fail to activate already active node"
-    );
+    assert!(result.is_ok(), "Activating an active node should be a no-op");
+
+    let network_config = fixt.get_network_config();
+    assert_eq!(network_config.approved_confidential_nodes.len(), 1);
+}
+
+#[test]
+fn test_activate_node_is_idempotent() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node();
+
+    let authority = fixt.authority.insecure_clone();
+    let node_pubkey = fixt.public_node.pubkey();
+
+    let result = fixt.activate_node(&authority, &node_pubkey);
+    assert!(result.is_ok(), "Failed to activate public node");
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.activate_node(&authority, &node_pubkey);
+
+    match result {
+        Ok(_) => {
+            let node_info = fixt.get_node_info(&node_pubkey);
+            let network_config = fixt.get_network_config();
+
+            assert_eq!(node_info.status, NodeStatus::Active);
+            assert_eq!(network_config.approved_public_nodes, vec![node_pubkey]);
+        }
+        Err(e) => panic!("Retrying activate_node should succeed: {:#?}", e),
+    }
 }
 
 #[test]
@@ -469,6 +752,59 @@ fn test_create_agent() {
     }
 }
 
+#[test]
+fn test_create_agent_with_allowed_cid_prefix() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+
+    let authority = fixt.authority.insecure_clone();
+    let agent_owner = fixt.agent_owner.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.allowed_cid_prefixes = Some(vec!["bafy".to_string()]);
+    });
+    assert!(result.is_ok(), "Failed to set allowed CID prefixes");
+    assert_eq!(
+        fixt.get_network_config().allowed_cid_prefixes,
+        vec!["bafy".to_string()]
+    );
+
+    let agent_config_cid = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+    let result = fixt.create_agent(&agent_owner, agent_config_cid.to_string());
+
+    match result {
+        Ok(_) => {
+            let agent = fixt.get_agent(&network_config_pda, 0);
+            assert_eq!(agent.agent_config_cid, agent_config_cid.to_string());
+        }
+        Err(e) => panic!("Failed to create agent with allowed CID: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_create_agent_with_disallowed_cid_prefix() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+
+    let authority = fixt.authority.insecure_clone();
+    let agent_owner = fixt.agent_owner.insecure_clone();
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.allowed_cid_prefixes = Some(vec!["bafy".to_string()]);
+    });
+    assert!(result.is_ok(), "Failed to set allowed CID prefixes");
+
+    // CIDv0 no longer matches the allow-list
+    let result = fixt.create_agent(&agent_owner, DEFAULT_AGENT_CONFIG_CID.to_string());
+    assert!(result.is_err(), "Should reject CID outside the allow-list");
+    assert_eq!(fixt.get_network_config().agent_count, 0);
+
+    let outsider = fixt.create_keypair();
+    let result = fixt.update_network_config_with(&outsider, |args| {
+        args.allowed_cid_prefixes = Some(Vec::new());
+    });
+    assert!(result.is_err(), "Non-authority should not update CID prefixes");
+}
+
 #[test]
 fn test_create_multiple_agents() {
     let mut fixt = TestFixture::new().with_initialize_network();
@@ -544,6 +880,7 @@ fn test_set_session() {
     assert_eq!(owner_contribution.contributor, fixt.agent_owner.pubkey());
     assert_eq!(owner_contribution.shares, DEFAULT_INITIAL_DEPOSIT);
     assert_eq!(owner_contribution.refund_amount, 0);
+    assert!(owner_contribution.is_owner);
 
     assert_eq!(task.status, TaskStatus::Pending);
     assert_eq!(task.task_type, TaskType::Completion(0));
@@ -576,6 +913,7 @@ fn test_contribute_to_session() {
     assert_eq!(contribution.contributor, contributor.pubkey());
     assert!(contribution.shares > 0, "Contributor should have shares");
     assert_eq!(contribution.refund_amount, 0);
+    assert!(!contribution.is_owner);
     assert!(
         session.total_shares > DEFAULT_INITIAL_DEPOSIT,
         "Total shares should include contributor's shares"
@@ -878,7 +1216,10 @@ fn test_submit_public_task_validation_approved() {
             assert_eq!(compute_node_info.total_tasks_completed, 1);
             assert_eq!(compute_node_info.total_earned, payment_amount);
 
-            let (node_treasury_pda, _) = fixt.find_node_treasury_pda(&compute_node_info_pda);
+            let (node_treasury_pda, _) = fixt.find_node_treasury_pda(
+                &compute_node_info_pda,
+                compute_node_info.registration_epoch,
+            );
             let node_treasury_lamports = fixt.svm.get_lamports(&node_treasury_pda);
 
             assert!(
@@ -969,10 +1310,14 @@ fn test_submit_confidential_task_validation_approved() {
     );
     assert!(result.is_ok(), "Failed to submit task result");
 
-    let mut hasher = Sha256::new();
-    hasher.update(input_cid.as_bytes());
-    hasher.update(output_cid.as_bytes());
-    let validation_proof: [u8; 32] = hasher.finalize().into();
+    let validation_proof = Helpers::compute_validation_proof(
+        session_slot_id,
+        task_slot_id,
+        1,
+        &TaskType::Completion(0),
+        &input_cid,
+        &output_cid,
+    );
 
     // Assigned validator is second_conf (compute is fixt.confidential_node, pool minus compute = [second_conf])
     let ed25519_ix = crate::setup::Helpers::create_ed25519_instruction_to_submit_task_validation(
@@ -982,6 +1327,7 @@ fn test_submit_confidential_task_validation_approved() {
         validation_proof,
         true,
         false,
+        0,
         &second_tee.insecure_clone(),
     );
 
@@ -999,6 +1345,7 @@ fn test_submit_confidential_task_validation_approved() {
             let network_config_pda = fixt.find_network_config_pda().0;
             let session = fixt.get_session(&network_config_pda, session_slot_id);
             let task = fixt.get_task(&network_config_pda, task_slot_id);
+            let compute_node_info = fixt.get_node_info(&fixt.confidential_node.pubkey());
             let (compute_node_info_pda, _) =
                 fixt.find_node_info_pda(&fixt.confidential_node.pubkey());
 
@@ -1014,7 +1361,10 @@ fn test_submit_confidential_task_validation_approved() {
             assert_eq!(session.locked_for_tasks, 0);
             assert_eq!(session.current_iteration, 1);
 
-            let (node_treasury_pda, _) = fixt.find_node_treasury_pda(&compute_node_info_pda);
+            let (node_treasury_pda, _) = fixt.find_node_treasury_pda(
+                &compute_node_info_pda,
+                compute_node_info.registration_epoch,
+            );
             let node_treasury_lamports = fixt.svm.get_lamports(&node_treasury_pda);
 
             assert!(
@@ -1028,6 +1378,134 @@ fn test_submit_confidential_task_validation_approved() {
     }
 }
 
+#[test]
+fn test_confidential_task_validation_rejects_oversized_output() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(true);
+
+    // Second confidential node for 1 compute + 1 validator
+    let second_conf_owner = fixt.create_keypair();
+    let second_conf = fixt.create_keypair();
+    let second_tee = fixt.create_keypair();
+    let result = fixt.register_node(
+        &second_conf_owner,
+        &second_conf.pubkey(),
+        NodeType::Confidential,
+    );
+    assert!(result.is_ok(), "Failed to register second confidential node");
+    let result = fixt.claim_confidential_node(
+        &second_conf,
+        DEFAULT_CODE_MEASUREMENT,
+        second_tee.pubkey(),
+    );
+    assert!(result.is_ok(), "Failed to claim second confidential node");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.confidential_node.pubkey();
+    let max_output_size = 1024;
+
+    let result = fixt.set_session_with(
+        &fixt.agent_owner.insecure_clone(),
+        session_slot_id,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_INITIAL_DEPOSIT,
+        compute_node,
+        TaskType::Completion(0),
+        |_, args| {
+            args.max_output_size = Some(max_output_size);
+        },
+    );
+    assert!(result.is_ok(), "Failed to set session");
+
+    let result = fixt.claim_task(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+    let result = fixt.submit_task_result(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        input_cid.clone(),
+        output_cid.clone(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    let validation_proof = Helpers::compute_validation_proof(
+        session_slot_id,
+        task_slot_id,
+        task.task_index,
+        &TaskType::Completion(0),
+        &input_cid,
+        &output_cid,
+    );
+
+    let oversized_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        session_slot_id,
+        task_slot_id,
+        500_000_000,
+        validation_proof,
+        true,
+        false,
+        max_output_size + 1,
+        &second_tee,
+    );
+    let result = fixt.submit_confidential_task_validation(
+        &second_conf,
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        &oversized_ix,
+    );
+    assert!(result.is_err(), "Should reject output above max_output_size");
+
+    let ed25519_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        session_slot_id,
+        task_slot_id,
+        500_000_000,
+        validation_proof,
+        true,
+        false,
+        max_output_size,
+        &second_tee,
+    );
+    let result = fixt.submit_confidential_task_validation(
+        &second_conf,
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        &ed25519_ix,
+    );
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+            assert_eq!(task.output_size, max_output_size);
+            assert_eq!(task.output_cid, Some(output_cid));
+        }
+        Err(e) => panic!("Failed to validate output within limit: {:#?}", e),
+    }
+}
+
 #[test]
 fn test_confidential_task_validation_wrong_tee_signing_pubkey() {
     let mut fixt = TestFixture::new()
@@ -1105,10 +1583,14 @@ fn test_confidential_task_validation_wrong_tee_signing_pubkey() {
     );
     assert!(result.is_ok(), "Failed to submit task result");
 
-    let mut hasher = Sha256::new();
-    hasher.update(input_cid.as_bytes());
-    hasher.update(output_cid.as_bytes());
-    let validation_proof: [u8; 32] = hasher.finalize().into();
+    let validation_proof = Helpers::compute_validation_proof(
+        session_slot_id,
+        task_slot_id,
+        1,
+        &TaskType::Completion(0),
+        &input_cid,
+        &output_cid,
+    );
 
     let attacker_tee_keypair = fixt.create_keypair();
     let ed25519_ix = crate::setup::Helpers::create_ed25519_instruction_to_submit_task_validation(
@@ -1118,6 +1600,7 @@ fn test_confidential_task_validation_wrong_tee_signing_pubkey() {
         validation_proof,
         true,
         false,
+        0,
         &attacker_tee_keypair, // Wrong TEE signing key
     );
 
@@ -1134,3 +1617,7815 @@ fn test_confidential_task_validation_wrong_tee_signing_pubkey() {
         "Should fail because TEE signing pubkey doesn't match stored pubkey"
     );
 }
+
+#[test]
+fn test_claim_task_rejects_confidential_node_on_public_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+
+    let result = fixt.claim_task(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+
+    assert!(
+        result.is_err(),
+        "Confidential node must not claim a task from a public session"
+    );
+}
+
+#[test]
+fn test_public_task_validation_rejects_confidential_validator() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &fixt.public_node.pubkey(),
+        500_000_000,
+        true,
+        false,
+    );
+
+    assert!(
+        result.is_err(),
+        "Confidential node must not validate a task from a public session"
+    );
+}
+
+#[test]
+fn test_confidential_session_public_validation_fallback_enabled() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(true)
+        .with_set_session_for_confidential(0, TaskType::Completion(0));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.confidential_public_fallback = Some(true);
+    });
+    assert!(result.is_ok(), "Failed to enable confidential public fallback");
+    assert!(fixt.get_network_config().confidential_public_fallback);
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let payment_amount = 500_000_000;
+
+    // Only confidential node is the compute node, so the validator must come from the public pool
+    let result = fixt.claim_task(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task with public fallback");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.validations.len(), 1);
+    assert_eq!(task.validations[0].pubkey, fixt.public_node.pubkey());
+
+    let result = fixt.submit_task_result(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &fixt.confidential_node.pubkey(),
+        payment_amount,
+        true,
+        false,
+    );
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+            assert_eq!(task.status, TaskStatus::Pending);
+            assert_eq!(session.current_iteration, 1);
+            assert_eq!(session.locked_for_tasks, 0);
+        }
+        Err(e) => panic!("Public fallback validation failed: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_confidential_session_public_validation_fallback_disabled() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(true)
+        .with_set_session_for_confidential(0, TaskType::Completion(0));
+
+    assert!(!fixt.get_network_config().confidential_public_fallback);
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+
+    let result = fixt.claim_task(
+        &fixt.confidential_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+
+    assert!(
+        result.is_err(),
+        "Should fail with NotEnoughValidators when fallback is disabled"
+    );
+}
+
+#[test]
+fn test_claim_task_rejects_mismatched_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_create_session(false)
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let other_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        0,
+        other_task_slot_id,
+        1_000_000_000,
+        10,
+    );
+
+    assert!(
+        result.is_err(),
+        "Should fail when the task does not belong to the session"
+    );
+}
+
+#[test]
+fn test_submit_task_result_rejects_mismatched_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_create_session(false)
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let other_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        1,
+        other_task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        0,
+        other_task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+
+    assert!(
+        result.is_err(),
+        "Should fail when the task does not belong to the session"
+    );
+}
+
+#[test]
+fn test_public_task_validation_rejects_mismatched_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_create_session(false)
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let other_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        1,
+        other_task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        1,
+        other_task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        0,
+        other_task_slot_id,
+        &fixt.public_node.pubkey(),
+        500_000_000,
+        true,
+        false,
+    );
+
+    assert!(
+        result.is_err(),
+        "Should fail when the task does not belong to the session"
+    );
+}
+
+#[test]
+fn test_confidential_task_validation_rejects_mismatched_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let second_conf_owner = fixt.create_keypair();
+    let second_conf = fixt.create_keypair();
+    let second_tee = fixt.create_keypair();
+    let result = fixt.register_node(
+        &second_conf_owner,
+        &second_conf.pubkey(),
+        NodeType::Confidential,
+    );
+    assert!(result.is_ok(), "Failed to register second confidential node");
+    let result = fixt.claim_confidential_node(
+        &second_conf,
+        DEFAULT_CODE_MEASUREMENT,
+        second_tee.pubkey(),
+    );
+    assert!(result.is_ok(), "Failed to claim second confidential node");
+
+    let mut fixt = fixt
+        .with_create_session(true)
+        .with_set_session_for_confidential(0, TaskType::Completion(0))
+        .with_create_session(true)
+        .with_set_session_for_confidential(0, TaskType::Completion(0));
+
+    let other_task_slot_id = fixt.find_session_task_slot_id(1);
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+
+    let result = fixt.claim_task(
+        &fixt.confidential_node.insecure_clone(),
+        1,
+        other_task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.confidential_node.insecure_clone(),
+        1,
+        other_task_slot_id,
+        input_cid.clone(),
+        output_cid.clone(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let validation_proof = Helpers::compute_validation_proof(
+        1,
+        other_task_slot_id,
+        1,
+        &TaskType::Completion(0),
+        &input_cid,
+        &output_cid,
+    );
+
+    let ed25519_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        0,
+        other_task_slot_id,
+        500_000_000,
+        validation_proof,
+        true,
+        false,
+        0,
+        &second_tee,
+    );
+
+    let result = fixt.submit_confidential_task_validation(
+        &second_conf,
+        0,
+        other_task_slot_id,
+        &fixt.confidential_node.pubkey(),
+        &ed25519_ix,
+    );
+
+    assert!(
+        result.is_err(),
+        "Should fail when the task does not belong to the session"
+    );
+}
+
+#[test]
+fn test_confidential_task_validation_rejects_replayed_proof() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let second_conf_owner = fixt.create_keypair();
+    let second_conf = fixt.create_keypair();
+    let second_tee = fixt.create_keypair();
+    let result = fixt.register_node(
+        &second_conf_owner,
+        &second_conf.pubkey(),
+        NodeType::Confidential,
+    );
+    assert!(result.is_ok(), "Failed to register second confidential node");
+    let result = fixt.claim_confidential_node(
+        &second_conf,
+        DEFAULT_CODE_MEASUREMENT,
+        second_tee.pubkey(),
+    );
+    assert!(result.is_ok(), "Failed to claim second confidential node");
+
+    let mut fixt = fixt
+        .with_create_session(true)
+        .with_set_session_for_confidential(0, TaskType::Completion(0))
+        .with_create_session(true)
+        .with_set_session_for_confidential(0, TaskType::Completion(0));
+
+    // Both sessions produce identical CIDs
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+    for session_slot_id in 0..2 {
+        let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+        let result = fixt.claim_task(
+            &fixt.confidential_node.insecure_clone(),
+            session_slot_id,
+            task_slot_id,
+            1_000_000_000,
+            10,
+        );
+        assert!(result.is_ok(), "Failed to claim task");
+
+        let result = fixt.submit_task_result(
+            &fixt.confidential_node.insecure_clone(),
+            session_slot_id,
+            task_slot_id,
+            input_cid.clone(),
+            output_cid.clone(),
+            None,
+            1,
+        );
+        assert!(result.is_ok(), "Failed to submit task result");
+    }
+
+    let first_task_slot_id = fixt.find_session_task_slot_id(0);
+    let second_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    // Proof bound to the first task, replayed on the second
+    let replayed_proof = Helpers::compute_validation_proof(
+        0,
+        first_task_slot_id,
+        1,
+        &TaskType::Completion(0),
+        &input_cid,
+        &output_cid,
+    );
+    let ed25519_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        1,
+        second_task_slot_id,
+        500_000_000,
+        replayed_proof,
+        true,
+        false,
+        0,
+        &second_tee,
+    );
+
+    let result = fixt.submit_confidential_task_validation(
+        &second_conf,
+        1,
+        second_task_slot_id,
+        &fixt.confidential_node.pubkey(),
+        &ed25519_ix,
+    );
+
+    assert!(
+        result.is_err(),
+        "Proof from one task must not validate another task with identical CIDs"
+    );
+}
+
+#[test]
+fn test_submit_task_result_rejects_duplicate_result() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.reject_duplicate_results = Some(true);
+    });
+    assert!(result.is_ok(), "Failed to enable duplicate result guard");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        input_cid.clone(),
+        output_cid.clone(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &fixt.public_node.pubkey(),
+        500_000_000,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate task");
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task for second iteration");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        input_cid,
+        output_cid,
+        None,
+        1,
+    );
+
+    let err = result.expect_err("Should fail when resubmitting the previously validated result");
+    assert!(Helpers::has_error_code(&err.meta.logs, "DuplicateResult"));
+}
+
+#[test]
+fn test_agent_quorum_independent_of_node_quorum() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node();
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.agent_required_validations = Some(2);
+    });
+    assert!(result.is_ok(), "Failed to set agent quorum");
+
+    let network_config = fixt.get_network_config();
+    assert_eq!(network_config.required_validations, DEFAULT_REQUIRED_VALIDATIONS);
+    assert_eq!(network_config.agent_required_validations, 2);
+
+    // Node quorum stays at one approval
+    let mut fixt = fixt
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent();
+    let public_node_info = fixt.get_node_info(&fixt.public_node.pubkey());
+    assert_eq!(public_node_info.status, NodeStatus::Active);
+
+    // Agent needs two approvals
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let result = fixt.validate_agent(&fixt.confidential_node.insecure_clone(), 0);
+    assert!(result.is_ok(), "Failed first agent validation");
+    assert_eq!(
+        fixt.get_agent(&network_config_pda, 0).status,
+        AgentStatus::Pending
+    );
+
+    let result = fixt.validate_agent(&fixt.public_node.insecure_clone(), 0);
+    assert!(result.is_ok(), "Failed second agent validation");
+    assert_eq!(
+        fixt.get_agent(&network_config_pda, 0).status,
+        AgentStatus::Active
+    );
+}
+
+#[test]
+fn test_owner_withdrawal_locked_before_first_iteration() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let err = fixt
+        .withdraw_from_session(&owner, 0, DEFAULT_INITIAL_DEPOSIT / 2)
+        .expect_err("Owner should not withdraw before the first iteration completes");
+    assert!(Helpers::has_error_code(&err.meta.logs, "WithdrawalLocked"));
+}
+
+#[test]
+fn test_owner_withdrawal_allowed_after_first_iteration() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        500_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &fixt.public_node.pubkey(),
+        100_000_000,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate task");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    assert_eq!(
+        fixt.get_session(&network_config_pda, session_slot_id)
+            .current_iteration,
+        1
+    );
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let result = fixt.withdraw_from_session(&owner, session_slot_id, DEFAULT_INITIAL_DEPOSIT / 2);
+
+    assert!(
+        result.is_ok(),
+        "Owner should withdraw after the first iteration: {:#?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_claim_task_emits_validators_assigned() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+
+    match result {
+        Ok(metadata) => {
+            let event: ValidatorsAssigned =
+                Helpers::decode_event(&metadata.logs, "ValidatorsAssigned")
+                    .expect("ValidatorsAssigned event not emitted");
+
+            assert_eq!(event.session_slot_id, session_slot_id);
+            assert_eq!(event.task_slot_id, task_slot_id);
+            assert_eq!(event.validators, vec![fixt.validator_node.pubkey()]);
+        }
+        Err(e) => panic!("Failed to claim task: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_force_resolve_stalled_task_validation() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let stall_slots = 10;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.validation_stall_slots = Some(stall_slots);
+    });
+    assert!(result.is_ok(), "Failed to set validation stall window");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.public_node.pubkey();
+    let resolver = fixt.authority.insecure_clone();
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    // Assigned validator never votes
+    let result = fixt.force_resolve_task(&resolver, session_slot_id, task_slot_id, &compute_node);
+    assert!(
+        result.is_err(),
+        "Should not force resolve before the stall window elapses"
+    );
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    fixt.svm
+        .warp_to_slot(task.validation_started_slot + stall_slots);
+    fixt.svm.expire_blockhash();
+
+    let outsider = fixt.create_keypair();
+    let err = fixt
+        .force_resolve_task(&outsider, session_slot_id, task_slot_id, &compute_node)
+        .expect_err("Only the authority can force resolve");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidAuthority"));
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let err = fixt
+        .force_resolve_task(&owner, session_slot_id, task_slot_id, &compute_node)
+        .expect_err("The session owner cannot rule on their own payment");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidAuthority"));
+
+    let result = fixt.force_resolve_task(&resolver, session_slot_id, task_slot_id, &compute_node);
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+            assert_eq!(task.status, TaskStatus::Ready);
+            assert_eq!(task.pending_input_cid, None);
+            assert_eq!(task.pending_output_cid, None);
+            assert!(task.validations.is_empty());
+            assert_eq!(session.locked_for_tasks, 0);
+            assert_eq!(session.current_iteration, 0);
+        }
+        Err(e) => panic!("Failed to force resolve task: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_force_resolved_approval_splits_payment() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let stall_slots = 10;
+    let protocol_share_bps = 1_000u16;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.validation_stall_slots = Some(stall_slots);
+        args.protocol_share_bps = Some(protocol_share_bps);
+    });
+    assert!(result.is_ok(), "Failed to update network config");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.public_node.insecure_clone();
+    let max_task_cost = 100_000_000;
+
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        max_task_cost,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    // The only vote cast approves, then the validators stall
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+    let mut task = fixt.get_task(&network_config_pda, task_slot_id);
+    task.validations[0].status = ValidationStatus::Approved;
+    let data = borsh::to_vec(&task).unwrap();
+    let mut account = fixt.svm.get_account(&task_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(task_pda, account).unwrap();
+    fixt.svm
+        .warp_to_slot(task.validation_started_slot + stall_slots);
+
+    let node_treasury = fixt.get_node_info(&compute_node.pubkey()).node_treasury;
+    let (protocol_treasury, _) = fixt.find_protocol_treasury_pda(&network_config_pda);
+    let node_treasury_lamports = fixt.svm.get_lamports(&node_treasury);
+    let protocol_treasury_lamports = fixt.svm.get_lamports(&protocol_treasury);
+
+    let authority = fixt.authority.insecure_clone();
+    let result = fixt.force_resolve_task(
+        &authority,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to force resolve task: {:#?}",
+        result.err()
+    );
+
+    let protocol_amount = max_task_cost * protocol_share_bps as u64 / 10_000;
+    assert_eq!(
+        fixt.svm.get_lamports(&protocol_treasury),
+        protocol_treasury_lamports + protocol_amount
+    );
+    assert_eq!(
+        fixt.svm.get_lamports(&node_treasury),
+        node_treasury_lamports + max_task_cost - protocol_amount
+    );
+    let node_info = fixt.get_node_info(&compute_node.pubkey());
+    assert_eq!(node_info.total_earned, max_task_cost - protocol_amount);
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.total_paid, max_task_cost);
+}
+
+#[test]
+fn test_reconcile_session_locks() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let max_task_cost = 1_000_000_000;
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        max_task_cost,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    // Artificially desync the session lock from its in-flight task
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session_pda = fixt.find_session_pda(&network_config_pda, session_slot_id).0;
+    let mut session = fixt.get_session(&network_config_pda, session_slot_id);
+    session.locked_for_tasks = 42;
+    let data = borsh::to_vec(&session).unwrap();
+    let mut account = fixt.svm.get_account(&session_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(session_pda, account).unwrap();
+
+    let outsider = fixt.create_keypair();
+    let result = fixt.reconcile_session_locks(&outsider, session_slot_id, &[task_slot_id]);
+    assert!(result.is_err(), "Non-authority should not reconcile locks");
+
+    let result = fixt.reconcile_session_locks(
+        &fixt.authority.insecure_clone(),
+        session_slot_id,
+        &[task_slot_id],
+    );
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+            assert_eq!(session.locked_for_tasks, max_task_cost);
+        }
+        Err(e) => panic!("Failed to reconcile session locks: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_payment_mint_cleared_until_first_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let authority = fixt.authority.insecure_clone();
+    let payment_mint = CreateMint::new(&mut fixt.svm, &authority)
+        .decimals(6)
+        .send()
+        .expect("Failed to create payment mint");
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.payment_mint = Some(payment_mint);
+    });
+    assert!(result.is_ok(), "Failed to set payment mint");
+    assert_eq!(fixt.get_network_config().payment_mint, Some(payment_mint));
+
+    // The default pubkey goes back to SOL payments
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.payment_mint = Some(Pubkey::default());
+    });
+    assert!(result.is_ok(), "Failed to clear payment mint");
+    assert_eq!(fixt.get_network_config().payment_mint, None);
+
+    let mut fixt = fixt.with_create_session(false);
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.payment_mint = Some(payment_mint);
+    });
+    let err = result.expect_err("Should not switch denomination once a session exists");
+    assert!(Helpers::has_error_code(&err.meta.logs, "PaymentMintLocked"));
+}
+
+#[test]
+fn test_token_denominated_session_contribution_and_payment() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let authority = fixt.authority.insecure_clone();
+    let payment_mint = CreateMint::new(&mut fixt.svm, &authority)
+        .decimals(6)
+        .send()
+        .expect("Failed to create payment mint");
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.payment_mint = Some(payment_mint);
+    });
+    assert!(result.is_ok(), "Failed to set payment mint");
+
+    let mut fixt = fixt.with_create_session(false);
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session_pda = fixt.find_session_pda(&network_config_pda, session_slot_id).0;
+    let vault_pda = fixt.find_session_vault_pda(&session_pda).0;
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let compute_node = fixt.public_node.pubkey();
+    let compute_node_info_pda = fixt.find_node_info_pda(&compute_node).0;
+    let registration_epoch = fixt.get_node_info(&compute_node).registration_epoch;
+    let node_treasury_pda = fixt
+        .find_node_treasury_pda(&compute_node_info_pda, registration_epoch)
+        .0;
+
+    let mut create_token_account = |token_owner: &solana_sdk::pubkey::Pubkey| {
+        CreateAssociatedTokenAccount::new(&mut fixt.svm, &authority, &payment_mint)
+            .owner(token_owner)
+            .send()
+            .expect("Failed to create token account")
+    };
+    let session_token_vault = create_token_account(&session_pda);
+    let owner_token_account = create_token_account(&owner.pubkey());
+    let contributor_token_account = create_token_account(&contributor.pubkey());
+    let node_token_account = create_token_account(&node_treasury_pda);
+
+    MintTo::new(
+        &mut fixt.svm,
+        &authority,
+        &payment_mint,
+        &owner_token_account,
+        DEFAULT_INITIAL_DEPOSIT,
+    )
+    .send()
+    .expect("Failed to mint to owner");
+    MintTo::new(
+        &mut fixt.svm,
+        &authority,
+        &payment_mint,
+        &contributor_token_account,
+        DEFAULT_CONTRIBUTION_AMOUNT,
+    )
+    .send()
+    .expect("Failed to mint to contributor");
+
+    let result = fixt.set_session_with(
+        &owner,
+        session_slot_id,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_INITIAL_DEPOSIT,
+        compute_node,
+        TaskType::Completion(0),
+        |builder, _| {
+            builder
+                .session_token_vault(Some(session_token_vault))
+                .owner_token_account(Some(owner_token_account))
+                .token_program(Some(TOKEN_PROGRAM_ID));
+        },
+    );
+    assert!(result.is_ok(), "Failed to set token session");
+    assert_eq!(fixt.get_token_balance(&session_token_vault), DEFAULT_INITIAL_DEPOSIT);
+    assert_eq!(fixt.svm.get_lamports(&vault_pda), 0);
+
+    // Token mode requires the token accounts
+    let result =
+        fixt.contribute_to_session(&contributor, session_slot_id, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_err(), "Native contribution should fail in token mode");
+
+    let result = fixt.contribute_to_session_with(
+        &contributor,
+        session_slot_id,
+        DEFAULT_CONTRIBUTION_AMOUNT,
+        |builder| {
+            builder
+                .session_token_vault(Some(session_token_vault))
+                .contributor_token_account(Some(contributor_token_account))
+                .token_program(Some(TOKEN_PROGRAM_ID));
+        },
+    );
+    assert!(result.is_ok(), "Failed to contribute tokens");
+
+    let contribution = fixt.get_contribution(&session_pda, &contributor.pubkey());
+    assert_eq!(contribution.shares, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert_eq!(fixt.get_token_balance(&contributor_token_account), 0);
+    assert_eq!(
+        fixt.get_token_balance(&session_token_vault),
+        DEFAULT_INITIAL_DEPOSIT + DEFAULT_CONTRIBUTION_AMOUNT
+    );
+
+    let max_task_cost = 1_000_000_000;
+    let payment_amount = 400_000_000;
+    let result = fixt.claim_task_with(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        max_task_cost,
+        10,
+        |builder| {
+            builder.session_token_vault(Some(session_token_vault));
+        },
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let node_treasury_lamports = fixt.svm.get_lamports(&node_treasury_pda);
+    let result = fixt.submit_public_task_validation_with(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        payment_amount,
+        true,
+        false,
+        |builder| {
+            builder
+                .session_token_vault(Some(session_token_vault))
+                .node_token_account(Some(node_token_account))
+                .token_program(Some(TOKEN_PROGRAM_ID));
+        },
+    );
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+            let compute_node_info = fixt.get_node_info(&compute_node);
+
+            assert_eq!(session.locked_for_tasks, 0);
+            assert_eq!(session.current_iteration, 1);
+            assert_eq!(compute_node_info.total_earned, payment_amount);
+            assert_eq!(fixt.get_token_balance(&node_token_account), payment_amount);
+            assert_eq!(
+                fixt.get_token_balance(&session_token_vault),
+                DEFAULT_INITIAL_DEPOSIT + DEFAULT_CONTRIBUTION_AMOUNT - payment_amount
+            );
+            assert_eq!(fixt.svm.get_lamports(&node_treasury_pda), node_treasury_lamports);
+        }
+        Err(e) => panic!("Failed to pay task in tokens: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_task_payment_uses_claim_snapshot() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.pubkey();
+    let max_task_cost = 600_000_000;
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        max_task_cost,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.claim_available_balance, DEFAULT_INITIAL_DEPOSIT);
+
+    // Contribution lands between claim and validation
+    let contributor = fixt.contributor.insecure_clone();
+    let result =
+        fixt.contribute_to_session(&contributor, session_slot_id, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Failed to contribute to session");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    // The live vault could cover this, the claim snapshot can't
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        DEFAULT_INITIAL_DEPOSIT + 1,
+        true,
+        false,
+    );
+    assert!(
+        result.is_err(),
+        "Payment above the claim snapshot should be rejected"
+    );
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        max_task_cost,
+        true,
+        false,
+    );
+
+    match result {
+        Ok(_) => {
+            let compute_node_info = fixt.get_node_info(&compute_node);
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+
+            assert_eq!(compute_node_info.total_earned, max_task_cost);
+            assert_eq!(session.current_iteration, 1);
+            assert_eq!(session.locked_for_tasks, 0);
+        }
+        Err(e) => panic!("Failed to pay within claim snapshot: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_sweep_abandoned_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let authority = fixt.authority.insecure_clone();
+    let abandonment_slots = 1_000;
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.abandonment_slots = Some(abandonment_slots);
+    });
+    assert!(result.is_ok(), "Failed to set abandonment window");
+
+    let session_slot_id = 0;
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session_pda = fixt.find_session_pda(&network_config_pda, session_slot_id).0;
+    let vault_pda = fixt.find_session_vault_pda(&session_pda).0;
+    let protocol_treasury_pda = fixt.find_protocol_treasury_pda(&network_config_pda).0;
+
+    let result = fixt.sweep_abandoned_session(&authority, session_slot_id);
+    assert!(result.is_err(), "Should not sweep before the abandonment window");
+
+    let outsider = fixt.create_keypair();
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    fixt.svm
+        .warp_to_slot(session.last_activity_slot + abandonment_slots);
+    fixt.svm.expire_blockhash();
+
+    let result = fixt.sweep_abandoned_session(&outsider, session_slot_id);
+    assert!(result.is_err(), "Non-authority should not sweep");
+
+    let vault_lamports = fixt.svm.get_lamports(&vault_pda);
+    let result = fixt.sweep_abandoned_session(&authority, session_slot_id);
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+
+            assert_eq!(session.status, SessionStatus::Swept);
+            assert_eq!(session.total_shares, 0);
+            assert_eq!(fixt.svm.get_lamports(&vault_pda), 0);
+            assert_eq!(fixt.svm.get_lamports(&protocol_treasury_pda), vault_lamports);
+        }
+        Err(e) => panic!("Failed to sweep abandoned session: {:#?}", e),
+    }
+
+    // The owner's old contribution still holds its shares, so a swept session stays retired
+    let owner = fixt.agent_owner.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let err = fixt
+        .set_session(
+            &owner,
+            session_slot_id,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            DEFAULT_CONTRIBUTION_AMOUNT,
+            fixt.public_node.pubkey(),
+            TaskType::Completion(0),
+        )
+        .expect_err("A swept session must not be set again");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidSessionStatus"
+    ));
+    let err = fixt
+        .withdraw_from_session(&owner, session_slot_id, 1)
+        .expect_err("Swept shares are void");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidSessionStatus"
+    ));
+}
+
+#[test]
+fn test_sweep_refuses_session_with_task_in_flight() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let authority = fixt.authority.insecure_clone();
+    let abandonment_slots = 1_000;
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.abandonment_slots = Some(abandonment_slots);
+    });
+    assert!(result.is_ok(), "Failed to set abandonment window");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.public_node.insecure_clone();
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    fixt.svm
+        .warp_to_slot(session.last_activity_slot + abandonment_slots);
+
+    let err = fixt
+        .sweep_abandoned_session(&authority, session_slot_id)
+        .expect_err("A claimed task keeps the session from being swept");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "SessionTasksInFlight"
+    ));
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.status, SessionStatus::Active);
+    assert_eq!(fixt.get_node_info(&compute_node.pubkey()).current_tasks, 1);
+}
+
+#[test]
+fn test_session_last_activity_slot_advances() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.pubkey();
+    let contributor = fixt.contributor.insecure_clone();
+
+    let mut slot = fixt.get_session(&network_config_pda, session_slot_id).last_activity_slot;
+    let mut advance = |fixt: &mut TestFixture| {
+        slot += 10;
+        fixt.svm.warp_to_slot(slot);
+        fixt.svm.expire_blockhash();
+        slot
+    };
+
+    let expected_slot = advance(&mut fixt);
+    let result =
+        fixt.contribute_to_session(&contributor, session_slot_id, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Failed to contribute to session");
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.last_activity_slot, expected_slot);
+
+    let expected_slot = advance(&mut fixt);
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        500_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.last_activity_slot, expected_slot);
+
+    let expected_slot = advance(&mut fixt);
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.last_activity_slot, expected_slot);
+
+    let expected_slot = advance(&mut fixt);
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        500_000_000,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to submit task validation");
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.last_activity_slot, expected_slot);
+}
+
+#[test]
+fn test_prune_rejected_nodes() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(false)
+        .with_validate_validator_node(false);
+
+    let authority = fixt.authority.insecure_clone();
+    let rejected_nodes = [fixt.public_node.pubkey(), fixt.validator_node.pubkey()];
+    let public_owner = fixt.public_node_owner.pubkey();
+    let validator_owner = fixt.validator_node_owner.pubkey();
+    let public_owner_lamports = fixt.svm.get_lamports(&public_owner);
+    let validator_owner_lamports = fixt.svm.get_lamports(&validator_owner);
+
+    // Active nodes can't be pruned
+    let result = fixt.prune_rejected_nodes(&authority, &[fixt.confidential_node.pubkey()]);
+    assert!(result.is_err(), "Should not prune an active node");
+
+    // Nor nodes still holding a claimed task
+    let node_info_pda = fixt.find_node_info_pda(&rejected_nodes[0]).0;
+    let mut node_info = fixt.get_node_info(&rejected_nodes[0]);
+    node_info.current_tasks = 1;
+    let data = borsh::to_vec(&node_info).unwrap();
+    let mut account = fixt.svm.get_account(&node_info_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm
+        .set_account(node_info_pda, account.clone())
+        .unwrap();
+
+    let err = fixt
+        .prune_rejected_nodes(&authority, &rejected_nodes)
+        .expect_err("A node with a task in flight can't be pruned");
+    assert!(Helpers::has_error_code(&err.meta.logs, "NodeTasksInFlight"));
+
+    node_info.current_tasks = 0;
+    let data = borsh::to_vec(&node_info).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(node_info_pda, account).unwrap();
+
+    let treasuries: Vec<Pubkey> = rejected_nodes
+        .iter()
+        .map(|node_pubkey| fixt.get_node_info(node_pubkey).node_treasury)
+        .collect();
+    let result = fixt.prune_rejected_nodes(&authority, &rejected_nodes);
+
+    match result {
+        Ok(_) => {
+            for (node_pubkey, treasury) in rejected_nodes.iter().zip(treasuries.iter()) {
+                let node_info_pda = fixt.find_node_info_pda(node_pubkey).0;
+                assert_eq!(fixt.svm.get_lamports(&node_info_pda), 0);
+                assert_eq!(fixt.svm.get_lamports(treasury), 0);
+            }
+            assert!(fixt.svm.get_lamports(&public_owner) > public_owner_lamports);
+            assert!(fixt.svm.get_lamports(&validator_owner) > validator_owner_lamports);
+        }
+        Err(e) => panic!("Failed to prune rejected nodes: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_rejection_reward_paid_to_rejecting_validators() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let authority = fixt.authority.insecure_clone();
+    let rejection_reward = 10_000_000;
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.rejection_reward = Some(rejection_reward);
+    });
+    assert!(result.is_ok(), "Failed to set rejection reward");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.pubkey();
+    let protocol_treasury_pda = fixt.find_protocol_treasury_pda(&network_config_pda).0;
+    let validator_node_info_pda = fixt.find_node_info_pda(&fixt.validator_node.pubkey()).0;
+    let validator_treasury_pda = fixt.get_node_info(&fixt.validator_node.pubkey()).node_treasury;
+
+    fixt.svm
+        .airdrop(&protocol_treasury_pda, LAMPORTS_PER_SOL)
+        .expect("Failed to fund protocol treasury");
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        600_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let validator_treasury_lamports = fixt.svm.get_lamports(&validator_treasury_pda);
+    let protocol_treasury_lamports = fixt.svm.get_lamports(&protocol_treasury_pda);
+    let result = fixt.submit_public_task_validation_with(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        0,
+        false,
+        false,
+        |builder| {
+            builder.add_remaining_accounts(&[
+                AccountMeta::new_readonly(validator_node_info_pda, false),
+                AccountMeta::new(validator_treasury_pda, false),
+            ]);
+        },
+    );
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+            assert_eq!(task.status, TaskStatus::Ready);
+            assert_eq!(
+                fixt.svm.get_lamports(&validator_treasury_pda),
+                validator_treasury_lamports + rejection_reward
+            );
+            assert_eq!(
+                fixt.svm.get_lamports(&protocol_treasury_pda),
+                protocol_treasury_lamports - rejection_reward
+            );
+        }
+        Err(e) => panic!("Failed to reward rejecting validator: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_set_session_restricted_to_allowed_owner() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let creator = fixt.create_keypair();
+    let allowed_owner = fixt.create_keypair();
+    let griefer = fixt.create_keypair();
+
+    let result = fixt.create_session_with(&creator, true, false, |builder| {
+        builder.allowed_owner(allowed_owner.pubkey());
+    });
+    assert!(result.is_err(), "Owned sessions should not take an allowed owner");
+
+    let result = fixt.create_session_with(&creator, false, false, |builder| {
+        builder.allowed_owner(allowed_owner.pubkey());
+    });
+    assert!(result.is_ok(), "Failed to create restricted session");
+
+    let session_slot_id = 0;
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.public_node.pubkey();
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.allowed_owner, Some(allowed_owner.pubkey()));
+
+    let result = fixt.set_session(
+        &griefer,
+        session_slot_id,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_INITIAL_DEPOSIT,
+        compute_node,
+        TaskType::Completion(0),
+    );
+    assert!(result.is_err(), "Only the allowed owner should claim the session");
+
+    let result = fixt.set_session(
+        &allowed_owner,
+        session_slot_id,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_INITIAL_DEPOSIT,
+        compute_node,
+        TaskType::Completion(0),
+    );
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+
+            assert_eq!(session.owner, allowed_owner.pubkey());
+            assert_eq!(session.status, SessionStatus::Active);
+        }
+        Err(e) => panic!("Allowed owner failed to claim session: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_multi_step_task_chains_next_input() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let step_one_next_input = "QmStepTwoInput123456789".to_string();
+    let max_task_cost = 100_000_000;
+
+    // Step 1 produces the input of step 2
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, max_task_cost, 10);
+    assert!(result.is_ok(), "Failed to claim step 1");
+
+    let result = fixt.submit_task_result_with(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmStepOneInput123456789".to_string(),
+        "QmStepOneOutput123456789".to_string(),
+        None,
+        1,
+        |builder| {
+            builder.next_input_cid(step_one_next_input.clone());
+        },
+    );
+    assert!(result.is_ok(), "Failed to submit step 1 result");
+
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        max_task_cost,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate step 1");
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.next_input_cid, Some(step_one_next_input.clone()));
+    assert_eq!(task.pending_next_input_cid, None);
+
+    // Step 2 must consume step 1's next input
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, max_task_cost, 10);
+    assert!(result.is_ok(), "Failed to claim step 2");
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmUnchainedInput123456789".to_string(),
+        "QmStepTwoOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_err(), "Step 2 should not accept an unchained input");
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        step_one_next_input.clone(),
+        "QmStepTwoOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit step 2 result");
+
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        max_task_cost,
+        true,
+        false,
+    );
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+            assert_eq!(task.input_cid, Some(step_one_next_input));
+            assert_eq!(task.next_input_cid, None);
+            assert_eq!(task.status, TaskStatus::Pending);
+        }
+        Err(e) => panic!("Failed to validate step 2: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_validate_agent_caps_approved_validators() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent();
+
+    // A quorum above the cap could never be reached
+    let max_agent_validators = 10;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.agent_required_validations = Some(max_agent_validators as u32 + 1);
+    });
+    let err = result.expect_err("Should reject an agent quorum above the validator cap");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidRequiredValidations"
+    ));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.agent_required_validations = Some(0);
+    });
+    let err = result.expect_err("Should reject an empty agent quorum");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidRequiredValidations"
+    ));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.agent_required_validations = Some(max_agent_validators as u32);
+    });
+    assert!(result.is_ok(), "Failed to set agent quorum");
+
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let mut validators = vec![confidential_node.insecure_clone()];
+    for _ in 1..=max_agent_validators {
+        let owner = fixt.create_keypair();
+        let node = fixt.create_keypair();
+
+        let result = fixt.register_node(&owner, &node.pubkey(), NodeType::Public);
+        assert!(result.is_ok(), "Failed to register node");
+        let result = fixt.claim_compute_node(&node, DEFAULT_NODE_INFO_CID.to_string());
+        assert!(result.is_ok(), "Failed to claim node");
+        let result = fixt.validate_public_node(&confidential_node, &node.pubkey(), true);
+        assert!(result.is_ok(), "Failed to validate node");
+
+        validators.push(node);
+    }
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    for validator in validators.iter().take(max_agent_validators) {
+        let result = fixt.validate_agent(validator, 0);
+        assert!(result.is_ok(), "Failed to validate agent up to the cap");
+    }
+
+    // The largest quorum is met exactly when the cap is
+    let agent = fixt.get_agent(&network_config_pda, 0);
+    assert_eq!(agent.approved_validators.len(), max_agent_validators);
+    assert_eq!(agent.status, AgentStatus::Active);
+
+    let result = fixt.validate_agent(&validators[max_agent_validators], 0);
+    let err = result.expect_err("Should reject a vote beyond the validator cap");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidAgentStatus"
+    ));
+
+    let agent = fixt.get_agent(&network_config_pda, 0);
+    assert_eq!(agent.approved_validators.len(), max_agent_validators);
+}
+
+#[test]
+fn test_reregistered_node_gets_fresh_treasury() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(false);
+
+    let authority = fixt.authority.insecure_clone();
+    let public_node = fixt.public_node.pubkey();
+    let public_node_owner = fixt.public_node_owner.insecure_clone();
+    let node_info_pda = fixt.find_node_info_pda(&public_node).0;
+    let first_registration = fixt.get_node_info(&public_node);
+
+    let result = fixt.prune_rejected_nodes(&authority, &[public_node]);
+    assert!(result.is_ok(), "Failed to prune rejected node");
+
+    let result = fixt.register_node(&public_node_owner, &public_node, NodeType::Public);
+
+    match result {
+        Ok(_) => {
+            let second_registration = fixt.get_node_info(&public_node);
+            let (expected_treasury, _) = fixt
+                .find_node_treasury_pda(&node_info_pda, second_registration.registration_epoch);
+
+            assert!(second_registration.registration_epoch > first_registration.registration_epoch);
+            assert_eq!(second_registration.node_treasury, expected_treasury);
+            assert_ne!(
+                second_registration.node_treasury,
+                first_registration.node_treasury
+            );
+        }
+        Err(e) => panic!("Failed to re-register node: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_create_agent_enforces_creation_allowlist() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+
+    let authority = fixt.authority.insecure_clone();
+    let listed_creator = fixt.create_keypair();
+    let unlisted_creator = fixt.create_keypair();
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.agent_creation_allowlist = Some(vec![listed_creator.pubkey()]);
+    });
+    assert!(result.is_ok(), "Failed to set agent creation allow-list");
+
+    let result = fixt.create_agent(&unlisted_creator, DEFAULT_AGENT_CONFIG_CID.to_string());
+    assert!(result.is_err(), "Unlisted creator should not create agents");
+
+    let result = fixt.create_agent(&listed_creator, DEFAULT_AGENT_CONFIG_CID.to_string());
+
+    match result {
+        Ok(_) => {
+            let network_config_pda = fixt.find_network_config_pda().0;
+            let agent = fixt.get_agent(&network_config_pda, 0);
+
+            assert_eq!(agent.owner, listed_creator.pubkey());
+            assert_eq!(fixt.get_network_config().agent_count, 1);
+        }
+        Err(e) => panic!("Listed creator failed to create agent: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_confidential_validation_proof_bound_to_task_type() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(true);
+
+    let validator_owner = fixt.create_keypair();
+    let validator = fixt.create_keypair();
+    let validator_tee = fixt.create_keypair();
+    let result = fixt.register_node(&validator_owner, &validator.pubkey(), NodeType::Confidential);
+    assert!(result.is_ok(), "Failed to register validator node");
+    let result = fixt.claim_confidential_node(
+        &validator,
+        DEFAULT_CODE_MEASUREMENT,
+        validator_tee.pubkey(),
+    );
+    assert!(result.is_ok(), "Failed to claim validator node");
+
+    let task_type = TaskType::Custom(7);
+    let mut fixt = fixt.with_set_session_for_confidential(0, task_type.clone());
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.confidential_node.insecure_clone();
+    let payment_amount = 500_000_000;
+
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, 1_000_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        input_cid.clone(),
+        output_cid.clone(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let custom_proof = Helpers::compute_validation_proof(
+        session_slot_id,
+        task_slot_id,
+        1,
+        &task_type,
+        &input_cid,
+        &output_cid,
+    );
+    let completion_proof = Helpers::compute_validation_proof(
+        session_slot_id,
+        task_slot_id,
+        1,
+        &TaskType::Completion(7),
+        &input_cid,
+        &output_cid,
+    );
+    assert_ne!(custom_proof, completion_proof);
+
+    // A proof derived for another task type doesn't verify
+    let cross_type_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        session_slot_id,
+        task_slot_id,
+        payment_amount,
+        completion_proof,
+        true,
+        false,
+        0,
+        &validator_tee,
+    );
+    let result = fixt.submit_confidential_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        &cross_type_ix,
+    );
+    assert!(result.is_err(), "Should reject a proof for another task type");
+
+    let ed25519_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        session_slot_id,
+        task_slot_id,
+        payment_amount,
+        custom_proof,
+        true,
+        false,
+        0,
+        &validator_tee,
+    );
+    let result = fixt.submit_confidential_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        &ed25519_ix,
+    );
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+            assert_eq!(task.status, TaskStatus::Pending);
+            assert_eq!(task.output_cid, Some(output_cid));
+        }
+        Err(e) => panic!("Failed to validate custom task: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_update_session_limits_raises_task_cost_ceiling() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator = fixt.validator_node.insecure_clone();
+    let low_ceiling = 100_000_000;
+    let high_task_cost = 400_000_000;
+
+    let outsider = fixt.create_keypair();
+    let result = fixt.update_session_limits(&outsider, session_slot_id, Some(low_ceiling));
+    assert!(result.is_err(), "Only the session owner can update limits");
+
+    let result = fixt.update_session_limits(&owner, session_slot_id, Some(low_ceiling));
+    assert!(result.is_ok(), "Failed to set ceiling: {:#?}", result.err());
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.max_task_cost_ceiling, Some(low_ceiling));
+
+    let err = fixt
+        .claim_task(&compute_node, session_slot_id, task_slot_id, high_task_cost, 10)
+        .expect_err("Claim above the ceiling should fail");
+    assert!(Helpers::has_error_code(&err.meta.logs, "TaskCostTooHigh"));
+
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, low_ceiling, 10);
+    assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+
+    let result = fixt.update_session_limits(&owner, session_slot_id, Some(high_task_cost));
+    assert!(result.is_err(), "Limits can't change while a task is processing");
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.submit_public_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        low_ceiling / 2,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate task: {:#?}", result.err());
+
+    // Raised between iterations, the higher-cost claim now goes through
+    let result = fixt.update_session_limits(&owner, session_slot_id, Some(high_task_cost));
+    assert!(result.is_ok(), "Failed to raise ceiling: {:#?}", result.err());
+
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, high_task_cost, 10);
+    assert!(result.is_ok(), "Failed to claim at raised ceiling: {:#?}", result.err());
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(session.max_task_cost_ceiling, Some(high_task_cost));
+    assert_eq!(session.locked_for_tasks, high_task_cost);
+    assert_eq!(task.max_task_cost, high_task_cost);
+}
+
+#[test]
+fn test_batch_withdraw_node_rewards() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+
+    let fleet_owner = fixt.create_keypair();
+    let fleet_nodes: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    for node_pubkey in fleet_nodes.iter() {
+        let result = fixt.register_node(&fleet_owner, node_pubkey, NodeType::Public);
+        assert!(result.is_ok(), "Failed to register fleet node");
+    }
+    let other_owner = fixt.public_node_owner.insecure_clone();
+    let other_node = fixt.public_node.pubkey();
+    let result = fixt.register_node(&other_owner, &other_node, NodeType::Public);
+    assert!(result.is_ok(), "Failed to register other node");
+
+    // Simulate task earnings landing in each treasury
+    let rewards = [100_000_000, 250_000_000, 50_000_000];
+    for (node_pubkey, reward) in fleet_nodes.iter().zip(rewards) {
+        let node_treasury = fixt.get_node_info(node_pubkey).node_treasury;
+        fixt.svm
+            .airdrop(&node_treasury, reward)
+            .expect("Failed to fund node treasury");
+    }
+    let total_rewards: u64 = rewards.iter().sum();
+
+    let mut mixed_fleet = fleet_nodes.clone();
+    mixed_fleet.push(other_node);
+    let result = fixt.batch_withdraw_node_rewards(&fleet_owner, &mixed_fleet);
+    assert!(result.is_err(), "Should not sweep a node owned by someone else");
+
+    let owner_lamports = fixt.svm.get_lamports(&fleet_owner.pubkey());
+    let result = fixt.batch_withdraw_node_rewards(&fleet_owner, &fleet_nodes);
+
+    match result {
+        Ok(metadata) => {
+            let event: NodeRewardsWithdrawn =
+                Helpers::decode_event(&metadata.logs, "NodeRewardsWithdrawn")
+                    .expect("NodeRewardsWithdrawn event not emitted");
+            assert_eq!(event.owner, fleet_owner.pubkey());
+            assert_eq!(event.node_count, 3);
+            assert_eq!(event.amount, total_rewards);
+
+            let rent_exempt_minimum = fixt.svm.minimum_balance_for_rent_exemption(0);
+            for node_pubkey in fleet_nodes.iter() {
+                let node_treasury = fixt.get_node_info(node_pubkey).node_treasury;
+                assert_eq!(fixt.svm.get_lamports(&node_treasury), rent_exempt_minimum);
+            }
+
+            let owner_gain = fixt.svm.get_lamports(&fleet_owner.pubkey()) - owner_lamports;
+            assert!(
+                owner_gain <= total_rewards && owner_gain >= total_rewards - 10_000,
+                "Owner should receive the swept rewards minus the fee, got {}",
+                owner_gain
+            );
+        }
+        Err(e) => panic!("Failed to batch withdraw node rewards: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_max_sessions_per_agent_caps_active_sessions() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_create_session(false);
+
+    let authority = fixt.authority.insecure_clone();
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.max_sessions_per_agent = Some(1);
+    });
+    assert!(result.is_ok(), "Failed to set max sessions per agent");
+
+    fixt = fixt.with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (agent_pda, _) = fixt.find_agent_pda(&network_config_pda, 0);
+    assert_eq!(fixt.get_agent(&network_config_pda, 0).active_session_count, 1);
+    assert_eq!(fixt.get_session(&network_config_pda, 0).agent, agent_pda);
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let second_task_slot_id = fixt.find_session_task_slot_id(1);
+    let set_second_session = |fixt: &mut TestFixture| {
+        fixt.svm.expire_blockhash();
+        fixt.set_session(
+            &owner,
+            1,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            second_task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            compute_node.pubkey(),
+            TaskType::Completion(0),
+        )
+    };
+
+    let err = set_second_session(&mut fixt).expect_err("Agent should be at capacity");
+    assert!(Helpers::has_error_code(&err.meta.logs, "SessionLimitReached"));
+
+    // Completing the first session frees the agent's slot
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        true,
+    );
+    assert!(result.is_ok(), "Failed to complete session: {:#?}", result.err());
+    assert_eq!(fixt.get_session(&network_config_pda, 0).status, SessionStatus::Completed);
+    assert_eq!(fixt.get_agent(&network_config_pda, 0).active_session_count, 0);
+
+    let result = set_second_session(&mut fixt);
+    assert!(result.is_ok(), "Failed to set second session: {:#?}", result.err());
+    assert_eq!(fixt.get_agent(&network_config_pda, 0).active_session_count, 1);
+}
+
+#[test]
+fn test_node_pool_membership_events() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node();
+
+    let authority = fixt.authority.insecure_clone();
+    let node_pubkey = fixt.public_node.pubkey();
+
+    let result = fixt.activate_node(&authority, &node_pubkey);
+    let metadata = result.expect("Failed to activate public node");
+    let event: NodePoolMembershipChanged =
+        Helpers::decode_event(&metadata.logs, "NodePoolMembershipChanged")
+            .expect("NodePoolMembershipChanged event not emitted on activation");
+    assert_eq!(event.node, node_pubkey);
+    assert_eq!(event.node_type, NodeType::Public);
+    assert_eq!(event.change, PoolMembershipChange::Added);
+
+    let result = fixt.disable_node(&authority, &node_pubkey);
+
+    match result {
+        Ok(metadata) => {
+            let event: NodePoolMembershipChanged =
+                Helpers::decode_event(&metadata.logs, "NodePoolMembershipChanged")
+                    .expect("NodePoolMembershipChanged event not emitted on disable");
+            assert_eq!(event.node, node_pubkey);
+            assert_eq!(event.node_type, NodeType::Public);
+            assert_eq!(event.change, PoolMembershipChange::Removed);
+
+            let node_info = fixt.get_node_info(&node_pubkey);
+            let network_config = fixt.get_network_config();
+            assert_eq!(node_info.status, NodeStatus::Disabled);
+            assert!(network_config.approved_public_nodes.is_empty());
+        }
+        Err(e) => panic!("Failed to disable node: {:#?}", e),
+    }
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.disable_node(&authority, &node_pubkey);
+    assert!(result.is_err(), "A disabled node can't be disabled again");
+}
+
+#[test]
+fn test_disable_node_rejected_while_task_in_flight() {
+    let mut fixt = public_task_awaiting_validation(0);
+    let authority = fixt.authority.insecure_clone();
+    let node_pubkey = fixt.public_node.pubkey();
+    assert_eq!(fixt.get_node_info(&node_pubkey).current_tasks, 1);
+
+    let err = fixt
+        .disable_node(&authority, &node_pubkey)
+        .expect_err("A node with a task awaiting validation can't be disabled");
+    assert!(Helpers::has_error_code(&err.meta.logs, "NodeTasksInFlight"));
+
+    let node_info = fixt.get_node_info(&node_pubkey);
+    assert_eq!(node_info.status, NodeStatus::Active);
+    assert!(fixt
+        .get_network_config()
+        .approved_public_nodes
+        .contains(&node_pubkey));
+}
+
+#[test]
+fn test_is_node_approved_reports_pool_membership() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node();
+
+    let payer = fixt.payer.insecure_clone();
+    let authority = fixt.authority.insecure_clone();
+    let public_node = fixt.public_node.pubkey();
+    let confidential_node = fixt.confidential_node.pubkey();
+
+    let metadata = fixt
+        .is_node_approved(&payer, &public_node)
+        .expect("Failed to query public node approval");
+    let status: ApprovedStatus = Helpers::decode_event(&metadata.logs, "ApprovedStatus")
+        .expect("ApprovedStatus event not emitted");
+    assert_eq!(status.node, public_node);
+    assert!(!status.public_approved, "Claimed node isn't approved until activated");
+    assert!(!status.confidential_approved);
+
+    let result = fixt.activate_node(&authority, &public_node);
+    assert!(result.is_ok(), "Failed to activate public node");
+    fixt.svm.expire_blockhash();
+
+    let result = fixt.is_node_approved(&payer, &public_node);
+
+    match result {
+        Ok(metadata) => {
+            let status: ApprovedStatus = Helpers::decode_event(&metadata.logs, "ApprovedStatus")
+                .expect("ApprovedStatus event not emitted");
+            assert_eq!(status.node, public_node);
+            assert!(status.public_approved);
+            assert!(!status.confidential_approved);
+        }
+        Err(e) => panic!("Failed to query public node approval: {:#?}", e),
+    }
+
+    let metadata = fixt
+        .is_node_approved(&payer, &confidential_node)
+        .expect("Failed to query confidential node approval");
+    let status: ApprovedStatus = Helpers::decode_event(&metadata.logs, "ApprovedStatus")
+        .expect("ApprovedStatus event not emitted");
+    assert!(!status.public_approved);
+    assert!(status.confidential_approved);
+}
+
+/// Confidential session 0 with a submitted result, returning the assigned validator,
+/// its TEE signing keypair and a serialized approval message for the task
+fn confidential_task_awaiting_validation() -> (TestFixture, Keypair, Keypair, Vec<u8>) {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(true);
+
+    // Second confidential node for 1 compute + 1 validator
+    let validator_owner = fixt.create_keypair();
+    let validator = fixt.create_keypair();
+    let validator_tee = fixt.create_keypair();
+    let result = fixt.register_node(&validator_owner, &validator.pubkey(), NodeType::Confidential);
+    assert!(result.is_ok(), "Failed to register second confidential node");
+    let result =
+        fixt.claim_confidential_node(&validator, DEFAULT_CODE_MEASUREMENT, validator_tee.pubkey());
+    assert!(result.is_ok(), "Failed to claim second confidential node");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.confidential_node.insecure_clone();
+    let mut fixt = fixt.with_set_session(
+        session_slot_id,
+        0,
+        compute_node.pubkey(),
+        TaskType::Completion(0),
+    );
+
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, 1_000_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        input_cid.clone(),
+        output_cid.clone(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let message = SubmitTaskValidationMessage {
+        goal_id: session_slot_id,
+        task_slot_id,
+        payment_amount: 500_000_000,
+        validation_proof: Helpers::compute_validation_proof(
+            session_slot_id,
+            task_slot_id,
+            1,
+            &TaskType::Completion(0),
+            &input_cid,
+            &output_cid,
+        ),
+        approved: true,
+        goal_completed: false,
+        output_size: 0,
+    };
+    let message_data = borsh::to_vec(&message).expect("Failed to serialize message");
+
+    (fixt, validator, validator_tee, message_data)
+}
+
+#[test]
+fn test_confidential_task_validation_rejects_multiple_signatures() {
+    let (mut fixt, validator, validator_tee, message_data) =
+        confidential_task_awaiting_validation();
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.confidential_node.pubkey();
+
+    // The first entry is a valid TEE signature, the second one just rides along
+    let other_signer = fixt.create_keypair();
+    let ed25519_ix = create_ed25519_instruction_with_signatures(&[
+        (message_data.as_slice(), &validator_tee),
+        (b"unrelated".as_slice(), &other_signer),
+    ]);
+
+    let result = fixt.submit_confidential_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        &ed25519_ix,
+    );
+    let err = result.expect_err("Should reject an Ed25519 instruction with two signatures");
+    assert!(Helpers::has_error_code(&err.meta.logs, "BadEd25519SignatureCount"));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::AwaitingValidation);
+}
+
+#[test]
+fn test_confidential_task_validation_rejects_out_of_range_offsets() {
+    let (mut fixt, validator, validator_tee, message_data) =
+        confidential_task_awaiting_validation();
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.confidential_node.pubkey();
+
+    // A first Ed25519 instruction carries the message in its data
+    let other_signer = fixt.create_keypair();
+    let carrier_ix = create_ed25519_instruction_with_signature(&message_data, &other_signer);
+    let carrier_message_offset = u16::from_le_bytes([carrier_ix.data[10], carrier_ix.data[11]]);
+
+    // The TEE signature points at that message, which lies past the end of its own data
+    const DATA_START: u16 = 16;
+    let signature = validator_tee.sign_message(&message_data);
+    let mut data = vec![1u8, 0];
+    for field in [
+        DATA_START + 32,
+        u16::MAX,
+        DATA_START,
+        u16::MAX,
+        carrier_message_offset,
+        message_data.len() as u16,
+        0,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(validator_tee.pubkey().as_ref());
+    data.extend_from_slice(signature.as_ref());
+    assert!((carrier_message_offset as usize) + message_data.len() > data.len());
+    let tee_ix = Instruction {
+        program_id: carrier_ix.program_id,
+        accounts: vec![],
+        data,
+    };
+
+    let result = fixt.submit_confidential_task_validation_after(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        &[carrier_ix, tee_ix],
+    );
+    let err = result.expect_err("Should reject offsets outside the Ed25519 instruction");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidInstructionSysvar"));
+    assert!(!err.meta.logs.iter().any(|log| log.contains("panicked")));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::AwaitingValidation);
+}
+
+#[test]
+fn test_confidential_task_validation_requires_adjacent_ed25519() {
+    let (mut fixt, validator, validator_tee, message_data) =
+        confidential_task_awaiting_validation();
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.confidential_node.pubkey();
+
+    let ed25519_ix = create_ed25519_instruction_with_signature(&message_data, &validator_tee);
+    let noop_ix = GetSharePrecisionBuilder::new().instruction();
+
+    let result = fixt.submit_confidential_task_validation_after(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        &[ed25519_ix.clone(), noop_ix],
+    );
+    let err = result.expect_err("Should reject an Ed25519 instruction that isn't adjacent");
+    assert!(Helpers::has_error_code(&err.meta.logs, "BadEd25519Program"));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::AwaitingValidation);
+
+    let result = fixt.submit_confidential_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        &ed25519_ix,
+    );
+    assert!(result.is_ok(), "Adjacent Ed25519 instruction should verify: {:#?}", result.err());
+}
+
+#[test]
+fn test_owner_fee_mints_shares_to_session_owner() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false);
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let compute_node = fixt.public_node.pubkey();
+    let owner_fee_bps = 500u16;
+
+    let set_session = |fixt: &mut TestFixture, owner_fee_bps: u16| {
+        fixt.set_session_with(
+            &owner,
+            session_slot_id,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            compute_node,
+            TaskType::Completion(0),
+            |_, args| {
+                args.owner_fee_bps = Some(owner_fee_bps);
+            },
+        )
+    };
+    let err = set_session(&mut fixt, 1_001).expect_err("Owner fee above 10% should be rejected");
+    assert!(Helpers::has_error_code(&err.meta.logs, "OwnerFeeTooHigh"));
+    let result = set_session(&mut fixt, owner_fee_bps);
+    assert!(result.is_ok(), "Failed to set session: {:#?}", result.err());
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session_pda = fixt.find_session_pda(&network_config_pda, session_slot_id).0;
+    let owner_contribution_pda = fixt.find_contribution_pda(&session_pda, &owner.pubkey()).0;
+
+    let err = fixt
+        .contribute_to_session(&contributor, session_slot_id, DEFAULT_CONTRIBUTION_AMOUNT)
+        .expect_err("Owner fee can't be collected without the owner contribution");
+    assert!(Helpers::has_error_code(&err.meta.logs, "OwnerContributionRequired"));
+
+    // No task has run yet so shares still mint 1:1 with lamports
+    let fee_shares = DEFAULT_CONTRIBUTION_AMOUNT * owner_fee_bps as u64 / 10_000;
+    let mut expected_owner_shares = DEFAULT_INITIAL_DEPOSIT;
+    let mut expected_contributor_shares = 0;
+
+    for _ in 0..2 {
+        let result = fixt.contribute_to_session_with(
+            &contributor,
+            session_slot_id,
+            DEFAULT_CONTRIBUTION_AMOUNT,
+            |builder| {
+                builder.owner_contribution(Some(owner_contribution_pda));
+            },
+        );
+        assert!(result.is_ok(), "Failed to contribute: {:#?}", result.err());
+        fixt.svm.expire_blockhash();
+
+        expected_owner_shares += fee_shares;
+        expected_contributor_shares += DEFAULT_CONTRIBUTION_AMOUNT - fee_shares;
+
+        let owner_contribution = fixt.get_contribution(&session_pda, &owner.pubkey());
+        let contribution = fixt.get_contribution(&session_pda, &contributor.pubkey());
+        assert_eq!(owner_contribution.shares, expected_owner_shares);
+        assert_eq!(contribution.shares, expected_contributor_shares);
+    }
+
+    // The owner's own deposits carry no fee
+    let result = fixt.contribute_to_session(&owner, session_slot_id, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Owner failed to contribute: {:#?}", result.err());
+    expected_owner_shares += DEFAULT_CONTRIBUTION_AMOUNT;
+
+    let owner_contribution = fixt.get_contribution(&session_pda, &owner.pubkey());
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(owner_contribution.shares, expected_owner_shares);
+    assert_eq!(
+        session.total_shares,
+        expected_owner_shares + expected_contributor_shares
+    );
+}
+
+/// Claim, submit and approve the session's task with `session_completed` set
+fn complete_public_session(fixt: &mut TestFixture, session_slot_id: u64, payment_amount: u64) {
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+
+    let result = fixt.claim_task(&compute_node, session_slot_id, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result: {:#?}", result.err());
+    let result = fixt.submit_public_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        payment_amount,
+        true,
+        true,
+    );
+    assert!(result.is_ok(), "Failed to complete session: {:#?}", result.err());
+}
+
+#[test]
+fn test_merge_sessions_migrates_contributions() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let (source_slot_id, destination_slot_id) = (0, 1);
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let newcomer = fixt.create_keypair();
+
+    let contributions = [
+        (&contributor, source_slot_id, DEFAULT_CONTRIBUTION_AMOUNT),
+        (&newcomer, source_slot_id, 2 * DEFAULT_CONTRIBUTION_AMOUNT),
+        (&contributor, destination_slot_id, DEFAULT_CONTRIBUTION_AMOUNT),
+    ];
+    for (contributor, session_slot_id, amount) in contributions {
+        let result = fixt.contribute_to_session(contributor, session_slot_id, amount);
+        assert!(result.is_ok(), "Failed to contribute: {:#?}", result.err());
+    }
+
+    let result = fixt.merge_sessions(&owner, source_slot_id, destination_slot_id, &[]);
+    let err = result.expect_err("Active sessions can't be merged");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidSessionStatus"));
+
+    // The paid source ends up at a different share price than the still active destination
+    complete_public_session(&mut fixt, source_slot_id, 80_000_000);
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (source_pda, _) = fixt.find_session_pda(&network_config_pda, source_slot_id);
+    let (destination_pda, _) = fixt.find_session_pda(&network_config_pda, destination_slot_id);
+    let (source_vault, _) = fixt.find_session_vault_pda(&source_pda);
+    let (destination_vault, _) = fixt.find_session_vault_pda(&destination_pda);
+    let rent_exempt_minimum = fixt.svm.minimum_balance_for_rent_exemption(0);
+
+    let source = fixt.get_session(&network_config_pda, source_slot_id);
+    let destination = fixt.get_session(&network_config_pda, destination_slot_id);
+    let source_balance = fixt.svm.get_lamports(&source_vault) - rent_exempt_minimum;
+    let destination_lamports = fixt.svm.get_lamports(&destination_vault);
+    let destination_balance = destination_lamports - rent_exempt_minimum;
+
+    let precision = 1_000_000_000u128;
+    let source_price = source_balance as u128 * precision / source.total_shares as u128;
+    let destination_price =
+        destination_balance as u128 * precision / destination.total_shares as u128;
+    let remint = |shares: u64| -> u64 {
+        let amount = shares as u128 * source_price / precision;
+        (amount * precision / destination_price) as u64
+    };
+
+    let participants = [owner.pubkey(), contributor.pubkey(), newcomer.pubkey()];
+    let source_shares: Vec<u64> = participants
+        .iter()
+        .map(|participant| fixt.get_contribution(&source_pda, participant).shares)
+        .collect();
+    let destination_shares: Vec<u64> = participants[..2]
+        .iter()
+        .map(|participant| fixt.get_contribution(&destination_pda, participant).shares)
+        .collect();
+
+    let result =
+        fixt.merge_sessions(&owner, source_slot_id, destination_slot_id, &participants[..2]);
+    let err = result.expect_err("Merge must cover every source contribution");
+    assert!(Helpers::has_error_code(&err.meta.logs, "IncompleteSessionMerge"));
+
+    let outsider = fixt.create_keypair();
+    let result = fixt.merge_sessions(&outsider, source_slot_id, destination_slot_id, &participants);
+    let err = result.expect_err("Only the owner of both sessions can merge them");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidSessionOwner"));
+
+    let result = fixt.merge_sessions(&owner, source_slot_id, destination_slot_id, &participants);
+
+    match result {
+        Ok(_) => {
+            let source = fixt.get_session(&network_config_pda, source_slot_id);
+            let merged = fixt.get_session(&network_config_pda, destination_slot_id);
+            assert_eq!(source.total_shares, 0);
+            assert_eq!(fixt.svm.get_lamports(&source_vault), rent_exempt_minimum);
+            assert_eq!(
+                fixt.svm.get_lamports(&destination_vault),
+                destination_lamports + source_balance
+            );
+
+            let mut minted = 0;
+            for (i, participant) in participants.iter().enumerate() {
+                assert_eq!(fixt.get_contribution(&source_pda, participant).shares, 0);
+
+                let reminted = remint(source_shares[i]);
+                let previous = destination_shares.get(i).copied().unwrap_or(0);
+                let contribution = fixt.get_contribution(&destination_pda, participant);
+                assert_eq!(contribution.session, destination_pda);
+                assert_eq!(contribution.shares, previous + reminted);
+                minted += reminted;
+            }
+            assert!(!fixt.get_contribution(&destination_pda, &newcomer.pubkey()).is_owner);
+            assert_eq!(merged.total_shares, destination.total_shares + minted);
+        }
+        Err(e) => panic!("Failed to merge sessions: {:#?}", e),
+    }
+
+    // The merged funds can be withdrawn from the destination
+    let merged = fixt.get_session(&network_config_pda, destination_slot_id);
+    let merged_balance = fixt.svm.get_lamports(&destination_vault) - rent_exempt_minimum;
+    let newcomer_shares = fixt
+        .get_contribution(&destination_pda, &newcomer.pubkey())
+        .shares;
+    let merged_price = merged_balance as u128 * precision / merged.total_shares as u128;
+    let expected = (newcomer_shares as u128 * merged_price / precision) as u64;
+
+    let result = fixt.withdraw_from_session(&newcomer, destination_slot_id, newcomer_shares);
+    assert!(
+        result.is_ok(),
+        "Failed to withdraw merged shares: {:#?}",
+        result.err()
+    );
+    assert_eq!(
+        fixt.get_contribution(&destination_pda, &newcomer.pubkey())
+            .shares,
+        0
+    );
+    assert_eq!(
+        fixt.svm.get_lamports(&destination_vault),
+        merged_balance + rent_exempt_minimum - expected
+    );
+}
+
+#[test]
+fn test_reused_session_keeps_task_chain_continuous() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.pubkey();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    complete_public_session(&mut fixt, session_slot_id, 50_000_000);
+    complete_public_session(&mut fixt, 1, 50_000_000);
+
+    let first_run_task = fixt.get_task(&network_config_pda, task_slot_id);
+    let first_run = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(first_run.task_index_end, first_run_task.task_index);
+
+    let reuse_session = |fixt: &mut TestFixture| {
+        fixt.svm.expire_blockhash();
+        fixt.set_session(
+            &owner,
+            session_slot_id,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            compute_node,
+            TaskType::Completion(0),
+        )
+    };
+
+    let err = reuse_session(&mut fixt).expect_err("Outstanding shares block reuse");
+    assert!(Helpers::has_error_code(&err.meta.logs, "SessionHasOutstandingShares"));
+
+    let result = fixt.merge_sessions(&owner, session_slot_id, 1, &[owner.pubkey()]);
+    assert!(result.is_ok(), "Failed to merge sessions: {:#?}", result.err());
+
+    let result = reuse_session(&mut fixt);
+    assert!(result.is_ok(), "Failed to reuse session: {:#?}", result.err());
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(session.status, SessionStatus::Active);
+    assert_eq!(session.current_iteration, 0);
+    assert_eq!(session.task_index_start, first_run_task.task_index);
+    assert_eq!(session.task_index_end, 0);
+    assert_eq!(task.task_index, first_run_task.task_index);
+    assert_eq!(task.chain_proof, first_run_task.chain_proof);
+
+    complete_public_session(&mut fixt, session_slot_id, 50_000_000);
+
+    // The first step of the new run chains onto the last step of the previous one
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.task_index, first_run_task.task_index + 1);
+    let mut hasher = Sha256::new();
+    hasher.update(first_run_task.chain_proof);
+    hasher.update(first_run_task.input_cid.unwrap_or_default().as_bytes());
+    hasher.update(first_run_task.output_cid.unwrap_or_default().as_bytes());
+    hasher.update(task.task_index.to_le_bytes());
+    let expected_chain_proof: [u8; 32] = hasher.finalize().into();
+    assert_eq!(task.chain_proof, expected_chain_proof);
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.task_index_end, task.task_index);
+}
+
+/// Public session 0 whose task result has been submitted under a `window_slots` validation window
+fn public_task_awaiting_validation(window_slots: u64) -> TestFixture {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.validation_window_slots = Some(window_slots);
+    });
+    assert!(result.is_ok(), "Failed to set validation window");
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    fixt
+}
+
+#[test]
+fn test_task_validation_within_deadline() {
+    let window_slots = 50;
+    let mut fixt = public_task_awaiting_validation(window_slots);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(
+        task.validation_deadline_slot,
+        task.validation_started_slot + window_slots
+    );
+
+    // The deadline slot itself is still in the window
+    fixt.svm.warp_to_slot(task.validation_deadline_slot);
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        0,
+        task_slot_id,
+        &fixt.public_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+            let session = fixt.get_session(&network_config_pda, 0);
+            assert_eq!(task.status, TaskStatus::Pending);
+            assert_eq!(session.current_iteration, 1);
+            assert_eq!(session.locked_for_tasks, 0);
+        }
+        Err(e) => panic!("In-window validation should succeed: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_task_validation_after_deadline_allows_reclaim() {
+    let mut fixt = public_task_awaiting_validation(50);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let compute_node = fixt.public_node.insecure_clone();
+
+    // Not reclaimable while validators are still in time
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 200_000_000, 10);
+    let err = result.expect_err("Task awaiting validation can't be reclaimed in the window");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidTaskStatus"));
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    fixt.svm.warp_to_slot(task.validation_deadline_slot + 1);
+    fixt.svm.expire_blockhash();
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    let err = result.expect_err("Validation after the deadline should be rejected");
+    assert!(Helpers::has_error_code(&err.meta.logs, "ValidationDeadlinePassed"));
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 200_000_000, 10);
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+            let session = fixt.get_session(&network_config_pda, 0);
+            assert_eq!(task.status, TaskStatus::Processing);
+            assert_eq!(task.pending_input_cid, None);
+            assert_eq!(task.pending_output_cid, None);
+            assert_eq!(task.validation_deadline_slot, 0);
+            // Only the new claim stays locked
+            assert_eq!(session.locked_for_tasks, 200_000_000);
+        }
+        Err(e) => panic!("Expired task should be reclaimable: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_submit_task_result_rejects_mismatched_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (other_session_pda, _) = fixt.find_session_pda(&network_config_pda, 1);
+
+    let result = fixt.submit_task_result_with(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+        |builder| {
+            builder.session(other_session_pda);
+        },
+    );
+    let err = result.expect_err("Result for another session's task should be rejected");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidSession"));
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::Processing);
+    assert_eq!(task.pending_output_cid, None);
+}
+
+#[test]
+fn test_seed_task_input_binds_first_step() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let seeded_input_cid = "QmSeededPrompt123456789".to_string();
+
+    let outsider = fixt.create_keypair();
+    let result = fixt.seed_task_input(&outsider, 0, seeded_input_cid.clone());
+    let err = result.expect_err("Only the session owner can seed the input");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidSessionOwner"));
+
+    let metadata = fixt
+        .seed_task_input(&owner, 0, seeded_input_cid.clone())
+        .expect("Failed to seed task input");
+    let event: TaskInputSeeded = Helpers::decode_event(&metadata.logs, "TaskInputSeeded")
+        .expect("TaskInputSeeded event not emitted");
+    assert_eq!(event.input_cid, seeded_input_cid);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.next_input_cid, Some(seeded_input_cid.clone()));
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let submit = |fixt: &mut TestFixture, input_cid: &str| {
+        fixt.submit_task_result(
+            &compute_node,
+            0,
+            task_slot_id,
+            input_cid.to_string(),
+            "QmTestOutput123456789".to_string(),
+            None,
+            1,
+        )
+    };
+
+    let err = submit(&mut fixt, "QmOtherInput123456789")
+        .expect_err("Node must consume the seeded input");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InputCidMismatch"));
+
+    let result = submit(&mut fixt, &seeded_input_cid);
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+            assert_eq!(task.status, TaskStatus::AwaitingValidation);
+            assert_eq!(task.pending_input_cid, Some(seeded_input_cid));
+        }
+        Err(e) => panic!("Failed to submit result for seeded input: {:#?}", e),
+    }
+
+    // A running step can't be re-seeded
+    let result = fixt.seed_task_input(&owner, 0, "QmLatePrompt123456789".to_string());
+    assert!(result.is_err(), "Seeding should fail once the step is submitted");
+}
+
+#[test]
+fn test_claim_task_rate_limited_per_node() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let min_slots_between_claims = 20;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.min_slots_between_claims = Some(min_slots_between_claims);
+    });
+    assert!(result.is_ok(), "Failed to set claim rate limit");
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let first_task_slot_id = fixt.find_session_task_slot_id(0);
+    let second_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    let result = fixt.claim_task(&compute_node, 0, first_task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim first task: {:#?}", result.err());
+    let node_info = fixt.get_node_info(&compute_node.pubkey());
+    let last_claim_slot = node_info.last_claim_slot.expect("Claim slot not recorded");
+
+    let result = fixt.claim_task(&compute_node, 1, second_task_slot_id, 100_000_000, 10);
+    let err = result.expect_err("Second claim in quick succession should be rate limited");
+    assert!(Helpers::has_error_code(&err.meta.logs, "ClaimRateLimited"));
+
+    fixt.svm
+        .warp_to_slot(last_claim_slot + min_slots_between_claims);
+    fixt.svm.expire_blockhash();
+
+    let result = fixt.claim_task(&compute_node, 1, second_task_slot_id, 100_000_000, 10);
+
+    match result {
+        Ok(_) => {
+            let network_config_pda = fixt.find_network_config_pda().0;
+            let task = fixt.get_task(&network_config_pda, second_task_slot_id);
+            let node_info = fixt.get_node_info(&compute_node.pubkey());
+            assert_eq!(task.status, TaskStatus::Processing);
+            assert_eq!(
+                node_info.last_claim_slot,
+                Some(last_claim_slot + min_slots_between_claims)
+            );
+        }
+        Err(e) => panic!("Claim after the rate limit window should succeed: {:#?}", e),
+    }
+}
+
+fn test_code_measurement(seed: u8) -> CodeMeasurement {
+    CodeMeasurement {
+        measurement: [seed; 32],
+        version: SemanticVersion {
+            major: seed as u16,
+            minor: 0,
+            patch: 0,
+        },
+    }
+}
+
+#[test]
+fn test_code_measurements_capped_consistently() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+    let authority = fixt.authority.insecure_clone();
+
+    let initial_count = fixt.get_network_config().approved_code_measurements.len();
+    for seed in initial_count..MAX_CODE_MEASUREMENTS {
+        let result = fixt.update_network_config(
+            &authority,
+            None,
+            Some(test_code_measurement(seed as u8 + 1)),
+        );
+        assert!(
+            result.is_ok(),
+            "Failed to add measurement {seed}: {:#?}",
+            result.err()
+        );
+    }
+
+    let network_config = fixt.get_network_config();
+    assert_eq!(
+        network_config.approved_code_measurements.len(),
+        MAX_CODE_MEASUREMENTS
+    );
+    assert_eq!(
+        network_config.approved_code_measurements[0].measurement,
+        [MAX_CODE_MEASUREMENTS as u8; 32]
+    );
+
+    let result = fixt.update_network_config(
+        &authority,
+        None,
+        Some(test_code_measurement(MAX_CODE_MEASUREMENTS as u8 + 1)),
+    );
+    let err = result.expect_err("Adding past the cap should fail");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "TooManyCodeMeasurements"
+    ));
+
+    let network_config = fixt.get_network_config();
+    assert_eq!(
+        network_config.approved_code_measurements.len(),
+        MAX_CODE_MEASUREMENTS
+    );
+    assert_eq!(
+        network_config.approved_code_measurements[MAX_CODE_MEASUREMENTS - 1].measurement,
+        DEFAULT_CODE_MEASUREMENT,
+        "The oldest measurement must not be evicted"
+    );
+
+    let full: Vec<CodeMeasurement> = (1..=MAX_CODE_MEASUREMENTS as u8)
+        .map(test_code_measurement)
+        .collect();
+    let over: Vec<CodeMeasurement> = (1..=MAX_CODE_MEASUREMENTS as u8 + 1)
+        .map(test_code_measurement)
+        .collect();
+
+    let mut at_cap = TestFixture::new();
+    let network_config_pda = at_cap.find_network_config_pda().0;
+    let result = at_cap.initialize_network(
+        &at_cap.authority.insecure_clone(),
+        &network_config_pda,
+        DEFAULT_CID_CONFIG.to_string(),
+        0,
+        full,
+        DEFAULT_REQUIRED_VALIDATIONS,
+        None,
+        &[],
+    );
+    assert!(
+        result.is_ok(),
+        "Initializing at the cap should succeed: {:#?}",
+        result.err()
+    );
+
+    let mut past_cap = TestFixture::new();
+    let network_config_pda = past_cap.find_network_config_pda().0;
+    let result = past_cap.initialize_network(
+        &past_cap.authority.insecure_clone(),
+        &network_config_pda,
+        DEFAULT_CID_CONFIG.to_string(),
+        0,
+        over,
+        DEFAULT_REQUIRED_VALIDATIONS,
+        None,
+        &[],
+    );
+    let err = result.expect_err("Initializing past the cap should fail");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "TooManyCodeMeasurements"
+    ));
+}
+
+#[test]
+fn test_list_code_measurements_in_insertion_order() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+    let authority = fixt.authority.insecure_clone();
+    let payer = fixt.payer.insecure_clone();
+
+    for seed in [2u8, 3u8] {
+        let result =
+            fixt.update_network_config(&authority, None, Some(test_code_measurement(seed)));
+        assert!(result.is_ok(), "Failed to add measurement {seed}");
+        fixt.svm.expire_blockhash();
+    }
+
+    let result = fixt.list_code_measurements(&payer);
+
+    match result {
+        Ok(metadata) => {
+            let listed: Vec<CodeMeasurementListed> =
+                Helpers::decode_events(&metadata.logs, "CodeMeasurementListed");
+            let measurements: Vec<[u8; 32]> =
+                listed.iter().map(|entry| entry.measurement).collect();
+            assert_eq!(
+                measurements,
+                vec![DEFAULT_CODE_MEASUREMENT, [2u8; 32], [3u8; 32]]
+            );
+            assert_eq!(listed[1].version, test_code_measurement(2).version);
+            assert_eq!(listed[2].version, test_code_measurement(3).version);
+        }
+        Err(e) => panic!("Failed to list code measurements: {:#?}", e),
+    }
+}
+
+fn session_with_contributors(second_contribution: u64) -> (TestFixture, Keypair) {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_contribute_to_session(0, DEFAULT_CONTRIBUTION_AMOUNT);
+
+    let second_contributor = fixt.create_keypair();
+    let result = fixt.contribute_to_session(&second_contributor, 0, second_contribution);
+    assert!(result.is_ok(), "Failed to contribute: {:#?}", result.err());
+
+    (fixt, second_contributor)
+}
+
+#[test]
+fn test_contributor_majority_cancels_session() {
+    // Owner 1 SOL, contributors 0.5 + 0.7 SOL, the two contributors hold a majority
+    let (mut fixt, second_contributor) = session_with_contributors(700_000_000);
+    let contributor = fixt.contributor.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let active_sessions = fixt.get_agent(&network_config_pda, 0).active_session_count;
+
+    let result = fixt.propose_session_action(&contributor, 0, SessionAction::Cancel);
+    assert!(
+        result.is_ok(),
+        "Failed to propose cancel: {:#?}",
+        result.err()
+    );
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(session.status, SessionStatus::Active);
+
+    let result = fixt.vote_session_action(&second_contributor, 0);
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, 0);
+            let proposal = fixt.get_session_proposal(&session_pda);
+            let agent = fixt.get_agent(&network_config_pda, 0);
+            assert_eq!(session.status, SessionStatus::Cancelled);
+            assert!(proposal.executed);
+            assert!(proposal.votes_shares * 2 > session.total_shares);
+            assert_eq!(agent.active_session_count, active_sessions - 1);
+        }
+        Err(e) => panic!("Majority vote should cancel the session: {:#?}", e),
+    }
+
+    let shares = fixt
+        .get_contribution(&session_pda, &contributor.pubkey())
+        .shares;
+    let result = fixt.withdraw_from_session(&contributor, 0, shares);
+    assert!(
+        result.is_ok(),
+        "Contributors should withdraw from a cancelled session"
+    );
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(
+        result.is_err(),
+        "Cancelled session should not accept claims"
+    );
+}
+
+#[test]
+fn test_contributor_minority_cannot_cancel_session() {
+    // Owner 1 SOL, contributors 0.5 + 0.2 SOL, the two contributors stay a minority
+    let (mut fixt, second_contributor) = session_with_contributors(200_000_000);
+    let contributor = fixt.contributor.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+
+    let result = fixt.propose_session_action(&contributor, 0, SessionAction::Cancel);
+    assert!(
+        result.is_ok(),
+        "Failed to propose cancel: {:#?}",
+        result.err()
+    );
+    let result = fixt.vote_session_action(&second_contributor, 0);
+    assert!(result.is_ok(), "Failed to vote: {:#?}", result.err());
+
+    let session = fixt.get_session(&network_config_pda, 0);
+    let proposal = fixt.get_session_proposal(&session_pda);
+    assert_eq!(session.status, SessionStatus::Active);
+    assert!(!proposal.executed);
+    assert_eq!(proposal.voters.len(), 2);
+    assert!(proposal.votes_shares * 2 <= session.total_shares);
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.vote_session_action(&contributor, 0);
+    let err = result.expect_err("A contributor can only vote once");
+    assert!(Helpers::has_error_code(&err.meta.logs, "AlreadyVoted"));
+
+    let result = fixt.propose_session_action(&second_contributor, 0, SessionAction::Pause);
+    let err = result.expect_err("An open proposal blocks a new one");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "SessionProposalActive"
+    ));
+
+    // Withdrawing voted shares would shrink the quorum under a vote that still counts
+    let shares = fixt
+        .get_contribution(&session_pda, &contributor.pubkey())
+        .shares;
+    let err = fixt
+        .withdraw_from_session(&contributor, 0, shares)
+        .expect_err("Voted shares stay put while the proposal is open");
+    assert!(Helpers::has_error_code(&err.meta.logs, "VotedSharesLocked"));
+
+    fixt.svm
+        .warp_to_slot(proposal.created_slot + SESSION_PROPOSAL_SLOTS);
+    fixt.svm.expire_blockhash();
+    let result = fixt.withdraw_from_session(&contributor, 0, shares);
+    assert!(
+        result.is_ok(),
+        "Shares should be free once the proposal lapses: {:#?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_claim_task_assigns_whole_pool_when_it_equals_required() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let second_validator_owner = fixt.create_keypair();
+    let second_validator = fixt.create_keypair();
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let result = fixt.register_node(
+        &second_validator_owner,
+        &second_validator.pubkey(),
+        NodeType::Public,
+    );
+    assert!(result.is_ok(), "Failed to register second validator");
+    let result = fixt.claim_compute_node(&second_validator, DEFAULT_NODE_INFO_CID.to_string());
+    assert!(result.is_ok(), "Failed to claim second validator");
+    let result = fixt.validate_public_node(&confidential_node, &second_validator.pubkey(), true);
+    assert!(result.is_ok(), "Failed to validate second validator");
+
+    // Pool minus the compute node is now exactly the required quorum
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut network_config = fixt.get_network_config();
+    network_config.required_validations = 2;
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        0,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+
+    match result {
+        Ok(metadata) => {
+            let event: ValidatorsAssigned =
+                Helpers::decode_event(&metadata.logs, "ValidatorsAssigned")
+                    .expect("ValidatorsAssigned event not emitted");
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+            let mut assigned: Vec<Pubkey> = task.validations.iter().map(|v| v.pubkey).collect();
+            assigned.sort();
+            let mut expected = vec![fixt.validator_node.pubkey(), second_validator.pubkey()];
+            expected.sort();
+            assert_eq!(assigned, expected);
+
+            let mut emitted = event.validators.clone();
+            emitted.sort();
+            assert_eq!(emitted, expected);
+        }
+        Err(e) => panic!("Failed to claim task: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_authority_rejects_active_confidential_node() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node();
+
+    let authority = fixt.authority.insecure_clone();
+    let node_pubkey = fixt.confidential_node.pubkey();
+    assert_eq!(fixt.get_node_info(&node_pubkey).status, NodeStatus::Active);
+    assert!(fixt
+        .get_network_config()
+        .approved_confidential_nodes
+        .contains(&node_pubkey));
+
+    let outsider = fixt.create_keypair();
+    let result = fixt.authority_reject_node(&outsider, &node_pubkey);
+    assert!(
+        result.is_err(),
+        "Only the authority can force-reject a node"
+    );
+
+    let result = fixt.authority_reject_node(&authority, &node_pubkey);
+
+    match result {
+        Ok(metadata) => {
+            let event: NodePoolMembershipChanged =
+                Helpers::decode_event(&metadata.logs, "NodePoolMembershipChanged")
+                    .expect("NodePoolMembershipChanged event not emitted on rejection");
+            assert_eq!(event.node, node_pubkey);
+            assert_eq!(event.node_type, NodeType::Confidential);
+            assert_eq!(event.change, PoolMembershipChange::Removed);
+
+            let node_info = fixt.get_node_info(&node_pubkey);
+            let network_config = fixt.get_network_config();
+            assert_eq!(node_info.status, NodeStatus::Rejected);
+            assert!(!network_config
+                .approved_confidential_nodes
+                .contains(&node_pubkey));
+        }
+        Err(e) => panic!("Failed to reject node: {:#?}", e),
+    }
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.authority_reject_node(&authority, &node_pubkey);
+    assert!(result.is_err(), "A rejected node can't be rejected again");
+}
+
+#[test]
+fn test_task_payment_split_between_node_validators_and_protocol() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let authority = fixt.authority.insecure_clone();
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.validator_share_bps = Some(6_000);
+        args.protocol_share_bps = Some(5_000);
+    });
+    let err = result.expect_err("Shares above 100% should be rejected");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidPaymentSplit"
+    ));
+
+    let validator_share_bps = 1_000;
+    let protocol_share_bps = 500;
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.validator_share_bps = Some(validator_share_bps);
+        args.protocol_share_bps = Some(protocol_share_bps);
+    });
+    assert!(result.is_ok(), "Failed to set payment split");
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.pubkey();
+    let compute_treasury_pda = fixt.get_node_info(&compute_node).node_treasury;
+    let protocol_treasury_pda = fixt.find_protocol_treasury_pda(&network_config_pda).0;
+    let validator_node_info_pda = fixt.find_node_info_pda(&fixt.validator_node.pubkey()).0;
+    let validator_treasury_pda = fixt
+        .get_node_info(&fixt.validator_node.pubkey())
+        .node_treasury;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, session_slot_id);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        600_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let payment_amount: u64 = 100_000_000;
+    let vault_lamports = fixt.svm.get_lamports(&vault_pda);
+    let compute_treasury_lamports = fixt.svm.get_lamports(&compute_treasury_pda);
+    let validator_treasury_lamports = fixt.svm.get_lamports(&validator_treasury_pda);
+    let protocol_treasury_lamports = fixt.svm.get_lamports(&protocol_treasury_pda);
+    let result = fixt.submit_public_task_validation_with(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node,
+        payment_amount,
+        true,
+        false,
+        |builder| {
+            builder.add_remaining_accounts(&[
+                AccountMeta::new_readonly(validator_node_info_pda, false),
+                AccountMeta::new(validator_treasury_pda, false),
+            ]);
+        },
+    );
+
+    match result {
+        Ok(_) => {
+            let validator_amount = payment_amount * validator_share_bps as u64 / 10_000;
+            let protocol_amount = payment_amount * protocol_share_bps as u64 / 10_000;
+            let compute_amount = payment_amount - validator_amount - protocol_amount;
+
+            let compute_paid =
+                fixt.svm.get_lamports(&compute_treasury_pda) - compute_treasury_lamports;
+            let validator_paid =
+                fixt.svm.get_lamports(&validator_treasury_pda) - validator_treasury_lamports;
+            let protocol_paid =
+                fixt.svm.get_lamports(&protocol_treasury_pda) - protocol_treasury_lamports;
+
+            assert_eq!(compute_paid, compute_amount);
+            assert_eq!(validator_paid, validator_amount);
+            assert_eq!(protocol_paid, protocol_amount);
+            assert_eq!(
+                compute_paid + validator_paid + protocol_paid,
+                payment_amount
+            );
+            assert_eq!(
+                fixt.svm.get_lamports(&vault_pda),
+                vault_lamports - payment_amount
+            );
+        }
+        Err(e) => panic!("Failed to split task payment: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_rejected_validation_clears_pending_result() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.insecure_clone();
+
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        600_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert!(task.pending_input_cid.is_some());
+    assert!(task.pending_output_cid.is_some());
+
+    let result = fixt.submit_public_task_validation(
+        &fixt.validator_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        0,
+        false,
+        false,
+    );
+
+    match result {
+        Ok(_) => {
+            let task = fixt.get_task(&network_config_pda, task_slot_id);
+            assert_eq!(task.status, TaskStatus::Ready);
+            assert_eq!(task.pending_input_cid, None);
+            assert_eq!(task.pending_output_cid, None);
+        }
+        Err(e) => panic!("Failed to reject task: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_crowdfunded_session_set_collectively_once_funded() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let creator = fixt.create_keypair();
+    let first_contributor = fixt.contributor.insecure_clone();
+    let second_contributor = fixt.create_keypair();
+    let outsider = fixt.create_keypair();
+    let funding_threshold = 2 * DEFAULT_CONTRIBUTION_AMOUNT;
+
+    let result = fixt.create_session_with(&creator, true, false, |builder| {
+        builder.funding_threshold(funding_threshold);
+    });
+    assert!(result.is_err(), "Owned sessions should not be crowdfunded");
+
+    let result = fixt.create_session_with(&creator, false, false, |builder| {
+        builder.funding_threshold(funding_threshold);
+    });
+    assert!(result.is_ok(), "Failed to create crowdfunded session");
+
+    let session_slot_id = 0;
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.public_node.pubkey();
+
+    let result = fixt.contribute_to_session(
+        &first_contributor,
+        session_slot_id,
+        DEFAULT_CONTRIBUTION_AMOUNT,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to fund pending session: {:#?}",
+        result
+    );
+
+    let err = fixt
+        .set_session(
+            &first_contributor,
+            session_slot_id,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            0,
+            compute_node,
+            TaskType::Completion(0),
+        )
+        .expect_err("Session should not be set below its funding threshold");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "FundingThresholdNotReached"
+    ));
+
+    let result = fixt.contribute_to_session(
+        &second_contributor,
+        session_slot_id,
+        DEFAULT_CONTRIBUTION_AMOUNT,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to fund pending session: {:#?}",
+        result
+    );
+
+    let err = fixt
+        .set_session(
+            &outsider,
+            session_slot_id,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            0,
+            compute_node,
+            TaskType::Completion(0),
+        )
+        .expect_err("Only contributors should set a crowdfunded session");
+    assert!(Helpers::has_error_code(&err.meta.logs, "NoVotingShares"));
+
+    let result = fixt.set_session(
+        &second_contributor,
+        session_slot_id,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        0,
+        compute_node,
+        TaskType::Completion(0),
+    );
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, session_slot_id);
+
+            assert_eq!(session.owner, Pubkey::default());
+            assert_eq!(session.status, SessionStatus::Active);
+            assert_eq!(session.funding_threshold, Some(funding_threshold));
+            assert_eq!(session.total_shares, funding_threshold);
+        }
+        Err(e) => panic!("Contributor failed to set crowdfunded session: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_task_validation_event_reports_cumulative_totals() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let max_task_cost = 100_000_000;
+    let max_iterations = fixt
+        .get_session(&network_config_pda, session_slot_id)
+        .max_iterations;
+
+    let mut expected_total_paid = 0;
+    for iteration in 1..=3u64 {
+        let payment_amount = iteration * 10_000_000;
+
+        let result = fixt.claim_task(
+            &compute_node,
+            session_slot_id,
+            task_slot_id,
+            max_task_cost,
+            10,
+        );
+        assert!(result.is_ok(), "Failed to claim iteration {iteration}");
+
+        let result = fixt.submit_task_result(
+            &compute_node,
+            session_slot_id,
+            task_slot_id,
+            format!("QmIterationInput{iteration}"),
+            format!("QmIterationOutput{iteration}"),
+            None,
+            1,
+        );
+        assert!(result.is_ok(), "Failed to submit iteration {iteration}");
+
+        let metadata = fixt
+            .submit_public_task_validation(
+                &validator_node,
+                session_slot_id,
+                task_slot_id,
+                &compute_node.pubkey(),
+                payment_amount,
+                true,
+                false,
+            )
+            .expect("Failed to validate iteration");
+        expected_total_paid += payment_amount;
+
+        let event: TaskValidationSubmitted =
+            Helpers::decode_event(&metadata.logs, "TaskValidationSubmitted")
+                .expect("TaskValidationSubmitted not emitted");
+        assert_eq!(event.current_iteration, iteration);
+        assert_eq!(event.total_paid_this_session, expected_total_paid);
+        assert_eq!(event.iterations_remaining, Some(max_iterations - iteration));
+
+        fixt.svm.expire_blockhash();
+    }
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.total_paid, expected_total_paid);
+}
+
+#[test]
+fn test_agent_validation_restricted_to_confidential_nodes() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent();
+
+    let authority = fixt.authority.insecure_clone();
+    let public_node = fixt.public_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.agent_validators_must_be_confidential = Some(true);
+    });
+    assert!(
+        result.is_ok(),
+        "Failed to require confidential agent validators"
+    );
+    assert!(
+        fixt.get_network_config()
+            .agent_validators_must_be_confidential
+    );
+
+    let err = fixt
+        .validate_agent(&public_node, 0)
+        .expect_err("Public node should not validate agents");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidNodeType"));
+    assert_eq!(
+        fixt.get_agent(&network_config_pda, 0).status,
+        AgentStatus::Pending
+    );
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.agent_validators_must_be_confidential = Some(false);
+    });
+    assert!(result.is_ok(), "Failed to allow public agent validators");
+
+    let result = fixt.validate_agent(&public_node, 0);
+    assert!(
+        result.is_ok(),
+        "Public node should validate agents: {:#?}",
+        result
+    );
+    assert_eq!(
+        fixt.get_agent(&network_config_pda, 0).status,
+        AgentStatus::Active
+    );
+}
+
+fn held_task_settlement(
+    hold_slots: u64,
+    payment_amount: u64,
+    prefunded_lamports: u64,
+) -> (TestFixture, u64) {
+    let mut fixt = public_task_awaiting_validation(0);
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.settlement_hold_slots = Some(hold_slots);
+    });
+    assert!(result.is_ok(), "Failed to set settlement hold");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+    let task_index = fixt.get_task(&network_config_pda, task_slot_id).task_index;
+    let (settlement_pda, _) = fixt.find_task_settlement_pda(&task_pda, task_index);
+    let compute_node = fixt.public_node.pubkey();
+    let validator_node = fixt.validator_node.insecure_clone();
+    if prefunded_lamports > 0 {
+        fixt.svm.airdrop(&settlement_pda, prefunded_lamports).unwrap();
+    }
+
+    let err = fixt
+        .submit_public_task_validation(
+            &validator_node,
+            0,
+            task_slot_id,
+            &compute_node,
+            payment_amount,
+            true,
+            false,
+        )
+        .expect_err("A held payment needs its escrow account");
+    assert!(Helpers::has_error_code(&err.meta.logs, "MissingAccount"));
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.submit_public_task_validation_with(
+        &validator_node,
+        0,
+        task_slot_id,
+        &compute_node,
+        payment_amount,
+        true,
+        false,
+        |builder| {
+            builder.task_settlement(Some(settlement_pda));
+        },
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to validate with a held payment: {:#?}",
+        result
+    );
+
+    (fixt, task_index)
+}
+
+#[test]
+fn test_task_payment_held_then_released() {
+    let hold_slots = 100;
+    let payment_amount = 50_000_000;
+    let (mut fixt, task_index) = held_task_settlement(hold_slots, payment_amount, 0);
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+    let (settlement_pda, _) = fixt.find_task_settlement_pda(&task_pda, task_index);
+    let compute_node = fixt.public_node.insecure_clone();
+    let node_treasury = fixt.get_node_info(&compute_node.pubkey()).node_treasury;
+    let treasury_before = fixt.svm.get_lamports(&node_treasury);
+
+    let settlement = fixt.get_task_settlement(&task_pda, task_index);
+    assert_eq!(settlement.amount, payment_amount);
+    assert_eq!(settlement.task_index, task_index);
+    assert_eq!(
+        fixt.get_node_info(&compute_node.pubkey()).total_earned,
+        0,
+        "Held payments are not earned yet"
+    );
+
+    let err = fixt
+        .release_task_settlement(&compute_node, task_slot_id, task_index)
+        .expect_err("Payment should stay held during the hold period");
+    assert!(Helpers::has_error_code(&err.meta.logs, "SettlementHeld"));
+    assert_eq!(fixt.svm.get_lamports(&node_treasury), treasury_before);
+
+    fixt.svm.warp_to_slot(settlement.release_slot);
+    fixt.svm.expire_blockhash();
+
+    let err = fixt
+        .dispute_task_settlement(
+            &fixt.authority.insecure_clone(),
+            0,
+            task_slot_id,
+            task_index,
+        )
+        .expect_err("Payment can't be disputed once the hold has passed");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "SettlementHoldExpired"
+    ));
+
+    let result = fixt.release_task_settlement(&compute_node, task_slot_id, task_index);
+
+    match result {
+        Ok(_) => {
+            assert_eq!(
+                fixt.svm.get_lamports(&node_treasury),
+                treasury_before + payment_amount
+            );
+            assert_eq!(fixt.svm.get_lamports(&settlement_pda), 0);
+            assert_eq!(
+                fixt.get_node_info(&compute_node.pubkey()).total_earned,
+                payment_amount
+            );
+        }
+        Err(e) => panic!("Failed to release held payment: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_task_payment_clawed_back_when_disputed() {
+    let payment_amount = 50_000_000;
+    let (mut fixt, task_index) = held_task_settlement(100, payment_amount, 0);
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+    let (settlement_pda, _) = fixt.find_task_settlement_pda(&task_pda, task_index);
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+    let compute_node = fixt.public_node.insecure_clone();
+    let node_treasury = fixt.get_node_info(&compute_node.pubkey()).node_treasury;
+    let treasury_before = fixt.svm.get_lamports(&node_treasury);
+    let vault_before = fixt.svm.get_lamports(&vault_pda);
+    // The vault paid the escrow's rent, closing the escrow returns it with the payment
+    assert_eq!(
+        fixt.get_task_settlement(&task_pda, task_index).payer,
+        vault_pda
+    );
+    let settlement_lamports = fixt.svm.get_lamports(&settlement_pda);
+
+    let result = fixt.dispute_task_settlement(
+        &fixt.authority.insecure_clone(),
+        0,
+        task_slot_id,
+        task_index,
+    );
+
+    match result {
+        Ok(_) => {
+            let session = fixt.get_session(&network_config_pda, 0);
+
+            assert_eq!(
+                fixt.svm.get_lamports(&vault_pda),
+                vault_before + settlement_lamports
+            );
+            assert_eq!(fixt.svm.get_lamports(&node_treasury), treasury_before);
+            assert_eq!(fixt.svm.get_lamports(&settlement_pda), 0);
+            assert_eq!(session.total_paid, 0);
+            assert_eq!(session.current_iteration, 1);
+        }
+        Err(e) => panic!("Failed to dispute held payment: {:#?}", e),
+    }
+}
+
+#[test]
+fn test_prefunded_task_settlement_address_still_holds_payment() {
+    let payment_amount = 50_000_000;
+    let prefunded_lamports = 1_000;
+    let (mut fixt, task_index) = held_task_settlement(100, payment_amount, prefunded_lamports);
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+    let (settlement_pda, _) = fixt.find_task_settlement_pda(&task_pda, task_index);
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+
+    let settlement = fixt.get_task_settlement(&task_pda, task_index);
+    assert_eq!(settlement.amount, payment_amount);
+    assert_eq!(settlement.payer, vault_pda);
+    let account = fixt.svm.get_account(&settlement_pda).unwrap();
+    assert_eq!(
+        account.lamports,
+        fixt.svm
+            .minimum_balance_for_rent_exemption(account.data.len())
+            + payment_amount
+    );
+}
+
+#[test]
+fn test_only_set_session_marks_owner_contribution() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+
+    assert!(
+        fixt.get_contribution(&session_pda, &owner.pubkey())
+            .is_owner
+    );
+
+    let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Failed to contribute: {:#?}", result);
+    assert!(
+        !fixt
+            .get_contribution(&session_pda, &contributor.pubkey())
+            .is_owner
+    );
+
+    // Topping up through contribute_to_session keeps the owner's flag
+    let result = fixt.contribute_to_session(&owner, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Owner failed to top up: {:#?}", result);
+
+    let owner_contribution = fixt.get_contribution(&session_pda, &owner.pubkey());
+    assert!(owner_contribution.is_owner);
+    assert!(owner_contribution.shares > DEFAULT_INITIAL_DEPOSIT);
+}
+
+#[test]
+fn test_validation_tally_after_partial_voting() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let second_validator_owner = fixt.create_keypair();
+    let second_validator = fixt.create_keypair();
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let result = fixt.register_node(
+        &second_validator_owner,
+        &second_validator.pubkey(),
+        NodeType::Public,
+    );
+    assert!(result.is_ok(), "Failed to register second validator");
+    let result = fixt.claim_compute_node(&second_validator, DEFAULT_NODE_INFO_CID.to_string());
+    assert!(result.is_ok(), "Failed to claim second validator");
+    let result = fixt.validate_public_node(&confidential_node, &second_validator.pubkey(), true);
+    assert!(result.is_ok(), "Failed to validate second validator");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut network_config = fixt.get_network_config();
+    network_config.required_validations = 2;
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let payer = fixt.create_keypair();
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed first approval: {:#?}", result);
+
+    let metadata = fixt
+        .get_validation_tally(&payer, task_slot_id)
+        .expect("Failed to read validation tally");
+    let tally: ValidationTally = Helpers::decode_event(&metadata.logs, "ValidationTally")
+        .expect("ValidationTally not emitted");
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(tally.task_slot_id, task_slot_id);
+    assert_eq!(tally.task_index, task.task_index);
+    assert_eq!(tally.approved, 1);
+    assert_eq!(tally.rejected, 0);
+    assert_eq!(tally.pending, 1);
+    assert_eq!(tally.required_validations, 2);
+    assert!(!tally.approval_threshold_reached);
+    assert!(!tally.rejection_threshold_reached);
+
+    let result = fixt.submit_public_task_validation(
+        &second_validator,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        0,
+        false,
+        false,
+    );
+    assert!(result.is_ok(), "Failed rejection: {:#?}", result);
+
+    fixt.svm.expire_blockhash();
+    let metadata = fixt
+        .get_validation_tally(&payer, task_slot_id)
+        .expect("Failed to read validation tally");
+    let tally: ValidationTally = Helpers::decode_event(&metadata.logs, "ValidationTally")
+        .expect("ValidationTally not emitted");
+    assert_eq!(tally.approved, 1);
+    assert_eq!(tally.rejected, 1);
+    assert_eq!(tally.pending, 0);
+    assert!(!tally.approval_threshold_reached);
+    assert!(!tally.rejection_threshold_reached);
+}
+
+#[test]
+fn test_network_default_task_type_applies_to_new_tasks() {
+    let mut fixt = TestFixture::new();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let allocate_tasks = 2;
+    let default_task_type = TaskType::Custom(7);
+    let remaining_accounts =
+        fixt.create_remaining_accounts_for_initialize(&network_config_pda, allocate_tasks);
+
+    let result = fixt.initialize_network_with(
+        &fixt.authority.insecure_clone(),
+        &network_config_pda,
+        DEFAULT_CID_CONFIG.to_string(),
+        allocate_tasks,
+        DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
+        DEFAULT_REQUIRED_VALIDATIONS,
+        &remaining_accounts,
+        |builder| {
+            builder.default_task_type(default_task_type.clone());
+        },
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to initialize network: {:#?}",
+        result
+    );
+    assert_eq!(
+        fixt.get_network_config().default_task_type,
+        default_task_type
+    );
+
+    for task_slot_id in 0..allocate_tasks {
+        let task = fixt.get_task(&network_config_pda, task_slot_id);
+        assert_eq!(task.task_type, default_task_type);
+    }
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let result = fixt.create_session(&owner, true, false);
+    assert!(result.is_ok(), "Failed to create session: {:#?}", result);
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    assert_eq!(task_slot_id, allocate_tasks);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.task_type, default_task_type);
+}
+
+#[test]
+fn test_session_records_agent_and_rejects_different_agent_on_reuse() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_agent()
+        .with_validated_agent(1)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.pubkey();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (agent_pda, _) = fixt.find_agent_pda(&network_config_pda, 0);
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.agent, agent_pda);
+
+    complete_public_session(&mut fixt, session_slot_id, 50_000_000);
+
+    // Reusing the completed session under another agent is rejected
+    fixt.svm.expire_blockhash();
+    let err = fixt
+        .set_session(
+            &owner,
+            session_slot_id,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            1,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            compute_node,
+            TaskType::Completion(0),
+        )
+        .expect_err("Session must keep its agent");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidSession"));
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(session.agent, agent_pda);
+}
+
+#[test]
+fn test_update_agent_memory_after_validated_iteration() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let session_slot_id = 0;
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let memory_cid = "QmAgentMemoryCID".to_string();
+
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+
+    // Memory can't move while the iteration is still running
+    let err = fixt
+        .update_agent_memory(&compute_node, session_slot_id, memory_cid.clone())
+        .expect_err("Memory updated before validation");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidTaskStatus"));
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to submit task result: {:#?}",
+        result.err()
+    );
+    let result = fixt.submit_public_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to validate task: {:#?}",
+        result.err()
+    );
+
+    let err = fixt
+        .update_agent_memory(&validator, session_slot_id, memory_cid.clone())
+        .expect_err("Only the session's compute node updates memory");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidComputeNodePubkey"
+    ));
+
+    let result = fixt.update_agent_memory(&compute_node, session_slot_id, memory_cid.clone());
+    assert!(
+        result.is_ok(),
+        "Failed to update agent memory: {:#?}",
+        result.err()
+    );
+
+    let agent = fixt.get_agent(&network_config_pda, 0);
+    assert_eq!(agent.agent_memory_cid, Some(memory_cid));
+}
+
+#[test]
+fn test_task_compute_estimate_set_by_owner() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false);
+
+    let session_slot_id = 0;
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.pubkey();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    let result = fixt.set_session_with(
+        &owner,
+        session_slot_id,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_INITIAL_DEPOSIT,
+        compute_node,
+        TaskType::Completion(0),
+        |_, args| {
+            args.estimated_compute_units = Some(250_000);
+        },
+    );
+    assert!(result.is_ok(), "Failed to set session: {:#?}", result.err());
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.estimated_compute_units, 250_000);
+
+    let result = fixt.seed_task_input_with(
+        &owner,
+        session_slot_id,
+        "QmSeededPrompt123456789".to_string(),
+        |builder| {
+            builder.estimated_compute_units(400_000);
+        },
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to seed task input: {:#?}",
+        result.err()
+    );
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.estimated_compute_units, 400_000);
+
+    // Advisory only, a claim doesn't have to match it
+    let result = fixt.claim_task(
+        &fixt.public_node.insecure_clone(),
+        session_slot_id,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+}
+
+#[test]
+fn test_create_session_rejects_allocated_task_slot() {
+    let mut fixt = TestFixture::new();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let allocate_tasks = 2;
+    let remaining_accounts =
+        fixt.create_remaining_accounts_for_initialize(&network_config_pda, allocate_tasks);
+
+    let result = fixt.initialize_network(
+        &fixt.authority.insecure_clone(),
+        &network_config_pda,
+        DEFAULT_CID_CONFIG.to_string(),
+        allocate_tasks,
+        DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
+        DEFAULT_REQUIRED_VALIDATIONS,
+        None,
+        &remaining_accounts,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to initialize network: {:#?}",
+        result
+    );
+
+    // New sessions get a fresh slot past the pre-allocated tasks
+    let owner = fixt.agent_owner.insecure_clone();
+    let result = fixt.create_session(&owner, true, false);
+    assert!(result.is_ok(), "Failed to create session: {:#?}", result);
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    assert_eq!(task_slot_id, allocate_tasks);
+    for pre_allocated_slot_id in 0..allocate_tasks {
+        let task = fixt.get_task(&network_config_pda, pre_allocated_slot_id);
+        assert_eq!(task.session_slot_id, None);
+    }
+
+    // A task_count that fell behind points back at an allocated task
+    let mut network_config = fixt.get_network_config();
+    network_config.task_count = 0;
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    fixt.svm.expire_blockhash();
+    let err = fixt
+        .create_session(&owner, true, false)
+        .expect_err("Allocated task slot must not be reused");
+    assert!(Helpers::has_error_code(&err.meta.logs, "TaskSlotInUse"));
+    let task = fixt.get_task(&network_config_pda, 0);
+    assert_eq!(task.session_slot_id, None);
+}
+
+#[test]
+fn test_create_session_with_prefunded_task_address() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.get_network_config().task_count;
+    let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+
+    // Anyone can send lamports to the next task address ahead of the session
+    fixt.svm.airdrop(&task_pda, 1_000).unwrap();
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let result = fixt.create_session(&owner, true, false);
+    assert!(
+        result.is_ok(),
+        "Pre-funded task address must not block create_session: {:#?}",
+        result.err()
+    );
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.session_slot_id, Some(0));
+    let account = fixt.svm.get_account(&task_pda).unwrap();
+    assert_eq!(account.owner, fixt.program_id);
+    assert!(
+        account.lamports
+            >= fixt
+                .svm
+                .minimum_balance_for_rent_exemption(account.data.len())
+    );
+}
+
+#[test]
+fn test_claim_task_waits_for_iteration_gap() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let iteration_gap = 50;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.min_slots_between_iterations = Some(iteration_gap);
+    });
+    assert!(result.is_ok(), "Failed to set iteration gap");
+
+    let session_slot_id = 0;
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    // The first iteration of a run is never gated
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to submit task result: {:#?}",
+        result.err()
+    );
+    let result = fixt.submit_public_task_validation(
+        &validator,
+        session_slot_id,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to validate task: {:#?}",
+        result.err()
+    );
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    let last_iteration_slot = session
+        .last_iteration_slot
+        .expect("Completed iteration records its slot");
+
+    fixt.svm.expire_blockhash();
+    let err = fixt
+        .claim_task(
+            &compute_node,
+            session_slot_id,
+            task_slot_id,
+            100_000_000,
+            10,
+        )
+        .expect_err("Next iteration claimed before the gap");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "IterationRateLimited"
+    ));
+
+    fixt.svm.warp_to_slot(last_iteration_slot + iteration_gap);
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        100_000_000,
+        10,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to claim after the gap: {:#?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_initialize_network_rejects_invalid_required_validations() {
+    for required_validations in [0, 11] {
+        let mut fixt = TestFixture::new();
+        let network_config_pda = fixt.find_network_config_pda().0;
+
+        let err = fixt
+            .initialize_network(
+                &fixt.authority.insecure_clone(),
+                &network_config_pda,
+                DEFAULT_CID_CONFIG.to_string(),
+                0,
+                DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
+                required_validations,
+                None,
+                &[],
+            )
+            .expect_err("Required validations must fit the validator pool");
+        assert!(Helpers::has_error_code(
+            &err.meta.logs,
+            "InvalidRequiredValidations"
+        ));
+    }
+}
+
+#[test]
+fn test_claim_task_rejects_out_of_policy_cost_and_paused_network() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let err = fixt
+        .claim_task(&compute_node, 0, task_slot_id, 0, 10)
+        .expect_err("A free task can't be claimed");
+    assert!(Helpers::has_error_code(&err.meta.logs, "TaskCostTooLow"));
+
+    let err = fixt
+        .claim_task(
+            &compute_node,
+            0,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT + 1,
+            10,
+        )
+        .expect_err("The session can't cover the task");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InsufficientBalance"
+    ));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.paused = Some(true);
+    });
+    assert!(result.is_ok(), "Failed to pause network");
+
+    let err = fixt
+        .claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10)
+        .expect_err("Claims are frozen while the network is paused");
+    assert!(Helpers::has_error_code(&err.meta.logs, "NetworkPaused"));
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.paused = Some(false);
+    });
+    assert!(result.is_ok(), "Failed to unpause network");
+    fixt.svm.expire_blockhash();
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+}
+
+#[test]
+fn test_submit_task_result_rejects_call_count_over_claim() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let err = fixt
+        .submit_task_result(
+            &compute_node,
+            0,
+            task_slot_id,
+            "QmTestInput123456789".to_string(),
+            "QmTestOutput123456789".to_string(),
+            None,
+            11,
+        )
+        .expect_err("Call count is bounded by the claim");
+    assert!(Helpers::has_error_code(&err.meta.logs, "CallCountExceeded"));
+}
+
+#[test]
+fn test_task_validation_rejects_self_validation_and_overpayment() {
+    let mut fixt = public_task_awaiting_validation(0);
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let err = fixt
+        .submit_public_task_validation(
+            &compute_node,
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            50_000_000,
+            true,
+            false,
+        )
+        .expect_err("A node can't validate its own result");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "SelfValidationForbidden"
+    ));
+
+    // Claimed at 100_000_000
+    let err = fixt
+        .submit_public_task_validation(
+            &validator,
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            100_000_001,
+            true,
+            false,
+        )
+        .expect_err("Payment can't exceed the claimed task cost");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "PaymentExceedsPolicy"
+    ));
+}
+
+#[test]
+fn test_validate_node_rejects_full_pool() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node();
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut network_config = fixt.get_network_config();
+    network_config.approved_public_nodes = (0..10).map(|_| Pubkey::new_unique()).collect();
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let public_node = fixt.public_node.pubkey();
+    let err = fixt
+        .validate_public_node(&fixt.confidential_node.insecure_clone(), &public_node, true)
+        .expect_err("A full pool can't take another node");
+    assert!(Helpers::has_error_code(&err.meta.logs, "TooManyNodes"));
+}
+
+#[test]
+fn test_contribution_slippage_and_ownership_cap() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let contributor = fixt.contributor.insecure_clone();
+    let err = fixt
+        .contribute_to_session_with(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT, |builder| {
+            builder.min_shares(u64::MAX);
+        })
+        .expect_err("Fewer shares than the minimum");
+    assert!(Helpers::has_error_code(&err.meta.logs, "SlippageExceeded"));
+
+    // Owner holds 1 SOL of shares, 0.5 SOL more would give the contributor a third
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.max_ownership_bps = Some(2_500);
+    });
+    assert!(result.is_ok(), "Failed to set ownership cap");
+
+    let err = fixt
+        .contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT)
+        .expect_err("Contribution above the ownership cap");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "OwnershipCapExceeded"
+    ));
+
+    let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT / 5);
+    assert!(
+        result.is_ok(),
+        "Failed to contribute under the cap: {:#?}",
+        result.err()
+    );
+}
+
+#[test]
+fn test_set_session_rejects_disallowed_model() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false);
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut network_config = fixt.get_network_config();
+    network_config.allowed_models = vec![1];
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let public_node = fixt.public_node.pubkey();
+    let err = fixt
+        .set_session(
+            &fixt.agent_owner.insecure_clone(),
+            0,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            public_node,
+            TaskType::Completion(0),
+        )
+        .expect_err("Model 0 isn't allowed on this network");
+    assert!(Helpers::has_error_code(&err.meta.logs, "NotAllowedModel"));
+}
+
+#[test]
+fn test_task_slots_unique_across_preallocation_and_sessions() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+    fixt.assert_task_slots_unique();
+
+    // Sessions draw from the same task_count as the slots allocated at initialize
+    let owner = fixt.agent_owner.insecure_clone();
+    let other_payer = fixt.create_keypair();
+    for (payer, is_owned, is_confidential) in [
+        (&owner, true, false),
+        (&other_payer, false, false),
+        (&owner, true, true),
+    ] {
+        let result = fixt.create_session(payer, is_owned, is_confidential);
+        assert!(
+            result.is_ok(),
+            "Failed to create session: {:#?}",
+            result.err()
+        );
+        fixt.assert_task_slots_unique();
+    }
+
+    let network_config = fixt.get_network_config();
+    assert_eq!(
+        network_config.task_count,
+        DEFAULT_ALLOCATE_TASKS + network_config.session_count
+    );
+}
+
+#[test]
+fn test_migrate_network_config_grows_account() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true);
+    let authority = fixt.authority.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    let config = fixt.get_network_config();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    let full_space = account.data.len();
+
+    // Rewrite the account in the first deployed layout, which ends at bump, followed by
+    // stale bytes the migration must not read as the appended fields
+    let mut legacy = account.data[..8].to_vec();
+    legacy.extend(
+        borsh::to_vec(&(
+            config.authority,
+            config.cid_config.clone(),
+            config.genesis_hash,
+            config.task_count,
+            config.required_validations,
+            config.allowed_models.clone(),
+        ))
+        .unwrap(),
+    );
+    legacy.extend(
+        borsh::to_vec(&(
+            config.approved_confidential_nodes.clone(),
+            config.approved_public_nodes.clone(),
+            config.agent_count,
+            config.session_count,
+            config.approved_code_measurements.clone(),
+            config.bump,
+        ))
+        .unwrap(),
+    );
+    legacy.extend([0xff; 16]);
+    assert!(legacy.len() < full_space);
+    let legacy_space = legacy.len();
+    account.data = legacy;
+    account.lamports = fixt.svm.minimum_balance_for_rent_exemption(legacy_space);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let result = fixt.migrate_network_config(&authority);
+    match result {
+        Ok(metadata) => {
+            let migrated: NetworkConfigMigrated =
+                Helpers::decode_event(&metadata.logs, "NetworkConfigMigrated")
+                    .expect("NetworkConfigMigrated event not found");
+            assert_eq!(migrated.previous_space, legacy_space as u64);
+            assert_eq!(migrated.space, full_space as u64);
+        }
+        Err(e) => panic!("Failed to migrate network config: {:#?}", e),
+    }
+
+    let account = fixt.svm.get_account(&network_config_pda).unwrap();
+    assert_eq!(account.data.len(), full_space);
+    assert!(account.lamports >= fixt.svm.minimum_balance_for_rent_exemption(full_space));
+    let migrated = fixt.get_network_config();
+    assert_eq!(migrated.authority, config.authority);
+    assert_eq!(migrated.cid_config, config.cid_config);
+    assert_eq!(migrated.genesis_hash, config.genesis_hash);
+    assert_eq!(migrated.task_count, config.task_count);
+    assert_eq!(migrated.required_validations, config.required_validations);
+    assert_eq!(
+        migrated.approved_public_nodes,
+        vec![fixt.public_node.pubkey()]
+    );
+    assert_eq!(
+        migrated.approved_code_measurements,
+        config.approved_code_measurements
+    );
+    assert_eq!(migrated.bump, config.bump);
+    // Appended fields take the values a new network starts with
+    assert_eq!(
+        migrated.agent_required_validations,
+        config.required_validations
+    );
+    assert_eq!(migrated.default_task_type, TaskType::Completion(0));
+    assert_eq!(migrated.validation_stall_slots, 0);
+    assert_eq!(migrated.genesis_nonce, None);
+    assert_eq!(migrated.max_vault_balance, None);
+    assert!(migrated.allowed_cid_prefixes.is_empty());
+
+    // The regained room is usable right away
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.allowed_cid_prefixes = Some(vec!["bafy".to_string(), "Qm".to_string()]);
+    });
+    assert!(
+        result.is_ok(),
+        "Failed to grow the config after migration: {:#?}",
+        result.err()
+    );
+    let network_config = fixt.get_network_config();
+    assert_eq!(network_config.allowed_cid_prefixes.len(), 2);
+    assert_eq!(
+        network_config.approved_public_nodes,
+        vec![fixt.public_node.pubkey()]
+    );
+}
+
+#[test]
+fn test_migrate_account_grows_legacy_session() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+    let payer = fixt.create_keypair();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session_slot_id = 0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, session_slot_id);
+
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    let mut account = fixt.svm.get_account(&session_pda).unwrap();
+    let full_space = account.data.len();
+
+    // Rewrite the account in the first deployed layout, which ends at bump, followed by
+    // stale bytes the migration must not read as the appended fields
+    let mut legacy = account.data[..8].to_vec();
+    legacy.extend(
+        borsh::to_vec(&(
+            session.session_slot_id,
+            session.owner,
+            session.task,
+            session.status,
+            session.is_confidential,
+            session.max_iterations,
+            session.current_iteration,
+        ))
+        .unwrap(),
+    );
+    legacy.extend(
+        borsh::to_vec(&(
+            session.task_index_start,
+            session.task_index_end,
+            session.total_shares,
+            session.locked_for_tasks,
+            session.specification_cid.clone(),
+            session.state_cid.clone(),
+            session.vault_bump,
+            session.bump,
+        ))
+        .unwrap(),
+    );
+    legacy.extend([0xff; 16]);
+    assert!(legacy.len() < full_space);
+    let legacy_space = legacy.len();
+    account.data = legacy;
+    account.lamports = fixt.svm.minimum_balance_for_rent_exemption(legacy_space);
+    fixt.svm.set_account(session_pda, account).unwrap();
+
+    let result = fixt.migrate_account(&payer, &session_pda);
+    match result {
+        Ok(metadata) => {
+            let migrated: AccountMigrated =
+                Helpers::decode_event(&metadata.logs, "AccountMigrated")
+                    .expect("AccountMigrated event not found");
+            assert_eq!(migrated.account, session_pda);
+            assert_eq!(migrated.previous_space, legacy_space as u64);
+            assert_eq!(migrated.space, full_space as u64);
+        }
+        Err(e) => panic!("Failed to migrate session: {:#?}", e),
+    }
+
+    let account = fixt.svm.get_account(&session_pda).unwrap();
+    assert_eq!(account.data.len(), full_space);
+    assert!(account.lamports >= fixt.svm.minimum_balance_for_rent_exemption(full_space));
+    let migrated = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(migrated.owner, session.owner);
+    assert_eq!(migrated.task, session.task);
+    assert_eq!(migrated.status, SessionStatus::Active);
+    assert_eq!(migrated.total_shares, session.total_shares);
+    assert_eq!(migrated.locked_for_tasks, session.locked_for_tasks);
+    assert_eq!(migrated.specification_cid, session.specification_cid);
+    assert_eq!(migrated.vault_bump, session.vault_bump);
+    assert_eq!(migrated.bump, session.bump);
+    // Appended fields take their defaults, the agent was never recorded on a legacy session
+    assert_eq!(migrated.agent, Pubkey::default());
+    assert_eq!(migrated.total_paid, 0);
+    assert_eq!(migrated.allowed_owner, None);
+    assert!(migrated.allowed_compute_nodes.is_empty());
+    assert!(migrated.accepting_contributions);
+    assert!(!migrated.paused);
+
+    let err = fixt
+        .migrate_account(&payer, &session_pda)
+        .expect_err("A migrated session is not in the legacy layout anymore");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "AccountAlreadyMigrated"
+    ));
+}
+
+#[test]
+fn test_claim_task_restricted_to_allowed_compute_nodes() {
+    for (allow_compute_node, expect_allowed) in [(true, true), (false, false)] {
+        let mut fixt = TestFixture::new()
+            .with_initialize_network()
+            .with_register_public_node()
+            .with_claim_public_node()
+            .with_validate_public_node(true)
+            .with_create_agent()
+            .with_validated_agent(0)
+            .with_create_session(false);
+
+        let compute_node = fixt.public_node.insecure_clone();
+        let allowed_node = if allow_compute_node {
+            compute_node.pubkey()
+        } else {
+            Pubkey::new_unique()
+        };
+        let task_slot_id = fixt.find_session_task_slot_id(0);
+        let result = fixt.set_session_with(
+            &fixt.agent_owner.insecure_clone(),
+            0,
+            DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+            10,
+            0,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            compute_node.pubkey(),
+            TaskType::Completion(0),
+            |_, args| {
+                args.allowed_compute_nodes = Some(vec![allowed_node]);
+            },
+        );
+        assert!(result.is_ok(), "Failed to set session: {:#?}", result.err());
+
+        let network_config_pda = fixt.find_network_config_pda().0;
+        let session = fixt.get_session(&network_config_pda, 0);
+        assert_eq!(session.allowed_compute_nodes, vec![allowed_node]);
+
+        // The compute node is in the approved pool either way
+        let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+        if expect_allowed {
+            assert!(result.is_ok(), "Failed to claim task: {:#?}", result.err());
+        } else {
+            let err = result.expect_err("Node outside the session's allowed list claimed");
+            assert!(Helpers::has_error_code(
+                &err.meta.logs,
+                "ComputeNodeNotAllowed"
+            ));
+        }
+    }
+}
+
+/// Session and task state rebuilt from emitted events alone.
+#[derive(Default)]
+struct ReplayedSession {
+    owner: Pubkey,
+    status: Option<SessionStatus>,
+    specification_cid: String,
+    max_iterations: u64,
+    current_iteration: u64,
+    total_shares: u64,
+    locked_for_tasks: u64,
+    total_paid: u64,
+    task_index_start: u64,
+    task_index_end: u64,
+    compute_node: Option<Pubkey>,
+    task_status: Option<TaskStatus>,
+    task_index: u64,
+    max_task_cost: u64,
+    max_call_count: u64,
+    call_count: u64,
+    pending_result: Option<(String, String)>,
+    input_cid: Option<String>,
+    output_cid: Option<String>,
+    validators: Vec<Pubkey>,
+}
+
+impl ReplayedSession {
+    /// Fold one transaction's events, in the order the program emits them.
+    fn apply(&mut self, logs: &[String]) {
+        for event in Helpers::decode_events::<SessionSet>(logs, "SessionSet") {
+            self.owner = event.owner;
+            self.status = Some(SessionStatus::Active);
+            self.specification_cid = event.specification_cid;
+            self.max_iterations = event.max_iterations;
+            self.current_iteration = 0;
+            self.total_shares = event.total_shares;
+            self.locked_for_tasks = 0;
+            self.total_paid = 0;
+            self.task_index_start = event.task_index_start;
+            self.compute_node = Some(event.compute_node);
+            self.task_status = Some(TaskStatus::Ready);
+        }
+        for event in Helpers::decode_events::<ContributionMade>(logs, "ContributionMade") {
+            self.total_shares = event.total_shares;
+        }
+        for event in Helpers::decode_events::<TaskClaimed>(logs, "TaskClaimed") {
+            self.locked_for_tasks += event.max_task_cost;
+            self.max_task_cost = event.max_task_cost;
+            self.max_call_count = event.max_call_count;
+            self.task_index = event.task_index;
+            self.task_status = Some(TaskStatus::Processing);
+        }
+        for event in Helpers::decode_events::<ValidatorsAssigned>(logs, "ValidatorsAssigned") {
+            self.validators = event.validators;
+        }
+        for event in Helpers::decode_events::<TaskResultSubmitted>(logs, "TaskResultSubmitted") {
+            self.call_count = event.call_count;
+            self.pending_result = Some((event.input_cid, event.output_cid));
+            self.task_status = Some(TaskStatus::AwaitingValidation);
+        }
+        for _ in Helpers::decode_events::<SessionCompleted>(logs, "SessionCompleted") {
+            self.status = Some(SessionStatus::Completed);
+            self.task_index_end = self.task_index;
+        }
+        for event in
+            Helpers::decode_events::<TaskValidationSubmitted>(logs, "TaskValidationSubmitted")
+        {
+            if let Some((input_cid, output_cid)) = self.pending_result.take() {
+                self.input_cid = Some(input_cid);
+                self.output_cid = Some(output_cid);
+            }
+            self.current_iteration = event.current_iteration;
+            self.locked_for_tasks = event.locked_for_tasks;
+            self.total_paid = event.total_paid_this_session;
+            self.validators.clear();
+            self.task_status = Some(if self.status == Some(SessionStatus::Completed) {
+                TaskStatus::Ready
+            } else {
+                TaskStatus::Pending
+            });
+        }
+    }
+}
+
+#[test]
+fn test_session_state_reconstructed_from_events() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false);
+
+    let session_slot_id = 0;
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut replay = ReplayedSession::default();
+
+    let steps: Vec<(&str, TransactionResult)> = vec![
+        (
+            "set session",
+            fixt.set_session(
+                &owner,
+                session_slot_id,
+                DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+                2,
+                0,
+                task_slot_id,
+                DEFAULT_INITIAL_DEPOSIT,
+                compute_node.pubkey(),
+                TaskType::Completion(0),
+            ),
+        ),
+        (
+            "contribute",
+            fixt.contribute_to_session(&contributor, session_slot_id, DEFAULT_CONTRIBUTION_AMOUNT),
+        ),
+        (
+            "claim first task",
+            fixt.claim_task(
+                &compute_node,
+                session_slot_id,
+                task_slot_id,
+                100_000_000,
+                10,
+            ),
+        ),
+        (
+            "submit first result",
+            fixt.submit_task_result(
+                &compute_node,
+                session_slot_id,
+                task_slot_id,
+                "QmReplayInput1".to_string(),
+                "QmReplayOutput1".to_string(),
+                None,
+                3,
+            ),
+        ),
+        (
+            "validate first result",
+            fixt.submit_public_task_validation(
+                &validator,
+                session_slot_id,
+                task_slot_id,
+                &compute_node.pubkey(),
+                40_000_000,
+                true,
+                false,
+            ),
+        ),
+    ];
+    for (step, result) in steps {
+        match result {
+            Ok(metadata) => replay.apply(&metadata.logs),
+            Err(e) => panic!("Failed to {step}: {:#?}", e),
+        }
+    }
+
+    // Mid-run the replay already matches the accounts
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    assert_eq!(replay.status, Some(session.status));
+    assert_eq!(replay.current_iteration, session.current_iteration);
+    assert_eq!(replay.total_paid, session.total_paid);
+    assert_eq!(
+        replay.task_status,
+        Some(fixt.get_task(&network_config_pda, task_slot_id).status)
+    );
+
+    fixt.svm.expire_blockhash();
+    let steps: Vec<(&str, TransactionResult)> = vec![
+        (
+            "claim second task",
+            fixt.claim_task(&compute_node, session_slot_id, task_slot_id, 200_000_000, 5),
+        ),
+        (
+            "submit second result",
+            fixt.submit_task_result(
+                &compute_node,
+                session_slot_id,
+                task_slot_id,
+                "QmReplayInput2".to_string(),
+                "QmReplayOutput2".to_string(),
+                None,
+                2,
+            ),
+        ),
+        (
+            "validate second result",
+            fixt.submit_public_task_validation(
+                &validator,
+                session_slot_id,
+                task_slot_id,
+                &compute_node.pubkey(),
+                150_000_000,
+                true,
+                false,
+            ),
+        ),
+    ];
+    for (step, result) in steps {
+        match result {
+            Ok(metadata) => replay.apply(&metadata.logs),
+            Err(e) => panic!("Failed to {step}: {:#?}", e),
+        }
+    }
+
+    // The second iteration hits max_iterations and completes the session
+    let session = fixt.get_session(&network_config_pda, session_slot_id);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(session.status, SessionStatus::Completed);
+
+    assert_eq!(replay.owner, session.owner);
+    assert_eq!(replay.status, Some(session.status));
+    assert_eq!(replay.specification_cid, session.specification_cid);
+    assert_eq!(replay.max_iterations, session.max_iterations);
+    assert_eq!(replay.current_iteration, session.current_iteration);
+    assert_eq!(replay.total_shares, session.total_shares);
+    assert_eq!(replay.locked_for_tasks, session.locked_for_tasks);
+    assert_eq!(replay.total_paid, session.total_paid);
+    assert_eq!(replay.task_index_start, session.task_index_start);
+    assert_eq!(replay.task_index_end, session.task_index_end);
+
+    assert_eq!(replay.compute_node, task.compute_node);
+    assert_eq!(replay.task_status, Some(task.status));
+    assert_eq!(replay.task_index, task.task_index);
+    assert_eq!(replay.max_task_cost, task.max_task_cost);
+    assert_eq!(replay.max_call_count, task.max_call_count);
+    assert_eq!(replay.call_count, task.call_count);
+    assert_eq!(replay.input_cid, task.input_cid);
+    assert_eq!(replay.output_cid, task.output_cid);
+    assert_eq!(
+        replay.validators,
+        task.validations
+            .iter()
+            .map(|v| v.pubkey)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_initialize_network_many_tasks_needs_raised_compute_limit() {
+    let mut fixt = TestFixture::new();
+    let authority = fixt.authority.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    // The most task accounts a legacy transaction can carry next to a compute budget request
+    let allocate_tasks = 26;
+    let remaining_accounts =
+        fixt.create_remaining_accounts_for_initialize(&network_config_pda, allocate_tasks);
+
+    // Without a compute budget request the instruction gets the default 200k units
+    let mut builder = InitializeNetworkBuilder::new();
+    builder
+        .authority(authority.pubkey())
+        .network_config(network_config_pda)
+        .cid_config(DEFAULT_CID_CONFIG.to_string())
+        .allocate_tasks(allocate_tasks)
+        .approved_code_measurements(DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec())
+        .required_validations(DEFAULT_REQUIRED_VALIDATIONS)
+        .add_remaining_accounts(&remaining_accounts);
+    let err = fixt
+        .svm
+        .send_tx(&[builder.instruction()], &authority.pubkey(), &[&authority])
+        .expect_err("Allocating this many tasks should exhaust the default compute budget");
+    assert!(err
+        .meta
+        .logs
+        .iter()
+        .any(|log| log.contains("exceeded CUs meter")));
+
+    // The fixture raises the limit
+    let result = fixt.initialize_network(
+        &authority,
+        &network_config_pda,
+        DEFAULT_CID_CONFIG.to_string(),
+        allocate_tasks,
+        DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
+        DEFAULT_REQUIRED_VALIDATIONS,
+        None,
+        &remaining_accounts,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to initialize with a raised compute limit: {:#?}",
+        result.err()
+    );
+    assert_eq!(fixt.get_network_config().task_count, allocate_tasks);
+    fixt.assert_task_slots_unique();
+}
+
+#[test]
+fn test_extend_tasks_in_batches() {
+    let allocate_tasks = 50;
+    let fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_extended_tasks(allocate_tasks);
+
+    let network_config = fixt.get_network_config();
+    assert_eq!(
+        network_config.task_count,
+        DEFAULT_ALLOCATE_TASKS + allocate_tasks
+    );
+    fixt.assert_task_slots_unique();
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let last_task = fixt.get_task(&network_config_pda, network_config.task_count - 1);
+    assert_eq!(last_task.status, TaskStatus::Ready);
+    assert_eq!(last_task.session_slot_id, None);
+    assert_eq!(last_task.chain_proof, network_config.genesis_hash);
+}
+
+#[test]
+fn test_extend_tasks_requires_next_slots_in_order() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+    let authority = fixt.authority.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+
+    // Skipping ahead one slot would leave a gap in the task range
+    let task_pdas = fixt.create_task_pdas_from(&network_config_pda, DEFAULT_ALLOCATE_TASKS + 1, 2);
+    let mut builder = ExtendTasksBuilder::new();
+    builder
+        .authority(authority.pubkey())
+        .network_config(network_config_pda)
+        .count(2)
+        .add_remaining_accounts(&task_pdas);
+    let err = fixt
+        .svm
+        .send_tx(&[builder.instruction()], &authority.pubkey(), &[&authority])
+        .expect_err("Task PDAs must start at task_count");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidPDAAccount"));
+
+    let outsider = fixt.create_keypair();
+    let err = fixt
+        .extend_tasks(&outsider, 2)
+        .expect_err("Only the authority extends its network");
+    assert!(Helpers::has_error_code(&err.meta.logs, "ConstraintSeeds"));
+    assert_eq!(fixt.get_network_config().task_count, DEFAULT_ALLOCATE_TASKS);
+}
+
+#[test]
+fn test_get_node_summary_after_activation() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node();
+
+    let payer = fixt.payer.insecure_clone();
+    let authority = fixt.authority.insecure_clone();
+    let public_node = fixt.public_node.pubkey();
+
+    let metadata = fixt
+        .get_node_summary(&payer, &public_node)
+        .expect("Failed to query node summary");
+    let summary: NodeSummary = Helpers::decode_event(&metadata.logs, "NodeSummary")
+        .expect("NodeSummary event not emitted");
+    assert_eq!(summary.node, public_node);
+    assert_eq!(summary.node_type, NodeType::Public);
+    assert!(
+        !summary.approved,
+        "Claimed node isn't approved until activated"
+    );
+
+    let result = fixt.activate_node(&authority, &public_node);
+    assert!(result.is_ok(), "Failed to activate public node");
+    fixt.svm.expire_blockhash();
+
+    let metadata = fixt
+        .get_node_summary(&payer, &public_node)
+        .expect("Failed to query node summary");
+    let summary: NodeSummary = Helpers::decode_event(&metadata.logs, "NodeSummary")
+        .expect("NodeSummary event not emitted");
+    let node_info = fixt.get_node_info(&public_node);
+    assert_eq!(summary.node, public_node);
+    assert_eq!(summary.node_type, NodeType::Public);
+    assert_eq!(summary.status, NodeStatus::Active);
+    assert_eq!(summary.status, node_info.status);
+    assert_eq!(summary.total_tasks_completed, 0);
+    assert_eq!(summary.total_earned, node_info.total_earned);
+    assert!(summary.approved);
+}
+
+#[test]
+fn test_single_required_validation_pays_on_first_approval() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    assert_eq!(fixt.get_network_config().required_validations, 1);
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let payment_amount = 50_000_000;
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    // A single validator is assigned, its vote alone decides the task
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::AwaitingValidation);
+    assert_eq!(task.validations.len(), 1);
+    assert_eq!(task.validations[0].pubkey, validator_node.pubkey());
+
+    let (compute_node_info_pda, _) = fixt.find_node_info_pda(&compute_node.pubkey());
+    let (node_treasury_pda, _) = fixt.find_node_treasury_pda(
+        &compute_node_info_pda,
+        fixt.get_node_info(&compute_node.pubkey())
+            .registration_epoch,
+    );
+    let treasury_before = fixt.svm.get_lamports(&node_treasury_pda);
+
+    let metadata = fixt
+        .submit_public_task_validation(
+            &validator_node,
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            payment_amount,
+            true,
+            false,
+        )
+        .expect("Failed to submit the only validation");
+    let event: TaskValidationSubmitted =
+        Helpers::decode_event(&metadata.logs, "TaskValidationSubmitted")
+            .expect("TaskValidationSubmitted not emitted on the first approval");
+    assert_eq!(event.payment_amount, payment_amount);
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    let session = fixt.get_session(&network_config_pda, 0);
+    let compute_node_info = fixt.get_node_info(&compute_node.pubkey());
+    assert_eq!(task.status, TaskStatus::Pending);
+    assert!(task.validations.is_empty());
+    assert_eq!(session.locked_for_tasks, 0);
+    assert_eq!(session.total_paid, payment_amount);
+    assert_eq!(session.current_iteration, 1);
+    assert_eq!(compute_node_info.total_tasks_completed, 1);
+    assert_eq!(
+        fixt.svm.get_lamports(&node_treasury_pda) - treasury_before,
+        compute_node_info.total_earned
+    );
+
+    fixt.svm.expire_blockhash();
+    let err = fixt
+        .submit_public_task_validation(
+            &validator_node,
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            payment_amount,
+            true,
+            false,
+        )
+        .expect_err("The task is already settled");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidTaskStatus"));
+}
+
+#[test]
+fn test_validator_fee_weighted_by_agreed_validations() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let second_validator_owner = fixt.create_keypair();
+    let second_validator = fixt.create_keypair();
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let result = fixt.register_node(
+        &second_validator_owner,
+        &second_validator.pubkey(),
+        NodeType::Public,
+    );
+    assert!(result.is_ok(), "Failed to register second validator");
+    let result = fixt.claim_compute_node(&second_validator, DEFAULT_NODE_INFO_CID.to_string());
+    assert!(result.is_ok(), "Failed to claim second validator");
+    let result = fixt.validate_public_node(&confidential_node, &second_validator.pubkey(), true);
+    assert!(result.is_ok(), "Failed to validate second validator");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut network_config = fixt.get_network_config();
+    network_config.required_validations = 2;
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let validator_share_bps = 1_000;
+    let authority = fixt.authority.insecure_clone();
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.validator_share_bps = Some(validator_share_bps);
+    });
+    assert!(result.is_ok(), "Failed to set validator share");
+
+    // The original validator matched 4 outcomes in 9 votes, the second one voted 20 times
+    // without ever matching one
+    let veteran = fixt.validator_node.insecure_clone();
+    for (validator, total_validations, agreed_validations) in
+        [(veteran.pubkey(), 9, 4), (second_validator.pubkey(), 20, 0)]
+    {
+        let node_info_pda = fixt.find_node_info_pda(&validator).0;
+        let mut node_info = fixt.get_node_info(&validator);
+        node_info.total_validations = total_validations;
+        node_info.agreed_validations = agreed_validations;
+        let data = borsh::to_vec(&node_info).unwrap();
+        let mut account = fixt.svm.get_account(&node_info_pda).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        fixt.svm.set_account(node_info_pda, account).unwrap();
+    }
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let compute_node = fixt.public_node.insecure_clone();
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let payment_amount: u64 = 100_000_000;
+    let result = fixt.submit_public_task_validation(
+        &veteran,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        payment_amount,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed first approval: {:#?}", result);
+    let veteran_node_info = fixt.get_node_info(&veteran.pubkey());
+    assert_eq!(veteran_node_info.total_validations, 10);
+    // Not credited until the outcome is settled
+    assert_eq!(veteran_node_info.agreed_validations, 4);
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    let mut validator_accounts = Vec::new();
+    for validation in &task.validations {
+        let node_info = fixt.get_node_info(&validation.pubkey);
+        // Writable so the agreement can be credited
+        validator_accounts.push(AccountMeta::new(
+            fixt.find_node_info_pda(&validation.pubkey).0,
+            false,
+        ));
+        validator_accounts.push(AccountMeta::new(node_info.node_treasury, false));
+    }
+    let veteran_treasury = fixt.get_node_info(&veteran.pubkey()).node_treasury;
+    let newcomer_treasury = fixt.get_node_info(&second_validator.pubkey()).node_treasury;
+    let veteran_lamports = fixt.svm.get_lamports(&veteran_treasury);
+    let newcomer_lamports = fixt.svm.get_lamports(&newcomer_treasury);
+
+    let result = fixt.submit_public_task_validation_with(
+        &second_validator,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        payment_amount,
+        true,
+        false,
+        |builder| {
+            builder.add_remaining_accounts(&validator_accounts);
+        },
+    );
+    assert!(result.is_ok(), "Failed settling approval: {:#?}", result);
+    let newcomer_node_info = fixt.get_node_info(&second_validator.pubkey());
+    assert_eq!(newcomer_node_info.total_validations, 21);
+    assert_eq!(newcomer_node_info.agreed_validations, 1);
+    assert_eq!(fixt.get_node_info(&veteran.pubkey()).agreed_validations, 5);
+
+    // Weights are agreed_validations + 1 once both approvals are credited: 6 for the
+    // veteran, 2 for the newcomer whatever its volume
+    let validator_fee = payment_amount * validator_share_bps as u64 / 10_000;
+    let veteran_paid = fixt.svm.get_lamports(&veteran_treasury) - veteran_lamports;
+    let newcomer_paid = fixt.svm.get_lamports(&newcomer_treasury) - newcomer_lamports;
+    assert_eq!(veteran_paid, validator_fee * 6 / 8);
+    assert_eq!(newcomer_paid, validator_fee * 2 / 8);
+    assert!(veteran_paid > newcomer_paid);
+
+    let compute_node_info = fixt.get_node_info(&compute_node.pubkey());
+    assert_eq!(
+        compute_node_info.total_earned,
+        payment_amount - veteran_paid - newcomer_paid
+    );
+}
+
+#[test]
+fn test_verify_genesis_matches_fresh_network() {
+    let mut fixt = TestFixture::new().with_initialize_network();
+    let payer = fixt.payer.insecure_clone();
+
+    let metadata = fixt
+        .verify_genesis(&payer)
+        .expect("Failed to verify genesis");
+    let verified: GenesisVerified = Helpers::decode_event(&metadata.logs, "GenesisVerified")
+        .expect("GenesisVerified event not emitted");
+    assert!(verified.matches);
+    assert_eq!(verified.genesis_nonce, None);
+    assert_eq!(verified.computed_hash, compute_genesis_hash());
+    assert_eq!(
+        verified.genesis_hash,
+        fixt.get_network_config().genesis_hash
+    );
+
+    let genesis_nonce = [7u8; 32];
+    let mut fixt = TestFixture::new();
+    let authority = fixt.authority.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let result = fixt.initialize_network(
+        &authority,
+        &network_config_pda,
+        DEFAULT_CID_CONFIG.to_string(),
+        0,
+        DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
+        DEFAULT_REQUIRED_VALIDATIONS,
+        Some(genesis_nonce),
+        &[],
+    );
+    assert!(result.is_ok(), "Failed to initialize network");
+
+    let payer = fixt.payer.insecure_clone();
+    let metadata = fixt
+        .verify_genesis(&payer)
+        .expect("Failed to verify genesis");
+    let verified: GenesisVerified = Helpers::decode_event(&metadata.logs, "GenesisVerified")
+        .expect("GenesisVerified event not emitted");
+    assert!(verified.matches);
+    assert_eq!(verified.genesis_nonce, Some(genesis_nonce));
+    assert_eq!(
+        verified.computed_hash,
+        compute_genesis_hash_with_nonce(&authority.pubkey(), genesis_nonce)
+    );
+}
+
+#[test]
+fn test_task_checkpoint_resumed_by_final_result() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let compute_node = fixt.public_node.insecure_clone();
+    let checkpoint_cid = "QmTestCheckpoint123456789".to_string();
+
+    let err = fixt
+        .submit_task_checkpoint(&compute_node, 0, checkpoint_cid.clone())
+        .expect_err("Only a claimed task can be checkpointed");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidTaskStatus"));
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let outsider = fixt.validator_node.insecure_clone();
+    let err = fixt
+        .submit_task_checkpoint(&outsider, 0, checkpoint_cid.clone())
+        .expect_err("Only the claiming node can checkpoint");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidComputeNodePubkey"
+    ));
+
+    let metadata = fixt
+        .submit_task_checkpoint(&compute_node, 0, checkpoint_cid.clone())
+        .expect("Failed to checkpoint task");
+    let event: TaskCheckpointed = Helpers::decode_event(&metadata.logs, "TaskCheckpointed")
+        .expect("TaskCheckpointed event not emitted");
+    assert_eq!(event.task_slot_id, task_slot_id);
+    assert_eq!(event.compute_node, compute_node.pubkey());
+    assert_eq!(event.checkpoint_cid, checkpoint_cid);
+
+    // The checkpoint doesn't finalize anything, the execution is still running
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::Processing);
+    assert_eq!(task.checkpoint_cid, Some(checkpoint_cid.clone()));
+    assert_eq!(task.pending_output_cid, None);
+
+    // After a restart the node reads its checkpoint back and finishes from it
+    let resumed_from = fixt
+        .get_task(&network_config_pda, task_slot_id)
+        .checkpoint_cid
+        .expect("Checkpoint lost");
+    let metadata = fixt
+        .submit_task_result(
+            &compute_node,
+            0,
+            task_slot_id,
+            "QmTestInput123456789".to_string(),
+            "QmTestOutput123456789".to_string(),
+            None,
+            1,
+        )
+        .expect("Failed to submit task result");
+    let event: TaskResultSubmitted = Helpers::decode_event(&metadata.logs, "TaskResultSubmitted")
+        .expect("TaskResultSubmitted event not emitted");
+    assert_eq!(event.checkpoint_cid, Some(resumed_from));
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::AwaitingValidation);
+    assert_eq!(task.checkpoint_cid, None);
+}
+
+#[test]
+fn test_chain_proof_independent_of_validator_order() {
+    fn validated_task(reverse_votes: bool, tamper: bool) -> Result<Task, Vec<String>> {
+        let mut fixt = TestFixture::new()
+            .with_initialize_network()
+            .with_register_confidential_node()
+            .with_claim_confidential_node()
+            .with_register_public_node()
+            .with_claim_public_node()
+            .with_register_validator_node()
+            .with_claim_validator_node()
+            .with_validate_public_node(true)
+            .with_validate_validator_node(true)
+            .with_create_agent()
+            .with_validated_agent(0)
+            .with_create_session(false)
+            .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+        let second_validator_owner = fixt.create_keypair();
+        let second_validator = fixt.create_keypair();
+        let confidential_node = fixt.confidential_node.insecure_clone();
+        let result = fixt.register_node(
+            &second_validator_owner,
+            &second_validator.pubkey(),
+            NodeType::Public,
+        );
+        assert!(result.is_ok(), "Failed to register second validator");
+        let result = fixt.claim_compute_node(&second_validator, DEFAULT_NODE_INFO_CID.to_string());
+        assert!(result.is_ok(), "Failed to claim second validator");
+        let result =
+            fixt.validate_public_node(&confidential_node, &second_validator.pubkey(), true);
+        assert!(result.is_ok(), "Failed to validate second validator");
+
+        let network_config_pda = fixt.find_network_config_pda().0;
+        let mut network_config = fixt.get_network_config();
+        network_config.required_validations = 2;
+        let data = borsh::to_vec(&network_config).unwrap();
+        let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        fixt.svm.set_account(network_config_pda, account).unwrap();
+
+        let task_slot_id = fixt.find_session_task_slot_id(0);
+        let compute_node = fixt.public_node.insecure_clone();
+        let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+        assert!(result.is_ok(), "Failed to claim task");
+        let result = fixt.submit_task_result(
+            &compute_node,
+            0,
+            task_slot_id,
+            "QmTestInput123456789".to_string(),
+            "QmTestOutput123456789".to_string(),
+            None,
+            1,
+        );
+        assert!(result.is_ok(), "Failed to submit task result");
+
+        if tamper {
+            // Stand-in for a resubmission slipping in between the votes
+            let (task_pda, _) = fixt.find_task_pda(&network_config_pda, task_slot_id);
+            let mut task = fixt.get_task(&network_config_pda, task_slot_id);
+            task.pending_output_cid = Some("QmTestOutput987654321".to_string());
+            let data = borsh::to_vec(&task).unwrap();
+            let mut account = fixt.svm.get_account(&task_pda).unwrap();
+            account.data[..data.len()].copy_from_slice(&data);
+            fixt.svm.set_account(task_pda, account).unwrap();
+        }
+
+        let mut validators = vec![fixt.validator_node.insecure_clone(), second_validator];
+        if reverse_votes {
+            validators.reverse();
+        }
+        for validator in &validators {
+            fixt.submit_public_task_validation(
+                validator,
+                0,
+                task_slot_id,
+                &compute_node.pubkey(),
+                50_000_000,
+                true,
+                false,
+            )
+            .map_err(|err| err.meta.logs)?;
+        }
+
+        Ok(fixt.get_task(&network_config_pda, task_slot_id))
+    }
+
+    let in_order = validated_task(false, false).expect("Failed approvals in order");
+    let reversed = validated_task(true, false).expect("Failed approvals in reverse order");
+    assert_eq!(in_order.status, TaskStatus::Pending);
+    assert_eq!(in_order.chain_proof, reversed.chain_proof);
+    assert_eq!(in_order.input_cid, reversed.input_cid);
+    assert_eq!(in_order.output_cid, reversed.output_cid);
+    assert_eq!(
+        in_order.output_cid,
+        Some("QmTestOutput123456789".to_string())
+    );
+    assert_eq!(in_order.pending_result_digest, [0; 32]);
+
+    let logs = validated_task(false, true).expect_err("A changed result must not be committed");
+    assert!(Helpers::has_error_code(&logs, "PendingResultChanged"));
+}
+
+#[test]
+fn test_authority_invalidates_task_of_rejected_node() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let authority = fixt.authority.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let max_task_cost = 100_000_000;
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, max_task_cost, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    assert_eq!(
+        fixt.get_session(&network_config_pda, 0).locked_for_tasks,
+        max_task_cost
+    );
+
+    let err = fixt
+        .authority_invalidate_task(&authority, 0, &compute_node.pubkey())
+        .expect_err("The node hasn't been rejected");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidNodeStatus"));
+
+    let result = fixt.authority_reject_node(&authority, &compute_node.pubkey());
+    assert!(result.is_ok(), "Failed to reject compute node");
+
+    let outsider = fixt.create_keypair();
+    let err = fixt
+        .authority_invalidate_task(&outsider, 0, &compute_node.pubkey())
+        .expect_err("Only the authority can invalidate a task");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidAuthority"));
+
+    let metadata = fixt
+        .authority_invalidate_task(&authority, 0, &compute_node.pubkey())
+        .expect("Failed to invalidate task");
+    let event: TaskInvalidated = Helpers::decode_event(&metadata.logs, "TaskInvalidated")
+        .expect("TaskInvalidated event not emitted");
+    assert_eq!(event.task_slot_id, task_slot_id);
+    assert_eq!(event.compute_node, compute_node.pubkey());
+    assert_eq!(event.released_amount, max_task_cost);
+    assert_eq!(event.locked_for_tasks, 0);
+
+    let session = fixt.get_session(&network_config_pda, 0);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(session.locked_for_tasks, 0);
+    assert_eq!(task.status, TaskStatus::Ready);
+    assert!(task.validations.is_empty());
+    assert_eq!(task.pending_output_cid, None);
+
+    fixt.svm.expire_blockhash();
+    let err = fixt
+        .authority_invalidate_task(&authority, 0, &compute_node.pubkey())
+        .expect_err("The task is no longer in flight");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidTaskStatus"));
+}
+
+#[test]
+fn test_validation_proof_mismatch_logs_diagnostics_when_enabled() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(true);
+
+    let validator_owner = fixt.create_keypair();
+    let validator = fixt.create_keypair();
+    let validator_tee = fixt.create_keypair();
+    let result = fixt.register_node(
+        &validator_owner,
+        &validator.pubkey(),
+        NodeType::Confidential,
+    );
+    assert!(result.is_ok(), "Failed to register validator node");
+    let result =
+        fixt.claim_confidential_node(&validator, DEFAULT_CODE_MEASUREMENT, validator_tee.pubkey());
+    assert!(result.is_ok(), "Failed to claim validator node");
+
+    let task_type = TaskType::Completion(0);
+    let mut fixt = fixt.with_set_session_for_confidential(0, task_type.clone());
+
+    let session_slot_id = 0;
+    let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+    let compute_node = fixt.confidential_node.insecure_clone();
+    let authority = fixt.authority.insecure_clone();
+
+    let result = fixt.claim_task(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        1_000_000_000,
+        10,
+    );
+    assert!(result.is_ok(), "Failed to claim task");
+    let input_cid = "QmTestInput123456789".to_string();
+    let output_cid = "QmTestOutput123456789".to_string();
+    let result = fixt.submit_task_result(
+        &compute_node,
+        session_slot_id,
+        task_slot_id,
+        input_cid.clone(),
+        output_cid.clone(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    let expected_proof = Helpers::compute_validation_proof(
+        session_slot_id,
+        task_slot_id,
+        1,
+        &task_type,
+        &input_cid,
+        &output_cid,
+    );
+    let wrong_proof = [0xab; 32];
+    let ed25519_ix = Helpers::create_ed25519_instruction_to_submit_task_validation(
+        session_slot_id,
+        task_slot_id,
+        500_000_000,
+        wrong_proof,
+        true,
+        false,
+        0,
+        &validator_tee,
+    );
+    let to_hex = |bytes: &[u8]| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+    let diagnostic = format!(
+        "Validation proof mismatch: expected {} received {}",
+        to_hex(&expected_proof),
+        to_hex(&wrong_proof)
+    );
+
+    // Off by default, the failure carries no proof details
+    let err = fixt
+        .submit_confidential_task_validation(
+            &validator,
+            session_slot_id,
+            task_slot_id,
+            &compute_node.pubkey(),
+            &ed25519_ix,
+        )
+        .expect_err("A mismatched proof must fail");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidTeeSignature"
+    ));
+    assert!(!err
+        .meta
+        .logs
+        .iter()
+        .any(|log| log.contains("Validation proof mismatch")));
+
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.debug_validation_logs = Some(true);
+    });
+    assert!(result.is_ok(), "Failed to enable validation debug logs");
+    fixt.svm.expire_blockhash();
+
+    let err = fixt
+        .submit_confidential_task_validation(
+            &validator,
+            session_slot_id,
+            task_slot_id,
+            &compute_node.pubkey(),
+            &ed25519_ix,
+        )
+        .expect_err("A mismatched proof must fail");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidTeeSignature"
+    ));
+    assert!(
+        err.meta.logs.iter().any(|log| log.contains(&diagnostic)),
+        "Missing proof diagnostics in logs: {:#?}",
+        err.meta.logs
+    );
+}
+
+#[test]
+fn test_claim_task_blocked_at_node_capacity() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_create_session(false)
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let node_owner = fixt.public_node_owner.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let first_task_slot_id = fixt.find_session_task_slot_id(0);
+    let second_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    let outsider = fixt.create_keypair();
+    let err = fixt
+        .set_node_capacity(&outsider, &compute_node.pubkey(), 1)
+        .expect_err("Only the node owner sets its capacity");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidNodeOwner"));
+
+    let metadata = fixt
+        .set_node_capacity(&node_owner, &compute_node.pubkey(), 1)
+        .expect("Failed to set node capacity");
+    let event: NodeCapacitySet = Helpers::decode_event(&metadata.logs, "NodeCapacitySet")
+        .expect("NodeCapacitySet event not emitted");
+    assert_eq!(event.node, compute_node.pubkey());
+    assert_eq!(event.max_concurrent_tasks, 1);
+    assert_eq!(event.current_tasks, 0);
+
+    let result = fixt.claim_task(&compute_node, 0, first_task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim the first task");
+    let node_info = fixt.get_node_info(&compute_node.pubkey());
+    assert_eq!(node_info.current_tasks, 1);
+
+    let err = fixt
+        .claim_task(&compute_node, 1, second_task_slot_id, 100_000_000, 10)
+        .expect_err("A node at capacity can't claim another task");
+    assert!(Helpers::has_error_code(&err.meta.logs, "NodeAtCapacity"));
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        first_task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        0,
+        first_task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate the first task");
+    assert_eq!(fixt.get_node_info(&compute_node.pubkey()).current_tasks, 0);
+
+    fixt.svm.expire_blockhash();
+    let result = fixt.claim_task(&compute_node, 1, second_task_slot_id, 100_000_000, 10);
+    assert!(
+        result.is_ok(),
+        "Failed to claim once capacity freed up: {:#?}",
+        result.err()
+    );
+    assert_eq!(fixt.get_node_info(&compute_node.pubkey()).current_tasks, 1);
+}
+
+#[test]
+fn test_node_capacity_released_on_rejection() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let node_owner = fixt.public_node_owner.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let result = fixt.set_node_capacity(&node_owner, &compute_node.pubkey(), 1);
+    assert!(result.is_ok(), "Failed to set node capacity");
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    assert_eq!(fixt.get_node_info(&compute_node.pubkey()).current_tasks, 1);
+
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        0,
+        false,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to reject the result");
+    assert_eq!(fixt.get_node_info(&compute_node.pubkey()).current_tasks, 0);
+}
+
+#[test]
+fn test_session_exposure_matches_locked_for_tasks() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_create_session(false)
+        .with_set_session_using_public_compute(1, 0, TaskType::Completion(0));
+
+    let payer = fixt.payer.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let first_task_slot_id = fixt.find_session_task_slot_id(0);
+    let second_task_slot_id = fixt.find_session_task_slot_id(1);
+
+    let result = fixt.claim_task(&compute_node, 0, first_task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim the first task");
+    let result = fixt.claim_task(&compute_node, 1, second_task_slot_id, 60_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim the second task");
+
+    for (session_slot_id, task_slot_id, max_task_cost) in [
+        (0, first_task_slot_id, 100_000_000),
+        (1, second_task_slot_id, 60_000_000),
+    ] {
+        let metadata = fixt
+            .get_session_exposure(&payer, session_slot_id, &[task_slot_id])
+            .expect("Failed to read session exposure");
+        let event: SessionExposure = Helpers::decode_event(&metadata.logs, "SessionExposure")
+            .expect("SessionExposure event not emitted");
+        let session = fixt.get_session(&network_config_pda, session_slot_id);
+        assert_eq!(event.session_slot_id, session_slot_id);
+        assert_eq!(event.tasks_locked, max_task_cost);
+        assert_eq!(event.locked_for_tasks, session.locked_for_tasks);
+        assert_eq!(event.task_count, 1);
+        assert!(event.balanced);
+    }
+
+    let err = fixt
+        .get_session_exposure(&payer, 0, &[first_task_slot_id, second_task_slot_id])
+        .expect_err("A task linked to another session is rejected");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidSession"));
+
+    let err = fixt
+        .get_session_exposure(&payer, 0, &[first_task_slot_id, first_task_slot_id])
+        .expect_err("A repeated task is rejected");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "DuplicateTaskAccount"
+    ));
+
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        first_task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        0,
+        first_task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate the first task");
+
+    fixt.svm.expire_blockhash();
+    let metadata = fixt
+        .get_session_exposure(&payer, 0, &[first_task_slot_id])
+        .expect("Failed to read session exposure");
+    let event: SessionExposure = Helpers::decode_event(&metadata.logs, "SessionExposure")
+        .expect("SessionExposure event not emitted");
+    assert_eq!(event.tasks_locked, 0);
+    assert_eq!(event.locked_for_tasks, 0);
+    assert_eq!(event.task_count, 0);
+    assert!(event.balanced);
+}
+
+#[test]
+fn test_claim_task_emits_assignment_receipt() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let compute_node = fixt.public_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let claim_slot = 500;
+    fixt.svm.warp_to_slot(claim_slot);
+
+    let metadata = fixt
+        .claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10)
+        .expect("Failed to claim task");
+    let receipt: TaskAssignmentReceipt =
+        Helpers::decode_event(&metadata.logs, "TaskAssignmentReceipt")
+            .expect("TaskAssignmentReceipt event not emitted");
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+
+    assert_eq!(receipt.network_config, network_config_pda);
+    assert_eq!(receipt.session_slot_id, 0);
+    assert_eq!(receipt.task_slot_id, task_slot_id);
+    assert_eq!(receipt.task_index, task.task_index);
+    assert_eq!(receipt.compute_node, compute_node.pubkey());
+    assert_eq!(receipt.validators, vec![fixt.validator_node.pubkey()]);
+    assert_eq!(
+        receipt.validators,
+        task.validations
+            .iter()
+            .map(|validator| validator.pubkey)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(receipt.max_task_cost, 100_000_000);
+    assert_eq!(receipt.claim_slot, claim_slot);
+    assert_eq!(
+        fixt.get_node_info(&compute_node.pubkey()).last_claim_slot,
+        Some(claim_slot)
+    );
+}
+
+#[test]
+fn test_owned_session_cancelled_by_its_owner() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_contribute_to_session(0, DEFAULT_CONTRIBUTION_AMOUNT);
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let active_sessions = fixt.get_agent(&network_config_pda, 0).active_session_count;
+
+    let err = fixt
+        .cancel_session(&contributor, 0)
+        .expect_err("Only the owner cancels an owned session");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidSessionOwner"
+    ));
+
+    let metadata = fixt
+        .cancel_session(&owner, 0)
+        .expect("Owner failed to cancel the session");
+    let event: SessionCancelled = Helpers::decode_event(&metadata.logs, "SessionCancelled")
+        .expect("SessionCancelled event not emitted");
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(event.session_slot_id, 0);
+    assert_eq!(event.owner, owner.pubkey());
+    assert_eq!(event.total_shares, session.total_shares);
+    assert_eq!(session.status, SessionStatus::Cancelled);
+    assert_eq!(
+        fixt.get_agent(&network_config_pda, 0).active_session_count,
+        active_sessions - 1
+    );
+
+    let shares = fixt
+        .get_contribution(&session_pda, &contributor.pubkey())
+        .shares;
+    let result = fixt.withdraw_from_session(&contributor, 0, shares);
+    assert!(
+        result.is_ok(),
+        "Contributors should withdraw from a cancelled session"
+    );
+}
+
+#[test]
+fn test_unowned_session_cancelled_only_by_contributor_vote() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let creator = fixt.create_keypair();
+    let contributor = fixt.contributor.insecure_clone();
+    let result = fixt.create_session_with(&creator, false, false, |builder| {
+        builder.funding_threshold(DEFAULT_CONTRIBUTION_AMOUNT);
+    });
+    assert!(result.is_ok(), "Failed to create crowdfunded session");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Failed to fund pending session");
+    let result = fixt.set_session(
+        &contributor,
+        0,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        0,
+        fixt.public_node.pubkey(),
+        TaskType::Completion(0),
+    );
+    assert!(
+        result.is_ok(),
+        "Contributor failed to set crowdfunded session"
+    );
+    assert_eq!(
+        fixt.get_session(&network_config_pda, 0).owner,
+        Pubkey::default()
+    );
+
+    for signer in [&creator, &contributor] {
+        let err = fixt
+            .cancel_session(signer, 0)
+            .expect_err("Nobody cancels a governed session on their own");
+        assert!(Helpers::has_error_code(
+            &err.meta.logs,
+            "SessionGovernedByContributors"
+        ));
+    }
+
+    // The contributor holds every share, so the proposal executes on its own vote
+    let result = fixt.propose_session_action(&contributor, 0, SessionAction::Cancel);
+    assert!(
+        result.is_ok(),
+        "Failed to propose cancel: {:#?}",
+        result.err()
+    );
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(session.status, SessionStatus::Cancelled);
+}
+
+#[test]
+fn test_reused_crowdfunded_session_cancelled_by_its_new_owner() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let creator = fixt.create_keypair();
+    let contributor = fixt.contributor.insecure_clone();
+    let owner = fixt.agent_owner.insecure_clone();
+    let result = fixt.create_session_with(&creator, false, false, |builder| {
+        builder.funding_threshold(DEFAULT_CONTRIBUTION_AMOUNT);
+    });
+    assert!(result.is_ok(), "Failed to create crowdfunded session");
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session_pda = fixt.find_session_pda(&network_config_pda, 0).0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Failed to fund pending session");
+    let result = fixt.set_session(
+        &contributor,
+        0,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        0,
+        fixt.public_node.pubkey(),
+        TaskType::Completion(0),
+    );
+    assert!(
+        result.is_ok(),
+        "Contributor failed to set crowdfunded session"
+    );
+    let result = fixt.propose_session_action(&contributor, 0, SessionAction::Cancel);
+    assert!(result.is_ok(), "Failed to cancel: {:#?}", result.err());
+    let shares = fixt
+        .get_contribution(&session_pda, &contributor.pubkey())
+        .shares;
+    let result = fixt.withdraw_from_session(&contributor, 0, shares);
+    assert!(result.is_ok(), "Failed to withdraw: {:#?}", result.err());
+
+    // Stand in for a completed run with every contributor paid out
+    let mut session = fixt.get_session(&network_config_pda, 0);
+    session.status = SessionStatus::Completed;
+    let data = borsh::to_vec(&session).unwrap();
+    let mut account = fixt.svm.get_account(&session_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(session_pda, account).unwrap();
+
+    let result = fixt.set_session(
+        &owner,
+        0,
+        DEFAULT_GOAL_SPECIFICATION_CID.to_string(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_CONTRIBUTION_AMOUNT,
+        fixt.public_node.pubkey(),
+        TaskType::Completion(0),
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to reuse the completed session: {:#?}",
+        result.err()
+    );
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(session.owner, owner.pubkey());
+    assert_eq!(session.funding_threshold, None);
+
+    let result = fixt.cancel_session(&owner, 0);
+    assert!(
+        result.is_ok(),
+        "New owner failed to cancel the reused session: {:#?}",
+        result.err()
+    );
+    assert_eq!(
+        fixt.get_session(&network_config_pda, 0).status,
+        SessionStatus::Cancelled
+    );
+}
+
+#[test]
+fn test_set_session_rejects_oversized_specification_cid() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false);
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let public_node = fixt.public_node.pubkey();
+
+    let err = fixt
+        .set_session(
+            &owner,
+            0,
+            format!("Qm{}", "a".repeat(MAX_SPECIFICATION_CID_LEN - 1)),
+            10,
+            0,
+            task_slot_id,
+            DEFAULT_INITIAL_DEPOSIT,
+            public_node,
+            TaskType::Completion(0),
+        )
+        .expect_err("A specification CID over the cap is rejected");
+    assert!(Helpers::has_error_code(&err.meta.logs, "InvalidCID"));
+
+    let specification_cid = format!("Qm{}", "a".repeat(MAX_SPECIFICATION_CID_LEN - 2));
+    let result = fixt.set_session(
+        &owner,
+        0,
+        specification_cid.clone(),
+        10,
+        0,
+        task_slot_id,
+        DEFAULT_INITIAL_DEPOSIT,
+        public_node,
+        TaskType::Completion(0),
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to set session at the cap: {:#?}",
+        result.err()
+    );
+    assert_eq!(
+        fixt.get_session(&network_config_pda, 0).specification_cid,
+        specification_cid
+    );
+}
+
+#[test]
+fn test_cancelled_session_lock_withdrawable_only_after_reclaim() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_contribute_to_session(0, DEFAULT_CONTRIBUTION_AMOUNT);
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let outsider = fixt.create_keypair();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let max_task_cost = 100_000_000;
+
+    let processing_window_slots = 20;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.processing_window_slots = Some(processing_window_slots);
+    });
+    assert!(result.is_ok(), "Failed to set processing window");
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, max_task_cost, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+
+    let err = fixt
+        .reclaim_cancelled_task(&outsider, 0, &compute_node.pubkey())
+        .expect_err("Only a cancelled session's task is reclaimed");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidSessionStatus"
+    ));
+
+    let result = fixt.cancel_session(&owner, 0);
+    assert!(
+        result.is_ok(),
+        "Failed to cancel with a task in flight: {:#?}",
+        result.err()
+    );
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(session.status, SessionStatus::Cancelled);
+    assert_eq!(session.locked_for_tasks, max_task_cost);
+
+    let half_shares = fixt
+        .get_contribution(&session_pda, &contributor.pubkey())
+        .shares
+        / 2;
+    let vault_before = fixt.svm.get_lamports(&vault_pda);
+    let result = fixt.withdraw_from_session(&contributor, 0, half_shares);
+    assert!(result.is_ok(), "Failed to withdraw before reclaim");
+    let withdrawn_before_reclaim = vault_before - fixt.svm.get_lamports(&vault_pda);
+
+    // The compute node may still submit until its processing deadline
+    let err = fixt
+        .reclaim_cancelled_task(&outsider, 0, &compute_node.pubkey())
+        .expect_err("The claimant keeps the task until its processing deadline");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "ProcessingWindowOpen"
+    ));
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_ne!(task.processing_deadline_slot, 0);
+    fixt.svm.warp_to_slot(task.processing_deadline_slot + 1);
+    fixt.svm.expire_blockhash();
+    let metadata = fixt
+        .reclaim_cancelled_task(&outsider, 0, &compute_node.pubkey())
+        .expect("Failed to reclaim the cancelled session's task");
+    let event: CancelledTaskReclaimed =
+        Helpers::decode_event(&metadata.logs, "CancelledTaskReclaimed")
+            .expect("CancelledTaskReclaimed event not emitted");
+    assert_eq!(event.task_slot_id, task_slot_id);
+    assert_eq!(event.compute_node, compute_node.pubkey());
+    assert_eq!(event.released_amount, max_task_cost);
+    assert_eq!(event.locked_for_tasks, 0);
+    assert_eq!(
+        fixt.get_task(&network_config_pda, task_slot_id).status,
+        TaskStatus::Ready
+    );
+    assert_eq!(fixt.get_node_info(&compute_node.pubkey()).current_tasks, 0);
+
+    // The released lock raises the share price of whatever is still outstanding
+    fixt.svm.expire_blockhash();
+    let vault_before = fixt.svm.get_lamports(&vault_pda);
+    let result = fixt.withdraw_from_session(&contributor, 0, half_shares);
+    assert!(result.is_ok(), "Failed to withdraw after reclaim");
+    let withdrawn_after_reclaim = vault_before - fixt.svm.get_lamports(&vault_pda);
+    assert!(withdrawn_after_reclaim > withdrawn_before_reclaim);
+}
+
+#[test]
+fn test_cancelled_session_settles_submitted_result() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.validation_window_slots = Some(100);
+    });
+    assert!(result.is_ok(), "Failed to set validation window");
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.cancel_session(&owner, 0);
+    assert!(result.is_ok(), "Failed to cancel with a result pending");
+
+    let err = fixt
+        .reclaim_cancelled_task(&owner, 0, &compute_node.pubkey())
+        .expect_err("Validators keep the submitted result until its deadline");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "ValidationWindowOpen"
+    ));
+
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        false,
+    );
+    assert!(
+        result.is_ok(),
+        "Failed to settle the in-flight result: {:#?}",
+        result.err()
+    );
+
+    let session = fixt.get_session(&network_config_pda, 0);
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(session.status, SessionStatus::Cancelled);
+    assert_eq!(session.locked_for_tasks, 0);
+    assert_eq!(session.total_paid, 50_000_000);
+    assert_eq!(session.current_iteration, 0);
+    assert_eq!(task.status, TaskStatus::Ready);
+}
+
+#[test]
+fn test_cancelled_task_without_deadline_reclaimable_after_stall() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let stall_slots = 10;
+    let result = fixt.update_network_config_with(&fixt.authority.insecure_clone(), |args| {
+        args.validation_stall_slots = Some(stall_slots);
+    });
+    assert!(result.is_ok(), "Failed to set validation stall window");
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.cancel_session(&owner, 0);
+    assert!(result.is_ok(), "Failed to cancel with a result pending");
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.validation_deadline_slot, 0);
+    let err = fixt
+        .reclaim_cancelled_task(&owner, 0, &compute_node.pubkey())
+        .expect_err("The stall window stands in for the missing deadline");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "ValidationWindowOpen"
+    ));
+
+    fixt.svm
+        .warp_to_slot(task.validation_started_slot + stall_slots);
+    fixt.svm.expire_blockhash();
+    let result = fixt.reclaim_cancelled_task(&owner, 0, &compute_node.pubkey());
+    assert!(
+        result.is_ok(),
+        "Failed to reclaim after the stall window: {:#?}",
+        result.err()
+    );
+
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(session.locked_for_tasks, 0);
+    assert_eq!(
+        fixt.get_task(&network_config_pda, task_slot_id).status,
+        TaskStatus::Ready
+    );
+}
+
+#[test]
+fn test_batched_public_validation_reaches_quorum_in_one_transaction() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_confidential_node()
+        .with_claim_confidential_node()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let confidential_node = fixt.confidential_node.insecure_clone();
+    let mut co_signers = Vec::new();
+    for _ in 0..2 {
+        let validator_owner = fixt.create_keypair();
+        let validator = fixt.create_keypair();
+        let result = fixt.register_node(&validator_owner, &validator.pubkey(), NodeType::Public);
+        assert!(result.is_ok(), "Failed to register validator");
+        let result = fixt.claim_compute_node(&validator, DEFAULT_NODE_INFO_CID.to_string());
+        assert!(result.is_ok(), "Failed to claim validator");
+        let result = fixt.validate_public_node(&confidential_node, &validator.pubkey(), true);
+        assert!(result.is_ok(), "Failed to validate validator");
+        co_signers.push(validator);
+    }
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let mut network_config = fixt.get_network_config();
+    network_config.required_validations = 3;
+    let data = borsh::to_vec(&network_config).unwrap();
+    let mut account = fixt.svm.get_account(&network_config_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(network_config_pda, account).unwrap();
+
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let compute_node = fixt.public_node.insecure_clone();
+    let coordinator = fixt.validator_node.insecure_clone();
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let task_index = fixt.get_task(&network_config_pda, task_slot_id).task_index;
+
+    let err = fixt
+        .submit_batch_public_validation(
+            &coordinator,
+            &[&co_signers[0], &compute_node],
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            50_000_000,
+            false,
+        )
+        .expect_err("Every batched vote must come from an assigned validator");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "ValidatorNotAssigned"
+    ));
+
+    let metadata = fixt
+        .submit_batch_public_validation(
+            &coordinator,
+            &[&co_signers[0], &co_signers[1]],
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            50_000_000,
+            false,
+        )
+        .expect("Batched approvals should reach quorum");
+    let batch: BatchValidationSubmitted =
+        Helpers::decode_event(&metadata.logs, "BatchValidationSubmitted")
+            .expect("BatchValidationSubmitted event not emitted");
+    assert_eq!(
+        batch.validators,
+        vec![
+            coordinator.pubkey(),
+            co_signers[0].pubkey(),
+            co_signers[1].pubkey()
+        ]
+    );
+    let event: TaskValidationSubmitted =
+        Helpers::decode_event(&metadata.logs, "TaskValidationSubmitted")
+            .expect("TaskValidationSubmitted event not emitted");
+    assert_eq!(event.payment_amount, 50_000_000);
+    assert_eq!(event.locked_for_tasks, 0);
+
+    let task = fixt.get_task(&network_config_pda, task_slot_id);
+    assert_eq!(task.status, TaskStatus::Pending);
+    assert_eq!(task.task_index, task_index);
+    assert!(task.validations.is_empty());
+    for validator in [&coordinator, &co_signers[0], &co_signers[1]] {
+        assert_eq!(fixt.get_node_info(&validator.pubkey()).total_validations, 1);
+    }
+}
+
+#[test]
+fn test_repeated_contributions_overflow_near_share_ceiling() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_contribute_to_session(0, DEFAULT_CONTRIBUTION_AMOUNT);
+
+    let contributor = fixt.contributor.insecure_clone();
+    let owner = fixt.agent_owner.pubkey();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let (contribution_pda, _) = fixt.find_contribution_pda(&session_pda, &contributor.pubkey());
+
+    // Share prices are whole units of 1/SHARE_PRECISION lamport, so a vault of 20 SOL keeps
+    // the price at 1 even with the outstanding shares inflated to just under u64::MAX
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+    fixt.svm
+        .airdrop(&vault_pda, 20 * LAMPORTS_PER_SOL)
+        .expect("Failed to fund vault");
+    let ceiling_room = 350_000_000_000_000_000;
+    let mut session = fixt.get_session(&network_config_pda, 0);
+    let owner_shares = fixt.get_contribution(&session_pda, &owner).shares;
+    session.total_shares = u64::MAX - ceiling_room;
+    let data = borsh::to_vec(&session).unwrap();
+    let mut account = fixt.svm.get_account(&session_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(session_pda, account).unwrap();
+
+    let mut contribution = fixt.get_contribution(&session_pda, &contributor.pubkey());
+    contribution.shares = session.total_shares - owner_shares;
+    let data = borsh::to_vec(&contribution).unwrap();
+    let mut account = fixt.svm.get_account(&contribution_pda).unwrap();
+    account.data[..data.len()].copy_from_slice(&data);
+    fixt.svm.set_account(contribution_pda, account).unwrap();
+
+    let mut accepted = 0;
+    let err = loop {
+        let before = fixt.get_session(&network_config_pda, 0).total_shares;
+        match fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT / 5) {
+            Ok(_) => {
+                accepted += 1;
+                assert!(fixt.get_session(&network_config_pda, 0).total_shares > before);
+            }
+            Err(err) => break err,
+        }
+        assert!(
+            accepted < 10,
+            "Contributions never reached the share ceiling"
+        );
+        fixt.svm.expire_blockhash();
+    };
+
+    assert!(accepted > 0, "Contributions below the ceiling should mint");
+    assert!(Helpers::has_error_code(&err.meta.logs, "Overflow"));
+
+    // The failed contribution left every share count as it was, nothing saturated
+    let session = fixt.get_session(&network_config_pda, 0);
+    let contribution = fixt.get_contribution(&session_pda, &contributor.pubkey());
+    assert!(session.total_shares < u64::MAX);
+    assert_eq!(session.total_shares, contribution.shares + owner_shares);
+}
+
+#[test]
+fn test_session_completion_invokes_completion_hook() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+    fixt.svm.deploy_program_from_id(
+        COMPLETION_HOOK_MOCK_PROGRAM_ID,
+        COMPLETION_HOOK_MOCK_SO_PATH,
+    );
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+
+    let dac_program_id = fixt.program_id;
+    let err = fixt
+        .set_completion_hook(&owner, 0, Some(dac_program_id))
+        .expect_err("The dac program cannot be its own hook");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidCompletionHook"
+    ));
+    let result = fixt.set_completion_hook(&owner, 0, Some(COMPLETION_HOOK_MOCK_PROGRAM_ID));
+    assert!(result.is_ok(), "Failed to set completion hook");
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(
+        session.completion_hook_program,
+        Some(COMPLETION_HOOK_MOCK_PROGRAM_ID)
+    );
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+
+    // A hook swapped in now could revert the settlement and leave the node unpaid
+    let err = fixt
+        .set_completion_hook(&owner, 0, Some(Pubkey::new_unique()))
+        .expect_err("The hook can't change while a task is in flight");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "SessionTasksInFlight"
+    ));
+
+    let err = fixt
+        .submit_public_task_validation(
+            &validator_node,
+            0,
+            task_slot_id,
+            &compute_node.pubkey(),
+            50_000_000,
+            true,
+            true,
+        )
+        .expect_err("Completing needs the hook program account");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "CompletionHookMissing"
+    ));
+
+    let result = fixt.submit_public_task_validation_with(
+        &validator_node,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        50_000_000,
+        true,
+        true,
+        |builder| {
+            builder.add_remaining_account(AccountMeta::new_readonly(
+                COMPLETION_HOOK_MOCK_PROGRAM_ID,
+                false,
+            ));
+        },
+    );
+    let meta = result.expect("Failed to complete the session through its hook");
+
+    let hook_invoke = format!("Program {} invoke [2]", COMPLETION_HOOK_MOCK_PROGRAM_ID);
+    assert!(
+        meta.logs.iter().any(|log| log.contains(&hook_invoke)),
+        "Completion hook was not invoked: {:#?}",
+        meta.logs
+    );
+    assert!(meta
+        .logs
+        .iter()
+        .any(|log| log.contains("Completion hook: session 0")));
+    assert_eq!(
+        fixt.get_session(&network_config_pda, 0).status,
+        SessionStatus::Completed
+    );
+}
+
+#[test]
+fn test_node_treasury_balance_reports_task_payment_as_claimable() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let payer = fixt.payer.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let task_slot_id = fixt.find_session_task_slot_id(0);
+    let payment_amount = 50_000_000;
+
+    let metadata = fixt
+        .get_node_treasury_balance(&payer, &compute_node.pubkey())
+        .expect("Failed to query node treasury balance");
+    let before: NodeTreasuryBalance = Helpers::decode_event(&metadata.logs, "NodeTreasuryBalance")
+        .expect("NodeTreasuryBalance event not emitted");
+    assert_eq!(before.node, compute_node.pubkey());
+    assert_eq!(
+        before.node_treasury,
+        fixt.get_node_info(&compute_node.pubkey()).node_treasury
+    );
+
+    let result = fixt.claim_task(&compute_node, 0, task_slot_id, 100_000_000, 10);
+    assert!(result.is_ok(), "Failed to claim task");
+    let result = fixt.submit_task_result(
+        &compute_node,
+        0,
+        task_slot_id,
+        "QmTestInput123456789".to_string(),
+        "QmTestOutput123456789".to_string(),
+        None,
+        1,
+    );
+    assert!(result.is_ok(), "Failed to submit task result");
+    let result = fixt.submit_public_task_validation(
+        &validator_node,
+        0,
+        task_slot_id,
+        &compute_node.pubkey(),
+        payment_amount,
+        true,
+        false,
+    );
+    assert!(result.is_ok(), "Failed to validate task");
+    fixt.svm.expire_blockhash();
+
+    let metadata = fixt
+        .get_node_treasury_balance(&payer, &compute_node.pubkey())
+        .expect("Failed to query node treasury balance");
+    let after: NodeTreasuryBalance = Helpers::decode_event(&metadata.logs, "NodeTreasuryBalance")
+        .expect("NodeTreasuryBalance event not emitted");
+    let rent_exempt_minimum = fixt.svm.minimum_balance_for_rent_exemption(0);
+    assert_eq!(after.claimable, before.claimable + payment_amount);
+    assert_eq!(after.balance, fixt.svm.get_lamports(&after.node_treasury));
+    assert_eq!(after.claimable, after.balance - rent_exempt_minimum);
+}
+
+#[test]
+fn test_contribution_rejected_above_vault_cap() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let authority = fixt.authority.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+    let rent_exempt_minimum = fixt.svm.minimum_balance_for_rent_exemption(0);
+
+    // Room for exactly two contributions on top of the owner's deposit
+    let vault_balance = fixt.svm.get_lamports(&vault_pda) - rent_exempt_minimum;
+    let max_vault_balance = vault_balance + 2 * DEFAULT_CONTRIBUTION_AMOUNT;
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.max_vault_balance = Some(max_vault_balance);
+    });
+    assert!(result.is_ok(), "Failed to set vault cap");
+    assert_eq!(
+        fixt.get_network_config().max_vault_balance,
+        Some(max_vault_balance)
+    );
+
+    for _ in 0..2 {
+        let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+        assert!(
+            result.is_ok(),
+            "Failed to contribute up to the cap: {:#?}",
+            result.err()
+        );
+        fixt.svm.expire_blockhash();
+    }
+    assert_eq!(
+        fixt.svm.get_lamports(&vault_pda) - rent_exempt_minimum,
+        max_vault_balance
+    );
+
+    let err = fixt
+        .contribute_to_session(&contributor, 0, 1)
+        .expect_err("Contribution past the vault cap");
+    assert!(Helpers::has_error_code(&err.meta.logs, "VaultCapExceeded"));
+    assert_eq!(
+        fixt.svm.get_lamports(&vault_pda) - rent_exempt_minimum,
+        max_vault_balance
+    );
+
+    // 0 lifts the cap
+    let result = fixt.update_network_config_with(&authority, |args| {
+        args.max_vault_balance = Some(0);
+    });
+    assert!(result.is_ok(), "Failed to lift vault cap");
+    assert_eq!(fixt.get_network_config().max_vault_balance, None);
+    let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(
+        result.is_ok(),
+        "Failed to contribute once the cap is lifted"
+    );
+}
+
+#[test]
+fn test_node_earnings_tallied_by_task_type() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0))
+        .with_create_session(false)
+        .with_set_session_using_public_compute(1, 0, TaskType::Custom(7));
+
+    let payer = fixt.payer.insecure_clone();
+    let compute_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+    let completion_payment = 40_000_000;
+    let custom_payment = 15_000_000;
+
+    for (session_slot_id, payment_amount) in [(0, completion_payment), (1, custom_payment)] {
+        let task_slot_id = fixt.find_session_task_slot_id(session_slot_id);
+        let result = fixt.claim_task(
+            &compute_node,
+            session_slot_id,
+            task_slot_id,
+            100_000_000,
+            10,
+        );
+        assert!(result.is_ok(), "Failed to claim task");
+        let result = fixt.submit_task_result(
+            &compute_node,
+            session_slot_id,
+            task_slot_id,
+            "QmTestInput123456789".to_string(),
+            "QmTestOutput123456789".to_string(),
+            None,
+            1,
+        );
+        assert!(result.is_ok(), "Failed to submit task result");
+        let result = fixt.submit_public_task_validation(
+            &validator_node,
+            session_slot_id,
+            task_slot_id,
+            &compute_node.pubkey(),
+            payment_amount,
+            true,
+            false,
+        );
+        assert!(
+            result.is_ok(),
+            "Failed to validate task: {:#?}",
+            result.err()
+        );
+    }
+
+    // Completion, Custom, HumanInLoop
+    let expected_by_type = [completion_payment, custom_payment, 0];
+    let node_info = fixt.get_node_info(&compute_node.pubkey());
+    assert_eq!(node_info.earned_by_type, expected_by_type);
+    assert_eq!(
+        node_info.total_earned,
+        node_info.earned_by_type.iter().sum::<u64>()
+    );
+
+    let metadata = fixt
+        .get_node_summary(&payer, &compute_node.pubkey())
+        .expect("Failed to query node summary");
+    let summary: NodeSummary = Helpers::decode_event(&metadata.logs, "NodeSummary")
+        .expect("NodeSummary event not emitted");
+    assert_eq!(summary.earned_by_type, expected_by_type);
+}
+
+#[test]
+fn test_sponsored_session_attributed_to_owner() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0);
+
+    let sponsor = fixt.payer.insecure_clone();
+    let owner = fixt.agent_owner.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let sponsor_balance = fixt.svm.get_lamports(&sponsor.pubkey());
+    let owner_balance = fixt.svm.get_lamports(&owner.pubkey());
+
+    let metadata = fixt
+        .create_sponsored_session(&sponsor, &owner, true, false)
+        .expect("Failed to create sponsored session");
+    let created: SessionCreated = Helpers::decode_event(&metadata.logs, "SessionCreated")
+        .expect("SessionCreated event not emitted");
+    assert_eq!(created.session_slot_id, 0);
+    assert_eq!(created.owner, owner.pubkey());
+    assert_eq!(created.payer, sponsor.pubkey());
+    assert!(created.sponsored);
+
+    let session = fixt.get_session(&network_config_pda, 0);
+    assert_eq!(session.owner, owner.pubkey());
+    // Rent and fees come from the sponsor alone
+    assert!(fixt.svm.get_lamports(&sponsor.pubkey()) < sponsor_balance);
+    assert_eq!(fixt.svm.get_lamports(&owner.pubkey()), owner_balance);
+
+    let mut fixt = fixt.with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, 0);
+    let session = fixt.get_session(&network_config_pda, 0);
+    let owner_contribution = fixt.get_contribution(&session_pda, &owner.pubkey());
+    assert!(owner_contribution.is_owner);
+    assert_eq!(owner_contribution.shares, session.total_shares);
+    let (sponsor_contribution_pda, _) = fixt.find_contribution_pda(&session_pda, &sponsor.pubkey());
+    assert!(
+        fixt.svm.get_account(&sponsor_contribution_pda).is_none(),
+        "Sponsor holds no stake in the session"
+    );
+
+    // Without a sponsor the same signer pays and owns
+    let metadata = fixt
+        .create_session(&owner, true, false)
+        .expect("Failed to create self-funded session");
+    let created: SessionCreated = Helpers::decode_event(&metadata.logs, "SessionCreated")
+        .expect("SessionCreated event not emitted");
+    assert_eq!(created.owner, owner.pubkey());
+    assert_eq!(created.payer, owner.pubkey());
+    assert!(!created.sponsored);
+}
+
+#[test]
+fn test_owner_closes_session_to_contributions() {
+    let mut fixt = TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_validate_public_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(0, 0, TaskType::Completion(0));
+
+    let owner = fixt.agent_owner.insecure_clone();
+    let contributor = fixt.contributor.insecure_clone();
+    let network_config_pda = fixt.find_network_config_pda().0;
+    assert!(
+        fixt.get_session(&network_config_pda, 0)
+            .accepting_contributions
+    );
+
+    let err = fixt
+        .set_accepting_contributions(&contributor, 0, false)
+        .expect_err("Only the owner closes the session");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "InvalidSessionOwner"
+    ));
+
+    let result = fixt.set_accepting_contributions(&owner, 0, false);
+    assert!(result.is_ok(), "Failed to close session to contributions");
+    assert!(
+        !fixt
+            .get_session(&network_config_pda, 0)
+            .accepting_contributions
+    );
+
+    let total_shares = fixt.get_session(&network_config_pda, 0).total_shares;
+    let err = fixt
+        .contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT)
+        .expect_err("Closed session refuses third-party contributions");
+    assert!(Helpers::has_error_code(
+        &err.meta.logs,
+        "ContributionsClosed"
+    ));
+    assert_eq!(
+        fixt.get_session(&network_config_pda, 0).total_shares,
+        total_shares
+    );
+
+    // The owner can still top up their own stake
+    let result = fixt.contribute_to_session(&owner, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(result.is_ok(), "Failed owner top-up: {:#?}", result.err());
+    fixt.svm.expire_blockhash();
+
+    let result = fixt.set_accepting_contributions(&owner, 0, true);
+    assert!(result.is_ok(), "Failed to reopen session to contributions");
+    let result = fixt.contribute_to_session(&contributor, 0, DEFAULT_CONTRIBUTION_AMOUNT);
+    assert!(
+        result.is_ok(),
+        "Failed to contribute once reopened: {:#?}",
+        result.err()
+    );
+}
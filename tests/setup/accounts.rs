@@ -1,5 +1,8 @@
-use dac_client::accounts::{Agent, Contribution, NetworkConfig, NodeInfo, Session, Task};
+use dac_client::accounts::{
+    Agent, Contribution, NetworkConfig, NodeInfo, Session, SessionProposal, Task, TaskSettlement,
+};
 use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey, signature::Signer};
+use std::collections::{HashMap, HashSet};
 
 use crate::setup::TestFixture;
 
@@ -9,13 +12,19 @@ pub trait Accounts {
     fn find_session_pda(&self, network_config: &Pubkey, session_slot_id: u64) -> (Pubkey, u8);
     fn find_task_pda(&self, network_config: &Pubkey, task_id: u64) -> (Pubkey, u8);
     fn create_task_pdas(&self, network_config: &Pubkey, count: u64) -> Vec<AccountMeta>;
+    fn create_task_pdas_from(
+        &self,
+        network_config: &Pubkey,
+        first_task_id: u64,
+        count: u64,
+    ) -> Vec<AccountMeta>;
     fn create_remaining_accounts_for_initialize(
         &self,
         network_config: &Pubkey,
         allocate_tasks: u64,
     ) -> Vec<AccountMeta>;
     fn find_node_info_pda(&self, node_pubkey: &Pubkey) -> (Pubkey, u8);
-    fn find_node_treasury_pda(&self, node_info: &Pubkey) -> (Pubkey, u8);
+    fn find_node_treasury_pda(&self, node_info: &Pubkey, registration_epoch: u64) -> (Pubkey, u8);
     fn get_node_info(&self, node_pubkey: &Pubkey) -> NodeInfo;
     fn find_agent_pda(&self, network_config: &Pubkey, agent_slot_id: u64) -> (Pubkey, u8);
     fn get_agent(&self, network_config: &Pubkey, agent_slot_id: u64) -> Agent;
@@ -24,6 +33,14 @@ pub trait Accounts {
     fn find_contribution_pda(&self, session: &Pubkey, contributor: &Pubkey) -> (Pubkey, u8);
     fn get_contribution(&self, session: &Pubkey, contributor: &Pubkey) -> Contribution;
     fn get_task(&self, network_config: &Pubkey, task_slot_id: u64) -> Task;
+    fn find_session_task_slot_id(&self, session_slot_id: u64) -> u64;
+    fn get_token_balance(&self, token_account: &Pubkey) -> u64;
+    fn find_protocol_treasury_pda(&self, network_config: &Pubkey) -> (Pubkey, u8);
+    fn find_session_proposal_pda(&self, session: &Pubkey) -> (Pubkey, u8);
+    fn get_session_proposal(&self, session: &Pubkey) -> SessionProposal;
+    fn find_task_settlement_pda(&self, task: &Pubkey, task_index: u64) -> (Pubkey, u8);
+    fn get_task_settlement(&self, task: &Pubkey, task_index: u64) -> TaskSettlement;
+    fn assert_task_slots_unique(&self);
 }
 
 impl Accounts for TestFixture {
@@ -58,7 +75,16 @@ impl Accounts for TestFixture {
     }
 
     fn create_task_pdas(&self, network_config: &Pubkey, count: u64) -> Vec<AccountMeta> {
-        (0..count)
+        self.create_task_pdas_from(network_config, 0, count)
+    }
+
+    fn create_task_pdas_from(
+        &self,
+        network_config: &Pubkey,
+        first_task_id: u64,
+        count: u64,
+    ) -> Vec<AccountMeta> {
+        (first_task_id..first_task_id + count)
             .map(|task_id| {
                 let (pda, _bump) = self.find_task_pda(network_config, task_id);
                 AccountMeta {
@@ -83,8 +109,12 @@ impl Accounts for TestFixture {
         Pubkey::find_program_address(seeds, &self.program_id)
     }
 
-    fn find_node_treasury_pda(&self, node_info: &Pubkey) -> (Pubkey, u8) {
-        let seeds = &[b"node_treasury", node_info.as_ref()];
+    fn find_node_treasury_pda(&self, node_info: &Pubkey, registration_epoch: u64) -> (Pubkey, u8) {
+        let seeds = &[
+            b"node_treasury".as_ref(),
+            node_info.as_ref(),
+            &registration_epoch.to_le_bytes(),
+        ];
         Pubkey::find_program_address(seeds, &self.program_id)
     }
 
@@ -155,4 +185,104 @@ impl Accounts for TestFixture {
 
         Task::from_bytes(&account.data).expect("Failed to deserialize Task account")
     }
+
+    fn find_session_task_slot_id(&self, session_slot_id: u64) -> u64 {
+        let network_config_pda = self.find_network_config_pda().0;
+        let session = self.get_session(&network_config_pda, session_slot_id);
+        let network_config = self.get_network_config();
+
+        (0..network_config.task_count)
+            .find(|i| self.find_task_pda(&network_config_pda, *i).0 == session.task)
+            .expect("Task for session not found")
+    }
+
+    fn get_token_balance(&self, token_account: &Pubkey) -> u64 {
+        let account = self
+            .svm
+            .get_account(token_account)
+            .expect("Token account not found");
+
+        // SPL token account layout: mint (32) | owner (32) | amount (8) | ...
+        u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+    }
+
+    fn find_protocol_treasury_pda(&self, network_config: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[b"protocol_treasury", network_config.as_ref()];
+        Pubkey::find_program_address(seeds, &self.program_id)
+    }
+
+    fn find_session_proposal_pda(&self, session: &Pubkey) -> (Pubkey, u8) {
+        let seeds = &[b"session_proposal", session.as_ref()];
+        Pubkey::find_program_address(seeds, &self.program_id)
+    }
+
+    fn get_session_proposal(&self, session: &Pubkey) -> SessionProposal {
+        let addr = self.find_session_proposal_pda(session).0;
+
+        let account = self
+            .svm
+            .get_account(&addr)
+            .expect("SessionProposal account not found");
+
+        SessionProposal::from_bytes(&account.data)
+            .expect("Failed to deserialize SessionProposal account")
+    }
+
+    fn find_task_settlement_pda(&self, task: &Pubkey, task_index: u64) -> (Pubkey, u8) {
+        let seeds = &[
+            b"task_settlement".as_ref(),
+            task.as_ref(),
+            &task_index.to_le_bytes(),
+        ];
+        Pubkey::find_program_address(seeds, &self.program_id)
+    }
+
+    fn get_task_settlement(&self, task: &Pubkey, task_index: u64) -> TaskSettlement {
+        let addr = self.find_task_settlement_pda(task, task_index).0;
+
+        let account = self
+            .svm
+            .get_account(&addr)
+            .expect("TaskSettlement account not found");
+
+        TaskSettlement::from_bytes(&account.data)
+            .expect("Failed to deserialize TaskSettlement account")
+    }
+
+    /// Walks every allocated task slot and every session, panicking if a task PDA is
+    /// missing, carries the wrong slot id or is bound to more than one session.
+    fn assert_task_slots_unique(&self) {
+        let network_config_pda = self.find_network_config_pda().0;
+        let network_config = self.get_network_config();
+
+        let mut task_pdas = HashSet::new();
+        for task_slot_id in 0..network_config.task_count {
+            let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+            assert!(
+                task_pdas.insert(task_pda),
+                "Task slot {task_slot_id} derives an already allocated PDA {task_pda}"
+            );
+            let task = self.get_task(&network_config_pda, task_slot_id);
+            assert_eq!(
+                task.task_slot_id, task_slot_id,
+                "Task PDA {task_pda} holds the task of another slot"
+            );
+        }
+
+        let mut session_tasks = HashMap::new();
+        for session_slot_id in 0..network_config.session_count {
+            let session = self.get_session(&network_config_pda, session_slot_id);
+            assert!(
+                task_pdas.contains(&session.task),
+                "Session {session_slot_id} points at unallocated task {}",
+                session.task
+            );
+            if let Some(other) = session_tasks.insert(session.task, session_slot_id) {
+                panic!(
+                    "Sessions {other} and {session_slot_id} share task {}",
+                    session.task
+                );
+            }
+        }
+    }
 }
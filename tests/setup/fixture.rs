@@ -117,6 +117,7 @@ impl TestFixture {
             DEFAULT_ALLOCATE_TASKS,
             DEFAULT_APPROVED_CODE_MEASUREMENTS.to_vec(),
             crate::setup::test_data::DEFAULT_REQUIRED_VALIDATIONS,
+            None,
             &remaining_accounts,
         );
         assert!(result.is_ok(), "Failed to initialize network");
@@ -358,4 +359,17 @@ impl TestFixture {
         assert!(result.is_ok(), "Failed to withdraw from session");
         self
     }
+    /// Allocates `count` more task slots, split over as many extend_tasks transactions as
+    /// the packet size requires
+    pub fn with_extended_tasks(mut self, count: u64) -> Self {
+        let authority = self.authority.insecure_clone();
+        let mut remaining = count;
+        while remaining > 0 {
+            let batch = remaining.min(MAX_TASKS_PER_EXTEND);
+            let result = self.extend_tasks(&authority, batch);
+            assert!(result.is_ok(), "Failed to extend tasks");
+            remaining -= batch;
+        }
+        self
+    }
 }
@@ -1,4 +1,7 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
 use borsh::{BorshDeserialize, BorshSerialize};
+use dac_client::TaskType;
+use sha2::{Digest, Sha256};
 use solana_sdk::{message::Instruction, signature::Keypair};
 use utils::create_ed25519_instruction_with_signature;
 
@@ -10,6 +13,7 @@ pub struct SubmitTaskValidationMessage {
     pub validation_proof: [u8; 32],
     pub approved: bool,
     pub goal_completed: bool,
+    pub output_size: u64,
 }
 
 pub struct Helpers;
@@ -22,6 +26,7 @@ impl Helpers {
         validation_proof: [u8; 32],
         approved: bool,
         goal_completed: bool,
+        output_size: u64,
         signing_keypair: &Keypair,
     ) -> Instruction {
         let message = SubmitTaskValidationMessage {
@@ -31,6 +36,7 @@ impl Helpers {
             validation_proof,
             approved,
             goal_completed,
+            output_size,
         };
         let mut message_data = Vec::new();
         message
@@ -39,4 +45,59 @@ impl Helpers {
 
         create_ed25519_instruction_with_signature(&message_data, signing_keypair)
     }
+
+    pub fn compute_validation_proof(
+        session_slot_id: u64,
+        task_slot_id: u64,
+        task_index: u64,
+        task_type: &TaskType,
+        input_cid: &str,
+        output_cid: &str,
+    ) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"DAC_VALIDATION_PROOF");
+        hasher.update(session_slot_id.to_le_bytes());
+        hasher.update(task_slot_id.to_le_bytes());
+        hasher.update(task_index.to_le_bytes());
+        match task_type {
+            TaskType::Completion(model_id) => {
+                hasher.update(b"COMPLETION");
+                hasher.update(model_id.to_le_bytes());
+            }
+            TaskType::Custom(module_id) => {
+                hasher.update(b"CUSTOM");
+                hasher.update(module_id.to_le_bytes());
+            }
+            TaskType::HumanInLoop => hasher.update(b"HUMAN_IN_LOOP"),
+        }
+        hasher.update(input_cid.as_bytes());
+        hasher.update(output_cid.as_bytes());
+        hasher.finalize().into()
+    }
+
+    pub fn decode_event<T: BorshDeserialize>(logs: &[String], event_name: &str) -> Option<T> {
+        let discriminator: [u8; 32] = Sha256::digest(format!("event:{}", event_name)).into();
+
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .find(|bytes| bytes.len() >= 8 && bytes[..8] == discriminator[..8])
+            .and_then(|bytes| T::try_from_slice(&bytes[8..]).ok())
+    }
+
+    pub fn decode_events<T: BorshDeserialize>(logs: &[String], event_name: &str) -> Vec<T> {
+        let discriminator: [u8; 32] = Sha256::digest(format!("event:{}", event_name)).into();
+
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|data| STANDARD.decode(data).ok())
+            .filter(|bytes| bytes.len() >= 8 && bytes[..8] == discriminator[..8])
+            .filter_map(|bytes| T::try_from_slice(&bytes[8..]).ok())
+            .collect()
+    }
+
+    pub fn has_error_code(logs: &[String], error_code: &str) -> bool {
+        let marker = format!("Error Code: {}.", error_code);
+        logs.iter().any(|log| log.contains(&marker))
+    }
 }
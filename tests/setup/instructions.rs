@@ -1,12 +1,26 @@
 use dac_client::instructions::{
-    ActivateNodeBuilder, ClaimConfidentialNodeBuilder, ClaimPublicNodeBuilder, ClaimTaskBuilder,
-    ContributeToSessionBuilder, CreateAgentBuilder, CreateSessionBuilder, InitializeNetworkBuilder,
-    RegisterNodeBuilder, SetSessionBuilder, SubmitConfidentialTaskValidationBuilder,
-    SubmitPublicTaskValidationBuilder, SubmitTaskResultBuilder, UpdateNetworkConfigBuilder,
-    ValidateAgentBuilder, ValidatePublicNodeBuilder, WithdrawFromSessionBuilder,
+    ActivateNodeBuilder, AuthorityInvalidateTaskBuilder, AuthorityRejectNodeBuilder,
+    BatchWithdrawNodeRewardsBuilder, CancelSessionBuilder, ClaimConfidentialNodeBuilder,
+    ClaimPublicNodeBuilder, ClaimTaskBuilder, ContributeToSessionBuilder, CreateAgentBuilder,
+    CreateSessionBuilder, DisableNodeBuilder, DisputeTaskSettlementBuilder, ExtendTasksBuilder,
+    ForceResolveTaskBuilder, GetNetworkStatsBuilder, GetNodeSummaryBuilder,
+    GetNodeTreasuryBalanceBuilder, GetSessionExposureBuilder, GetSharePrecisionBuilder,
+    GetValidationTallyBuilder, InitializeNetworkBuilder, IsNodeApprovedBuilder,
+    ListCodeMeasurementsBuilder, MergeSessionsBuilder, MigrateAccountBuilder,
+    MigrateNetworkConfigBuilder, ProposeSessionActionBuilder, PruneRejectedNodesBuilder,
+    ReclaimCancelledTaskBuilder, ReconcileSessionLocksBuilder, RegisterNodeBuilder,
+    ReleaseTaskSettlementBuilder, SeedTaskInputBuilder, SetAcceptingContributionsBuilder,
+    SetCompletionHookBuilder, SetNodeCapacityBuilder, SetSessionBuilder,
+    SubmitBatchPublicValidationBuilder, SubmitConfidentialTaskValidationBuilder,
+    SubmitPublicTaskValidationBuilder, SubmitTaskCheckpointBuilder, SubmitTaskResultBuilder,
+    SweepAbandonedSessionBuilder, UpdateAgentMemoryBuilder, UpdateNetworkConfigBuilder,
+    UpdateSessionLimitsBuilder, ValidateAgentBuilder, ValidatePublicNodeBuilder,
+    VerifyGenesisBuilder, VoteSessionActionBuilder, WithdrawFromSessionBuilder,
 };
 use dac_client::types::TaskType;
-use dac_client::types::{CodeMeasurement, NodeType};
+use dac_client::types::{
+    CodeMeasurement, NodeType, SessionAction, SetSessionArgs, UpdateNetworkConfigArgs,
+};
 use litesvm::types::TransactionResult;
 use solana_sdk::message::Instruction;
 use solana_sdk::{
@@ -17,10 +31,22 @@ use solana_sdk::{
 use std::str::FromStr;
 
 use crate::setup::{Accounts, TestFixture};
-use utils::Utils;
+use utils::{with_compute_unit_limit, Utils, MAX_COMPUTE_UNIT_LIMIT};
 
 pub trait Instructions {
     fn initialize_network(
+        &mut self,
+        authority: &Keypair,
+        network_config: &Pubkey,
+        cid_config: String,
+        allocate_tasks: u64,
+        approved_code_measurements: Vec<CodeMeasurement>,
+        required_validations: u32,
+        genesis_nonce: Option<[u8; 32]>,
+        remaining_accounts: &[AccountMeta],
+    ) -> TransactionResult;
+
+    fn initialize_network_with(
         &mut self,
         authority: &Keypair,
         network_config: &Pubkey,
@@ -29,7 +55,9 @@ pub trait Instructions {
         approved_code_measurements: Vec<CodeMeasurement>,
         required_validations: u32,
         remaining_accounts: &[AccountMeta],
+        configure: impl FnOnce(&mut InitializeNetworkBuilder),
     ) -> TransactionResult;
+
     fn register_node(
         &mut self,
         owner: &Keypair,
@@ -59,6 +87,14 @@ pub trait Instructions {
 
     fn activate_node(&mut self, authority: &Keypair, node_pubkey: &Pubkey) -> TransactionResult;
 
+    fn disable_node(&mut self, authority: &Keypair, node_pubkey: &Pubkey) -> TransactionResult;
+
+    fn authority_reject_node(
+        &mut self,
+        authority: &Keypair,
+        node_pubkey: &Pubkey,
+    ) -> TransactionResult;
+
     fn validate_agent(
         &mut self,
         node_validating: &Keypair,
@@ -78,6 +114,22 @@ pub trait Instructions {
         is_confidential: bool,
     ) -> TransactionResult;
 
+    fn create_session_with(
+        &mut self,
+        payer: &Keypair,
+        is_owned: bool,
+        is_confidential: bool,
+        configure: impl FnOnce(&mut CreateSessionBuilder),
+    ) -> TransactionResult;
+
+    fn create_sponsored_session(
+        &mut self,
+        payer: &Keypair,
+        owner: &Keypair,
+        is_owned: bool,
+        is_confidential: bool,
+    ) -> TransactionResult;
+
     fn set_session(
         &mut self,
         session_owner: &Keypair,
@@ -91,6 +143,20 @@ pub trait Instructions {
         task_type: TaskType,
     ) -> TransactionResult;
 
+    fn set_session_with(
+        &mut self,
+        session_owner: &Keypair,
+        session_slot_id: u64,
+        specification_cid: String,
+        max_iterations: u64,
+        agent_slot_id: u64,
+        task_slot_id: u64,
+        initial_deposit: u64,
+        compute_node: Pubkey,
+        task_type: TaskType,
+        configure: impl FnOnce(&mut SetSessionBuilder, &mut SetSessionArgs),
+    ) -> TransactionResult;
+
     fn contribute_to_session(
         &mut self,
         contributor: &Keypair,
@@ -98,6 +164,14 @@ pub trait Instructions {
         deposit_amount: u64,
     ) -> TransactionResult;
 
+    fn contribute_to_session_with(
+        &mut self,
+        contributor: &Keypair,
+        session_slot_id: u64,
+        deposit_amount: u64,
+        configure: impl FnOnce(&mut ContributeToSessionBuilder),
+    ) -> TransactionResult;
+
     fn withdraw_from_session(
         &mut self,
         contributor: &Keypair,
@@ -105,6 +179,7 @@ pub trait Instructions {
         shares_to_burn: u64,
     ) -> TransactionResult;
 
+
     fn claim_task(
         &mut self,
         compute_node: &Keypair,
@@ -114,6 +189,16 @@ pub trait Instructions {
         max_call_count: u64,
     ) -> TransactionResult;
 
+    fn claim_task_with(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        max_task_cost: u64,
+        max_call_count: u64,
+        configure: impl FnOnce(&mut ClaimTaskBuilder),
+    ) -> TransactionResult;
+
     fn submit_task_result(
         &mut self,
         compute_node: &Keypair,
@@ -125,6 +210,18 @@ pub trait Instructions {
         call_count: u64,
     ) -> TransactionResult;
 
+    fn submit_task_result_with(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        input_cid: String,
+        output_cid: String,
+        state_cid: Option<String>,
+        call_count: u64,
+        configure: impl FnOnce(&mut SubmitTaskResultBuilder),
+    ) -> TransactionResult;
+
     fn submit_confidential_task_validation(
         &mut self,
         node_validating: &Keypair,
@@ -134,6 +231,16 @@ pub trait Instructions {
         ed25519_ix: &Instruction,
     ) -> TransactionResult;
 
+    /// Sends `instructions` ahead of the validation, the last one must be the Ed25519 check
+    fn submit_confidential_task_validation_after(
+        &mut self,
+        node_validating: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+        instructions: &[Instruction],
+    ) -> TransactionResult;
+
     fn submit_public_task_validation(
         &mut self,
         node_validating: &Keypair,
@@ -145,16 +252,239 @@ pub trait Instructions {
         session_completed: bool,
     ) -> TransactionResult;
 
+    fn submit_public_task_validation_with(
+        &mut self,
+        node_validating: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+        payment_amount: u64,
+        approved: bool,
+        session_completed: bool,
+        configure: impl FnOnce(&mut SubmitPublicTaskValidationBuilder),
+    ) -> TransactionResult;
+
     fn update_network_config(
         &mut self,
         authority: &Keypair,
         cid_config: Option<String>,
         new_code_measurement: Option<CodeMeasurement>,
     ) -> TransactionResult;
+
+    fn update_network_config_with(
+        &mut self,
+        authority: &Keypair,
+        configure: impl FnOnce(&mut UpdateNetworkConfigArgs),
+    ) -> TransactionResult;
+
+    fn get_network_stats(&mut self, payer: &Keypair) -> TransactionResult;
+
+    fn is_node_approved(&mut self, payer: &Keypair, node: &Pubkey) -> TransactionResult;
+
+    fn list_code_measurements(&mut self, payer: &Keypair) -> TransactionResult;
+
+    fn get_share_precision(&mut self, payer: &Keypair) -> TransactionResult;
+
+    fn get_validation_tally(&mut self, payer: &Keypair, task_slot_id: u64) -> TransactionResult;
+
+    fn force_resolve_task(
+        &mut self,
+        resolver: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+    ) -> TransactionResult;
+
+    fn reconcile_session_locks(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+        task_slot_ids: &[u64],
+    ) -> TransactionResult;
+
+    fn sweep_abandoned_session(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+    ) -> TransactionResult;
+
+    fn prune_rejected_nodes(
+        &mut self,
+        authority: &Keypair,
+        node_pubkeys: &[Pubkey],
+    ) -> TransactionResult;
+
+    fn update_session_limits(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        max_task_cost_ceiling: Option<u64>,
+    ) -> TransactionResult;
+
+    fn seed_task_input(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        input_cid: String,
+    ) -> TransactionResult;
+
+    fn seed_task_input_with(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        input_cid: String,
+        configure: impl FnOnce(&mut SeedTaskInputBuilder),
+    ) -> TransactionResult;
+
+    fn batch_withdraw_node_rewards(
+        &mut self,
+        owner: &Keypair,
+        node_pubkeys: &[Pubkey],
+    ) -> TransactionResult;
+
+    fn merge_sessions(
+        &mut self,
+        owner: &Keypair,
+        source_session_slot_id: u64,
+        destination_session_slot_id: u64,
+        contributors: &[Pubkey],
+    ) -> TransactionResult;
+
+    fn propose_session_action(
+        &mut self,
+        proposer: &Keypair,
+        session_slot_id: u64,
+        action: SessionAction,
+    ) -> TransactionResult;
+
+    fn vote_session_action(&mut self, voter: &Keypair, session_slot_id: u64) -> TransactionResult;
+
+    fn release_task_settlement(
+        &mut self,
+        node: &Keypair,
+        task_slot_id: u64,
+        task_index: u64,
+    ) -> TransactionResult;
+
+    fn dispute_task_settlement(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        task_index: u64,
+    ) -> TransactionResult;
+
+    fn update_agent_memory(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        memory_cid: String,
+    ) -> TransactionResult;
+
+    fn migrate_network_config(&mut self, authority: &Keypair) -> TransactionResult;
+
+    fn migrate_account(&mut self, payer: &Keypair, account: &Pubkey) -> TransactionResult;
+
+    fn extend_tasks(&mut self, authority: &Keypair, count: u64) -> TransactionResult;
+
+    fn get_node_summary(&mut self, payer: &Keypair, node: &Pubkey) -> TransactionResult;
+
+    fn verify_genesis(&mut self, payer: &Keypair) -> TransactionResult;
+
+    fn submit_task_checkpoint(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        checkpoint_cid: String,
+    ) -> TransactionResult;
+
+    fn authority_invalidate_task(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+    ) -> TransactionResult;
+
+    fn set_node_capacity(
+        &mut self,
+        owner: &Keypair,
+        node_pubkey: &Pubkey,
+        max_concurrent_tasks: u32,
+    ) -> TransactionResult;
+
+    fn get_session_exposure(
+        &mut self,
+        payer: &Keypair,
+        session_slot_id: u64,
+        task_slot_ids: &[u64],
+    ) -> TransactionResult;
+
+    fn cancel_session(&mut self, owner: &Keypair, session_slot_id: u64) -> TransactionResult;
+
+    fn reclaim_cancelled_task(
+        &mut self,
+        signer: &Keypair,
+        session_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+    ) -> TransactionResult;
+
+    fn submit_batch_public_validation(
+        &mut self,
+        coordinator: &Keypair,
+        co_signers: &[&Keypair],
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+        payment_amount: u64,
+        session_completed: bool,
+    ) -> TransactionResult;
+
+    fn set_completion_hook(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        completion_hook_program: Option<Pubkey>,
+    ) -> TransactionResult;
+
+    fn get_node_treasury_balance(&mut self, payer: &Keypair, node: &Pubkey) -> TransactionResult;
+
+    fn set_accepting_contributions(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        accepting_contributions: bool,
+    ) -> TransactionResult;
 }
 
 impl Instructions for TestFixture {
     fn initialize_network(
+        &mut self,
+        authority: &Keypair,
+        network_config: &Pubkey,
+        cid_config: String,
+        allocate_tasks: u64,
+        approved_code_measurements: Vec<CodeMeasurement>,
+        required_validations: u32,
+        genesis_nonce: Option<[u8; 32]>,
+        remaining_accounts: &[AccountMeta],
+    ) -> TransactionResult {
+        self.initialize_network_with(
+            authority,
+            network_config,
+            cid_config,
+            allocate_tasks,
+            approved_code_measurements,
+            required_validations,
+            remaining_accounts,
+            |builder| {
+                if let Some(genesis_nonce) = genesis_nonce {
+                    builder.genesis_nonce(genesis_nonce);
+                }
+            },
+        )
+    }
+
+    fn initialize_network_with(
         &mut self,
         authority: &Keypair,
         network_config: &Pubkey,
@@ -163,6 +493,7 @@ impl Instructions for TestFixture {
         approved_code_measurements: Vec<CodeMeasurement>,
         required_validations: u32,
         remaining_accounts: &[AccountMeta],
+        configure: impl FnOnce(&mut InitializeNetworkBuilder),
     ) -> TransactionResult {
         let authority_pubkey = authority.pubkey();
 
@@ -175,12 +506,18 @@ impl Instructions for TestFixture {
             .approved_code_measurements(approved_code_measurements)
             .required_validations(required_validations);
 
+        configure(&mut builder);
+
         if !remaining_accounts.is_empty() {
             builder.add_remaining_accounts(remaining_accounts);
         }
 
+        // Every pre-allocated task is a CPI, the default budget runs out well before the
+        // account limit
+        let instructions =
+            with_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT, &[builder.instruction()]);
         self.svm
-            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+            .send_tx(&instructions, &authority_pubkey, &[authority])
     }
 
     fn register_node(
@@ -192,7 +529,9 @@ impl Instructions for TestFixture {
         let owner_pubkey = owner.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
         let (node_info_pda, _) = self.find_node_info_pda(node_pubkey);
-        let (node_treasury_pda, _) = self.find_node_treasury_pda(&node_info_pda);
+        let registration_epoch = self.get_network_config().node_registration_count;
+        let (node_treasury_pda, _) =
+            self.find_node_treasury_pda(&node_info_pda, registration_epoch);
 
         let mut builder = RegisterNodeBuilder::new();
         builder
@@ -314,6 +653,40 @@ impl Instructions for TestFixture {
             .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
     }
 
+    fn disable_node(&mut self, authority: &Keypair, node_pubkey: &Pubkey) -> TransactionResult {
+        let authority_pubkey = authority.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (node_info_pda, _) = self.find_node_info_pda(node_pubkey);
+
+        let mut builder = DisableNodeBuilder::new();
+        builder
+            .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .node_info(node_info_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+    }
+
+    fn authority_reject_node(
+        &mut self,
+        authority: &Keypair,
+        node_pubkey: &Pubkey,
+    ) -> TransactionResult {
+        let authority_pubkey = authority.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (node_info_pda, _) = self.find_node_info_pda(node_pubkey);
+
+        let mut builder = AuthorityRejectNodeBuilder::new();
+        builder
+            .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .node_info(node_info_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+    }
+
     fn create_agent(
         &mut self,
         agent_owner: &Keypair,
@@ -345,7 +718,17 @@ impl Instructions for TestFixture {
         is_owned: bool,
         is_confidential: bool,
     ) -> TransactionResult {
-        let owner_pubkey = owner.pubkey();
+        self.create_session_with(owner, is_owned, is_confidential, |_| {})
+    }
+
+    fn create_session_with(
+        &mut self,
+        owner: &Keypair,
+        is_owned: bool,
+        is_confidential: bool,
+        configure: impl FnOnce(&mut CreateSessionBuilder),
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
         let network_config = self.get_network_config();
         let session_slot_id = network_config.session_count;
@@ -363,10 +746,41 @@ impl Instructions for TestFixture {
             .is_owned(is_owned)
             .is_confidential(is_confidential);
 
+        configure(&mut builder);
+
         self.svm
             .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
     }
 
+    fn create_sponsored_session(
+        &mut self,
+        payer: &Keypair,
+        owner: &Keypair,
+        is_owned: bool,
+        is_confidential: bool,
+    ) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let network_config = self.get_network_config();
+        let (session_pda, _) =
+            self.find_session_pda(&network_config_pda, network_config.session_count);
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, network_config.task_count);
+
+        let mut builder = CreateSessionBuilder::new();
+        builder
+            .payer(payer_pubkey)
+            .owner(owner_pubkey)
+            .network_config(network_config_pda)
+            .session(session_pda)
+            .task(task_pda)
+            .is_owned(is_owned)
+            .is_confidential(is_confidential);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer, owner])
+    }
+
     fn set_session(
         &mut self,
         session_owner: &Keypair,
@@ -378,6 +792,33 @@ impl Instructions for TestFixture {
         initial_deposit: u64,
         compute_node: Pubkey,
         task_type: TaskType,
+    ) -> TransactionResult {
+        self.set_session_with(
+            session_owner,
+            session_slot_id,
+            specification_cid,
+            max_iterations,
+            agent_slot_id,
+            task_slot_id,
+            initial_deposit,
+            compute_node,
+            task_type,
+            |_, _| {},
+        )
+    }
+
+    fn set_session_with(
+        &mut self,
+        session_owner: &Keypair,
+        session_slot_id: u64,
+        specification_cid: String,
+        max_iterations: u64,
+        agent_slot_id: u64,
+        task_slot_id: u64,
+        initial_deposit: u64,
+        compute_node: Pubkey,
+        task_type: TaskType,
+        configure: impl FnOnce(&mut SetSessionBuilder, &mut SetSessionArgs),
     ) -> TransactionResult {
         let session_owner_pubkey = session_owner.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
@@ -396,12 +837,21 @@ impl Instructions for TestFixture {
             .owner_contribution(owner_contribution_pda)
             .task(task_pda)
             .agent(agent_pda)
-            .network_config(network_config_pda)
-            .specification_cid(specification_cid)
-            .max_iterations(max_iterations)
-            .initial_deposit(initial_deposit)
-            .compute_node(compute_node)
-            .task_type(task_type);
+            .network_config(network_config_pda);
+
+        let mut args = SetSessionArgs {
+            specification_cid,
+            max_iterations,
+            initial_deposit,
+            compute_node,
+            task_type,
+            max_output_size: None,
+            owner_fee_bps: None,
+            estimated_compute_units: None,
+            allowed_compute_nodes: None,
+        };
+        configure(&mut builder, &mut args);
+        builder.args(args);
 
         self.svm
             .send_tx(&[builder.instruction()], &session_owner_pubkey, &[session_owner])
@@ -412,6 +862,16 @@ impl Instructions for TestFixture {
         contributor: &Keypair,
         session_slot_id: u64,
         deposit_amount: u64,
+    ) -> TransactionResult {
+        self.contribute_to_session_with(contributor, session_slot_id, deposit_amount, |_| {})
+    }
+
+    fn contribute_to_session_with(
+        &mut self,
+        contributor: &Keypair,
+        session_slot_id: u64,
+        deposit_amount: u64,
+        configure: impl FnOnce(&mut ContributeToSessionBuilder),
     ) -> TransactionResult {
         let contributor_pubkey = contributor.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
@@ -428,6 +888,8 @@ impl Instructions for TestFixture {
             .network_config(network_config_pda)
             .deposit_amount(deposit_amount);
 
+        configure(&mut builder);
+
         self.svm.send_tx(
             &[builder.instruction()],
             &contributor_pubkey,
@@ -446,6 +908,7 @@ impl Instructions for TestFixture {
         let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
         let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
         let (contribution_pda, _) = self.find_contribution_pda(&session_pda, &contributor_pubkey);
+        let (session_proposal_pda, _) = self.find_session_proposal_pda(&session_pda);
 
         let mut builder = WithdrawFromSessionBuilder::new();
         builder
@@ -453,6 +916,7 @@ impl Instructions for TestFixture {
             .session(session_pda)
             .vault(vault_pda)
             .contribution(contribution_pda)
+            .session_proposal(session_proposal_pda)
             .network_config(network_config_pda)
             .shares_to_burn(shares_to_burn);
 
@@ -470,23 +934,48 @@ impl Instructions for TestFixture {
         task_slot_id: u64,
         max_task_cost: u64,
         max_call_count: u64,
+    ) -> TransactionResult {
+        self.claim_task_with(
+            compute_node,
+            session_slot_id,
+            task_slot_id,
+            max_task_cost,
+            max_call_count,
+            |_| {},
+        )
+    }
+
+    fn claim_task_with(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        max_task_cost: u64,
+        max_call_count: u64,
+        configure: impl FnOnce(&mut ClaimTaskBuilder),
     ) -> TransactionResult {
         let compute_node_pubkey = compute_node.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
         let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
         let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
         let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
+        let (node_info_pda, _) = self.find_node_info_pda(&compute_node_pubkey);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
 
         let mut builder = ClaimTaskBuilder::new();
         builder
             .compute_node(compute_node_pubkey)
+            .node_info(node_info_pda)
             .task(task_pda)
             .session(session_pda)
+            .agent(agent_pda)
             .vault(vault_pda)
             .network_config(network_config_pda)
             .max_task_cost(max_task_cost)
             .max_call_count(max_call_count);
 
+        configure(&mut builder);
+
         self.svm.send_tx(
             &[builder.instruction()],
             &compute_node_pubkey,
@@ -503,6 +992,29 @@ impl Instructions for TestFixture {
         output_cid: String,
         state_cid: Option<String>,
         call_count: u64,
+    ) -> TransactionResult {
+        self.submit_task_result_with(
+            compute_node,
+            session_slot_id,
+            task_slot_id,
+            input_cid,
+            output_cid,
+            state_cid,
+            call_count,
+            |_| {},
+        )
+    }
+
+    fn submit_task_result_with(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        input_cid: String,
+        output_cid: String,
+        state_cid: Option<String>,
+        call_count: u64,
+        configure: impl FnOnce(&mut SubmitTaskResultBuilder),
     ) -> TransactionResult {
         let compute_node_pubkey = compute_node.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
@@ -524,6 +1036,8 @@ impl Instructions for TestFixture {
 
         builder.call_count(call_count);
 
+        configure(&mut builder);
+
         self.svm.send_tx(
             &[builder.instruction()],
             &compute_node_pubkey,
@@ -538,6 +1052,23 @@ impl Instructions for TestFixture {
         task_slot_id: u64,
         compute_node_pubkey: &Pubkey,
         ed25519_ix: &Instruction,
+    ) -> TransactionResult {
+        self.submit_confidential_task_validation_after(
+            node_validating,
+            session_slot_id,
+            task_slot_id,
+            compute_node_pubkey,
+            &[ed25519_ix.clone()],
+        )
+    }
+
+    fn submit_confidential_task_validation_after(
+        &mut self,
+        node_validating: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+        instructions: &[Instruction],
     ) -> TransactionResult {
         let validator_pubkey = node_validating.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
@@ -545,8 +1076,12 @@ impl Instructions for TestFixture {
         let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
         let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
         let (compute_node_info_pda, _) = self.find_node_info_pda(compute_node_pubkey);
-        let (node_treasury_pda, _) = self.find_node_treasury_pda(&compute_node_info_pda);
+        let registration_epoch = self.get_node_info(compute_node_pubkey).registration_epoch;
+        let (node_treasury_pda, _) =
+            self.find_node_treasury_pda(&compute_node_info_pda, registration_epoch);
         let (validator_node_info_pda, _) = self.find_node_info_pda(&validator_pubkey);
+        let (protocol_treasury_pda, _) = self.find_protocol_treasury_pda(&network_config_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
 
         let mut builder = SubmitConfidentialTaskValidationBuilder::new();
         builder
@@ -554,19 +1089,19 @@ impl Instructions for TestFixture {
             .session(session_pda)
             .vault(vault_pda)
             .task(task_pda)
+            .agent(agent_pda)
             .node_info(compute_node_info_pda)
             .node_treasury(node_treasury_pda)
+            .protocol_treasury(protocol_treasury_pda)
             .validator_node_info(validator_node_info_pda)
             .network_config(network_config_pda)
             .instruction_sysvar(solana_sdk::sysvar::instructions::id());
 
-        let validate_ix = builder.instruction();
+        let mut tx_instructions = instructions.to_vec();
+        tx_instructions.push(builder.instruction());
 
-        self.svm.send_tx(
-            &[ed25519_ix.clone(), validate_ix],
-            &validator_pubkey,
-            &[node_validating],
-        )
+        self.svm
+            .send_tx(&tx_instructions, &validator_pubkey, &[node_validating])
     }
 
     fn submit_public_task_validation(
@@ -578,6 +1113,29 @@ impl Instructions for TestFixture {
         payment_amount: u64,
         approved: bool,
         session_completed: bool,
+    ) -> TransactionResult {
+        self.submit_public_task_validation_with(
+            node_validating,
+            session_slot_id,
+            task_slot_id,
+            compute_node_pubkey,
+            payment_amount,
+            approved,
+            session_completed,
+            |_| {},
+        )
+    }
+
+    fn submit_public_task_validation_with(
+        &mut self,
+        node_validating: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+        payment_amount: u64,
+        approved: bool,
+        session_completed: bool,
+        configure: impl FnOnce(&mut SubmitPublicTaskValidationBuilder),
     ) -> TransactionResult {
         let node_validating_pubkey = node_validating.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
@@ -585,8 +1143,12 @@ impl Instructions for TestFixture {
         let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
         let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
         let (compute_node_info_pda, _) = self.find_node_info_pda(compute_node_pubkey);
-        let (node_treasury_pda, _) = self.find_node_treasury_pda(&compute_node_info_pda);
+        let registration_epoch = self.get_node_info(compute_node_pubkey).registration_epoch;
+        let (node_treasury_pda, _) =
+            self.find_node_treasury_pda(&compute_node_info_pda, registration_epoch);
         let (node_validating_info_pda, _) = self.find_node_info_pda(&node_validating_pubkey);
+        let (protocol_treasury_pda, _) = self.find_protocol_treasury_pda(&network_config_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
 
         let mut builder = SubmitPublicTaskValidationBuilder::new();
         builder
@@ -594,8 +1156,10 @@ impl Instructions for TestFixture {
             .session(session_pda)
             .vault(vault_pda)
             .task(task_pda)
+            .agent(agent_pda)
             .node_info(compute_node_info_pda)
             .node_treasury(node_treasury_pda)
+            .protocol_treasury(protocol_treasury_pda)
             .validator_node_info(node_validating_info_pda)
             .network_config(network_config_pda)
             .instruction_sysvar(solana_sdk::sysvar::instructions::id())
@@ -603,6 +1167,8 @@ impl Instructions for TestFixture {
             .approved(approved)
             .goal_completed(session_completed);
 
+        configure(&mut builder);
+
         let validate_ix = builder.instruction();
 
         self.svm
@@ -614,24 +1180,797 @@ impl Instructions for TestFixture {
         authority: &Keypair,
         cid_config: Option<String>,
         new_code_measurement: Option<CodeMeasurement>,
+    ) -> TransactionResult {
+        self.update_network_config_with(authority, |args| {
+            args.cid_config = cid_config;
+            args.new_code_measurement = new_code_measurement;
+        })
+    }
+
+    fn update_network_config_with(
+        &mut self,
+        authority: &Keypair,
+        configure: impl FnOnce(&mut UpdateNetworkConfigArgs),
     ) -> TransactionResult {
         let authority_pubkey = authority.pubkey();
         let network_config_pda = self.find_network_config_pda().0;
 
+        let mut args = UpdateNetworkConfigArgs {
+            cid_config: None,
+            new_code_measurement: None,
+            confidential_public_fallback: None,
+            reject_duplicate_results: None,
+            agent_required_validations: None,
+            validation_stall_slots: None,
+            payment_mint: None,
+            allowed_cid_prefixes: None,
+            abandonment_slots: None,
+            rejection_reward: None,
+            agent_creation_allowlist: None,
+            max_sessions_per_agent: None,
+            validation_window_slots: None,
+            min_slots_between_claims: None,
+            validator_share_bps: None,
+            protocol_share_bps: None,
+            agent_validators_must_be_confidential: None,
+            settlement_hold_slots: None,
+            min_slots_between_iterations: None,
+            debug_validation_logs: None,
+            max_vault_balance: None,
+            processing_window_slots: None,
+            min_measurement_version: None,
+            paused: None,
+            max_ownership_bps: None,
+        };
+        configure(&mut args);
+
         let mut builder = UpdateNetworkConfigBuilder::new();
         builder
             .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .args(args);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+    }
+
+    fn get_network_stats(&mut self, payer: &Keypair) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+
+        let mut builder = GetNetworkStatsBuilder::new();
+        builder.network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn is_node_approved(&mut self, payer: &Keypair, node: &Pubkey) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+
+        let mut builder = IsNodeApprovedBuilder::new();
+        builder.network_config(network_config_pda).node(*node);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn list_code_measurements(&mut self, payer: &Keypair) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+
+        let mut builder = ListCodeMeasurementsBuilder::new();
+        builder.network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn get_validation_tally(&mut self, payer: &Keypair, task_slot_id: u64) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+
+        let mut builder = GetValidationTallyBuilder::new();
+        builder.task(task_pda).network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn get_share_precision(&mut self, payer: &Keypair) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+
+        let builder = GetSharePrecisionBuilder::new();
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn force_resolve_task(
+        &mut self,
+        resolver: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+    ) -> TransactionResult {
+        let resolver_pubkey = resolver.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+        let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
+        let (compute_node_info_pda, _) = self.find_node_info_pda(compute_node_pubkey);
+        let registration_epoch = self.get_node_info(compute_node_pubkey).registration_epoch;
+        let (node_treasury_pda, _) =
+            self.find_node_treasury_pda(&compute_node_info_pda, registration_epoch);
+        let (protocol_treasury_pda, _) = self.find_protocol_treasury_pda(&network_config_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
+
+        let mut builder = ForceResolveTaskBuilder::new();
+        builder
+            .resolver(resolver_pubkey)
+            .session(session_pda)
+            .vault(vault_pda)
+            .task(task_pda)
+            .agent(agent_pda)
+            .node_info(compute_node_info_pda)
+            .node_treasury(node_treasury_pda)
+            .protocol_treasury(protocol_treasury_pda)
             .network_config(network_config_pda);
 
-        if let Some(cid) = cid_config {
-            builder.cid_config(cid);
-        }
+        self.svm
+            .send_tx(&[builder.instruction()], &resolver_pubkey, &[resolver])
+    }
 
-        if let Some(measurement) = new_code_measurement {
-            builder.new_code_measurement(measurement);
-        }
+    fn reconcile_session_locks(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+        task_slot_ids: &[u64],
+    ) -> TransactionResult {
+        let authority_pubkey = authority.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let task_accounts: Vec<AccountMeta> = task_slot_ids
+            .iter()
+            .map(|task_slot_id| {
+                AccountMeta::new_readonly(self.find_task_pda(&network_config_pda, *task_slot_id).0, false)
+            })
+            .collect();
+
+        let mut builder = ReconcileSessionLocksBuilder::new();
+        builder
+            .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .session(session_pda)
+            .add_remaining_accounts(&task_accounts);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+    }
+
+    fn sweep_abandoned_session(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+    ) -> TransactionResult {
+        let authority_pubkey = authority.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
+        let (protocol_treasury_pda, _) = self.find_protocol_treasury_pda(&network_config_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
+
+        let mut builder = SweepAbandonedSessionBuilder::new();
+        builder
+            .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .session(session_pda)
+            .vault(vault_pda)
+            .agent(agent_pda)
+            .protocol_treasury(protocol_treasury_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+    }
+
+    fn prune_rejected_nodes(
+        &mut self,
+        authority: &Keypair,
+        node_pubkeys: &[Pubkey],
+    ) -> TransactionResult {
+        let authority_pubkey = authority.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let node_accounts: Vec<AccountMeta> = node_pubkeys
+            .iter()
+            .flat_map(|node_pubkey| {
+                let node_info = self.get_node_info(node_pubkey);
+                [
+                    AccountMeta::new(self.find_node_info_pda(node_pubkey).0, false),
+                    AccountMeta::new(node_info.node_treasury, false),
+                    AccountMeta::new(node_info.owner, false),
+                ]
+            })
+            .collect();
+
+        let mut builder = PruneRejectedNodesBuilder::new();
+        builder
+            .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .add_remaining_accounts(&node_accounts);
 
         self.svm
             .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
     }
+
+    fn update_session_limits(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        max_task_cost_ceiling: Option<u64>,
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let task_slot_id = self.find_session_task_slot_id(session_slot_id);
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+
+        let mut builder = UpdateSessionLimitsBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .session(session_pda)
+            .task(task_pda)
+            .network_config(network_config_pda);
+        if let Some(max_task_cost_ceiling) = max_task_cost_ceiling {
+            builder.max_task_cost_ceiling(max_task_cost_ceiling);
+        }
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
+
+    fn seed_task_input(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        input_cid: String,
+    ) -> TransactionResult {
+        self.seed_task_input_with(owner, session_slot_id, input_cid, |_| {})
+    }
+
+    fn seed_task_input_with(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        input_cid: String,
+        configure: impl FnOnce(&mut SeedTaskInputBuilder),
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let task_slot_id = self.find_session_task_slot_id(session_slot_id);
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+
+        let mut builder = SeedTaskInputBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .session(session_pda)
+            .task(task_pda)
+            .network_config(network_config_pda)
+            .input_cid(input_cid);
+
+        configure(&mut builder);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
+
+    fn batch_withdraw_node_rewards(
+        &mut self,
+        owner: &Keypair,
+        node_pubkeys: &[Pubkey],
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let node_accounts: Vec<AccountMeta> = node_pubkeys
+            .iter()
+            .flat_map(|node_pubkey| {
+                let node_treasury = self.get_node_info(node_pubkey).node_treasury;
+                [
+                    AccountMeta::new_readonly(self.find_node_info_pda(node_pubkey).0, false),
+                    AccountMeta::new(node_treasury, false),
+                ]
+            })
+            .collect();
+
+        let mut builder = BatchWithdrawNodeRewardsBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .add_remaining_accounts(&node_accounts);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
+
+    fn merge_sessions(
+        &mut self,
+        owner: &Keypair,
+        source_session_slot_id: u64,
+        destination_session_slot_id: u64,
+        contributors: &[Pubkey],
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (source_session_pda, _) =
+            self.find_session_pda(&network_config_pda, source_session_slot_id);
+        let (destination_session_pda, _) =
+            self.find_session_pda(&network_config_pda, destination_session_slot_id);
+        let contribution_accounts: Vec<AccountMeta> = contributors
+            .iter()
+            .flat_map(|contributor| {
+                let (source_contribution, _) =
+                    self.find_contribution_pda(&source_session_pda, contributor);
+                let (destination_contribution, _) =
+                    self.find_contribution_pda(&destination_session_pda, contributor);
+                [
+                    AccountMeta::new(source_contribution, false),
+                    AccountMeta::new(destination_contribution, false),
+                ]
+            })
+            .collect();
+
+        let mut builder = MergeSessionsBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .source_session(source_session_pda)
+            .source_vault(self.find_session_vault_pda(&source_session_pda).0)
+            .destination_session(destination_session_pda)
+            .destination_vault(self.find_session_vault_pda(&destination_session_pda).0)
+            .network_config(network_config_pda)
+            .add_remaining_accounts(&contribution_accounts);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
+
+    fn propose_session_action(
+        &mut self,
+        proposer: &Keypair,
+        session_slot_id: u64,
+        action: SessionAction,
+    ) -> TransactionResult {
+        let proposer_pubkey = proposer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let (contribution_pda, _) = self.find_contribution_pda(&session_pda, &proposer_pubkey);
+        let (proposal_pda, _) = self.find_session_proposal_pda(&session_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
+
+        let mut builder = ProposeSessionActionBuilder::new();
+        builder
+            .proposer(proposer_pubkey)
+            .session(session_pda)
+            .contribution(contribution_pda)
+            .proposal(proposal_pda)
+            .agent(agent_pda)
+            .network_config(network_config_pda)
+            .action(action);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &proposer_pubkey, &[proposer])
+    }
+
+    fn vote_session_action(&mut self, voter: &Keypair, session_slot_id: u64) -> TransactionResult {
+        let voter_pubkey = voter.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let (contribution_pda, _) = self.find_contribution_pda(&session_pda, &voter_pubkey);
+        let (proposal_pda, _) = self.find_session_proposal_pda(&session_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
+
+        let mut builder = VoteSessionActionBuilder::new();
+        builder
+            .voter(voter_pubkey)
+            .session(session_pda)
+            .contribution(contribution_pda)
+            .proposal(proposal_pda)
+            .agent(agent_pda)
+            .network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &voter_pubkey, &[voter])
+    }
+
+    fn release_task_settlement(
+        &mut self,
+        node: &Keypair,
+        task_slot_id: u64,
+        task_index: u64,
+    ) -> TransactionResult {
+        let node_pubkey = node.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+        let (settlement_pda, _) = self.find_task_settlement_pda(&task_pda, task_index);
+        let settlement = self.get_task_settlement(&task_pda, task_index);
+        let node_info = self.get_node_info(&node_pubkey);
+
+        let mut builder = ReleaseTaskSettlementBuilder::new();
+        builder
+            .node(node_pubkey)
+            .node_info(settlement.node_info)
+            .node_treasury(node_info.node_treasury)
+            .task_settlement(settlement_pda)
+            .payer(settlement.payer);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &node_pubkey, &[node])
+    }
+
+    fn dispute_task_settlement(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+        task_slot_id: u64,
+        task_index: u64,
+    ) -> TransactionResult {
+        let authority_pubkey = authority.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+        let (settlement_pda, _) = self.find_task_settlement_pda(&task_pda, task_index);
+        let settlement = self.get_task_settlement(&task_pda, task_index);
+
+        let mut builder = DisputeTaskSettlementBuilder::new();
+        builder
+            .authority(authority_pubkey)
+            .network_config(network_config_pda)
+            .session(session_pda)
+            .vault(vault_pda)
+            .node_info(settlement.node_info)
+            .task_settlement(settlement_pda)
+            .payer(settlement.payer);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority_pubkey, &[authority])
+    }
+
+    fn update_agent_memory(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        memory_cid: String,
+    ) -> TransactionResult {
+        let compute_node_pubkey = compute_node.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let session = self.get_session(&network_config_pda, session_slot_id);
+
+        let mut builder = UpdateAgentMemoryBuilder::new();
+        builder
+            .compute_node(compute_node_pubkey)
+            .task(session.task)
+            .session(session_pda)
+            .agent(session.agent)
+            .network_config(network_config_pda)
+            .memory_cid(memory_cid);
+
+        self.svm.send_tx(
+            &[builder.instruction()],
+            &compute_node_pubkey,
+            &[compute_node],
+        )
+    }
+
+    fn migrate_network_config(&mut self, authority: &Keypair) -> TransactionResult {
+        let network_config_pda = self.find_network_config_pda().0;
+
+        let mut builder = MigrateNetworkConfigBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority.pubkey(), &[authority])
+    }
+
+    fn migrate_account(&mut self, payer: &Keypair, account: &Pubkey) -> TransactionResult {
+        let mut builder = MigrateAccountBuilder::new();
+        builder.payer(payer.pubkey()).account(*account);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer.pubkey(), &[payer])
+    }
+
+    fn extend_tasks(&mut self, authority: &Keypair, count: u64) -> TransactionResult {
+        let network_config_pda = self.find_network_config_pda().0;
+        let task_count = self.get_network_config().task_count;
+        let task_pdas = self.create_task_pdas_from(&network_config_pda, task_count, count);
+
+        let mut builder = ExtendTasksBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .network_config(network_config_pda)
+            .count(count)
+            .add_remaining_accounts(&task_pdas);
+
+        let instructions =
+            with_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT, &[builder.instruction()]);
+        self.svm
+            .send_tx(&instructions, &authority.pubkey(), &[authority])
+    }
+
+    fn get_node_summary(&mut self, payer: &Keypair, node: &Pubkey) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (node_info_pda, _) = self.find_node_info_pda(node);
+
+        let mut builder = GetNodeSummaryBuilder::new();
+        builder
+            .node_info(node_info_pda)
+            .network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn verify_genesis(&mut self, payer: &Keypair) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+
+        let mut builder = VerifyGenesisBuilder::new();
+        builder.network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn submit_task_checkpoint(
+        &mut self,
+        compute_node: &Keypair,
+        session_slot_id: u64,
+        checkpoint_cid: String,
+    ) -> TransactionResult {
+        let compute_node_pubkey = compute_node.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let session = self.get_session(&network_config_pda, session_slot_id);
+
+        let mut builder = SubmitTaskCheckpointBuilder::new();
+        builder
+            .compute_node(compute_node_pubkey)
+            .task(session.task)
+            .session(session_pda)
+            .network_config(network_config_pda)
+            .checkpoint_cid(checkpoint_cid);
+
+        self.svm.send_tx(
+            &[builder.instruction()],
+            &compute_node_pubkey,
+            &[compute_node],
+        )
+    }
+
+    fn authority_invalidate_task(
+        &mut self,
+        authority: &Keypair,
+        session_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+    ) -> TransactionResult {
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let session = self.get_session(&network_config_pda, session_slot_id);
+        let (node_info_pda, _) = self.find_node_info_pda(compute_node_pubkey);
+
+        let mut builder = AuthorityInvalidateTaskBuilder::new();
+        builder
+            .authority(authority.pubkey())
+            .network_config(network_config_pda)
+            .session(session_pda)
+            .task(session.task)
+            .node_info(node_info_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &authority.pubkey(), &[authority])
+    }
+
+    fn set_node_capacity(
+        &mut self,
+        owner: &Keypair,
+        node_pubkey: &Pubkey,
+        max_concurrent_tasks: u32,
+    ) -> TransactionResult {
+        let (node_info_pda, _) = self.find_node_info_pda(node_pubkey);
+
+        let mut builder = SetNodeCapacityBuilder::new();
+        builder
+            .owner(owner.pubkey())
+            .node_info(node_info_pda)
+            .max_concurrent_tasks(max_concurrent_tasks);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner.pubkey(), &[owner])
+    }
+
+    fn get_session_exposure(
+        &mut self,
+        payer: &Keypair,
+        session_slot_id: u64,
+        task_slot_ids: &[u64],
+    ) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let task_accounts: Vec<AccountMeta> = task_slot_ids
+            .iter()
+            .map(|task_slot_id| {
+                let (task_pda, _) = self.find_task_pda(&network_config_pda, *task_slot_id);
+                AccountMeta::new_readonly(task_pda, false)
+            })
+            .collect();
+
+        let mut builder = GetSessionExposureBuilder::new();
+        builder
+            .session(session_pda)
+            .network_config(network_config_pda)
+            .add_remaining_accounts(&task_accounts);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn cancel_session(&mut self, owner: &Keypair, session_slot_id: u64) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
+
+        let mut builder = CancelSessionBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .session(session_pda)
+            .agent(agent_pda)
+            .network_config(network_config_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
+
+    fn reclaim_cancelled_task(
+        &mut self,
+        signer: &Keypair,
+        session_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+    ) -> TransactionResult {
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let session = self.get_session(&network_config_pda, session_slot_id);
+        let (node_info_pda, _) = self.find_node_info_pda(compute_node_pubkey);
+
+        let mut builder = ReclaimCancelledTaskBuilder::new();
+        builder
+            .signer(signer.pubkey())
+            .network_config(network_config_pda)
+            .session(session_pda)
+            .task(session.task)
+            .node_info(node_info_pda);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &signer.pubkey(), &[signer])
+    }
+
+    fn submit_batch_public_validation(
+        &mut self,
+        coordinator: &Keypair,
+        co_signers: &[&Keypair],
+        session_slot_id: u64,
+        task_slot_id: u64,
+        compute_node_pubkey: &Pubkey,
+        payment_amount: u64,
+        session_completed: bool,
+    ) -> TransactionResult {
+        let coordinator_pubkey = coordinator.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+        let (task_pda, _) = self.find_task_pda(&network_config_pda, task_slot_id);
+        let (vault_pda, _) = self.find_session_vault_pda(&session_pda);
+        let (compute_node_info_pda, _) = self.find_node_info_pda(compute_node_pubkey);
+        let registration_epoch = self.get_node_info(compute_node_pubkey).registration_epoch;
+        let (node_treasury_pda, _) =
+            self.find_node_treasury_pda(&compute_node_info_pda, registration_epoch);
+        let (coordinator_info_pda, _) = self.find_node_info_pda(&coordinator_pubkey);
+        let (protocol_treasury_pda, _) = self.find_protocol_treasury_pda(&network_config_pda);
+        let agent_pda = self.get_session(&network_config_pda, session_slot_id).agent;
+        let vote_accounts: Vec<AccountMeta> = co_signers
+            .iter()
+            .flat_map(|validator| {
+                let (validator_info_pda, _) = self.find_node_info_pda(&validator.pubkey());
+                [
+                    AccountMeta::new_readonly(validator.pubkey(), true),
+                    AccountMeta::new(validator_info_pda, false),
+                ]
+            })
+            .collect();
+
+        let mut builder = SubmitBatchPublicValidationBuilder::new();
+        builder
+            .node_validating(coordinator_pubkey)
+            .session(session_pda)
+            .vault(vault_pda)
+            .task(task_pda)
+            .agent(agent_pda)
+            .node_info(compute_node_info_pda)
+            .node_treasury(node_treasury_pda)
+            .protocol_treasury(protocol_treasury_pda)
+            .validator_node_info(coordinator_info_pda)
+            .network_config(network_config_pda)
+            .instruction_sysvar(solana_sdk::sysvar::instructions::id())
+            .payment_amount(payment_amount)
+            .goal_completed(session_completed)
+            .co_signers(co_signers.len() as u8)
+            .add_remaining_accounts(&vote_accounts);
+
+        let mut signers = vec![coordinator];
+        signers.extend_from_slice(co_signers);
+        self.svm
+            .send_tx(&[builder.instruction()], &coordinator_pubkey, &signers)
+    }
+
+    fn set_completion_hook(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        completion_hook_program: Option<Pubkey>,
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+
+        let mut builder = SetCompletionHookBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .session(session_pda)
+            .network_config(network_config_pda);
+        if let Some(completion_hook_program) = completion_hook_program {
+            builder.completion_hook_program(completion_hook_program);
+        }
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
+
+    fn get_node_treasury_balance(&mut self, payer: &Keypair, node: &Pubkey) -> TransactionResult {
+        let payer_pubkey = payer.pubkey();
+        let (node_info_pda, _) = self.find_node_info_pda(node);
+        let node_treasury = self.get_node_info(node).node_treasury;
+
+        let mut builder = GetNodeTreasuryBalanceBuilder::new();
+        builder
+            .node_info(node_info_pda)
+            .node_treasury(node_treasury);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &payer_pubkey, &[payer])
+    }
+
+    fn set_accepting_contributions(
+        &mut self,
+        owner: &Keypair,
+        session_slot_id: u64,
+        accepting_contributions: bool,
+    ) -> TransactionResult {
+        let owner_pubkey = owner.pubkey();
+        let network_config_pda = self.find_network_config_pda().0;
+        let (session_pda, _) = self.find_session_pda(&network_config_pda, session_slot_id);
+
+        let mut builder = SetAcceptingContributionsBuilder::new();
+        builder
+            .owner(owner_pubkey)
+            .session(session_pda)
+            .network_config(network_config_pda)
+            .accepting_contributions(accepting_contributions);
+
+        self.svm
+            .send_tx(&[builder.instruction()], &owner_pubkey, &[owner])
+    }
 }
@@ -4,11 +4,16 @@ use sha2::Digest;
 // Program paths and IDs
 pub const DAC_KEYPAIR_PATH: &str = "target/deploy/dac-keypair.json";
 pub const DAC_SO_PATH: &str = "target/deploy/dac.so";
+pub const COMPLETION_HOOK_MOCK_SO_PATH: &str = "target/deploy/completion_hook_mock.so";
+pub const COMPLETION_HOOK_MOCK_PROGRAM_ID: solana_sdk::pubkey::Pubkey =
+    solana_sdk::pubkey!("3qYy7zFCpHJojtBQdNToBWq4w3Ljhx1twvtvWZyXpjzx");
 
 //test data
 pub const DEFAULT_CID_CONFIG: &str = "QmDefaultConfig";
 pub const DEFAULT_ALLOCATE_GOALS: u64 = 2;
 pub const DEFAULT_ALLOCATE_TASKS: u64 = 3;
+// Task PDAs per extend_tasks transaction, well inside the 1232 byte packet
+pub const MAX_TASKS_PER_EXTEND: u64 = 20;
 pub const DEFAULT_GENESIS_HASH: [u8; 32] = [0u8; 32];
 pub const DEFAULT_APPROVED_CODE_MEASUREMENTS: [CodeMeasurement; 1] = [CodeMeasurement {
     measurement: [1u8; 32],
@@ -23,6 +28,16 @@ pub fn compute_genesis_hash() -> [u8; 32] {
     hasher.update(b"DAC_GENESIS");
     hasher.finalize().into()
 }
+pub fn compute_genesis_hash_with_nonce(
+    authority: &solana_sdk::pubkey::Pubkey,
+    genesis_nonce: [u8; 32],
+) -> [u8; 32] {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(b"DAC_GENESIS");
+    hasher.update(authority.as_ref());
+    hasher.update(genesis_nonce);
+    hasher.finalize().into()
+}
 
 // Node test data
 pub const DEFAULT_NODE_INFO_CID: &str = "QmNodeInfoCID";
@@ -35,6 +50,16 @@ pub const DEFAULT_AGENT_CONFIG_CID: &str = "QmAgentConfigCID";
 pub const DEFAULT_GOAL_SPECIFICATION_CID: &str = "QmGoalSpecificationCID";
 pub const DEFAULT_INITIAL_DEPOSIT: u64 = 1_000_000_000; // 1 SOL
 pub const DEFAULT_CONTRIBUTION_AMOUNT: u64 = 500_000_000; // 0.5 SOL
+pub const MAX_SPECIFICATION_CID_LEN: usize = 128;
+pub const SESSION_PROPOSAL_SLOTS: u64 = 216_000;
+
+// Share accounting test data
+pub const SHARE_PRECISION: u64 = 1_000_000_000;
 
 // Network config test data
 pub const DEFAULT_REQUIRED_VALIDATIONS: u32 = 1;
+pub const MAX_CODE_MEASUREMENTS: usize = 10;
+
+// Token payment test data
+pub const TOKEN_PROGRAM_ID: solana_sdk::pubkey::Pubkey =
+    solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
@@ -0,0 +1,242 @@
+//! Randomized audit of session share accounting.
+//!
+//! Each seed drives a public session through a random sequence of contributions,
+//! withdrawals, task claims and validated payments, checking the share and vault
+//! invariants after every step. Set `DAC_SHARE_AUDIT_SEED` to replay a single seed.
+
+use crate::setup::test_data::DEFAULT_CONTRIBUTION_AMOUNT;
+use crate::setup::{Accounts, Instructions, TestFixture};
+use dac_client::accounts::Contribution;
+use dac_client::{TaskStatus, TaskType};
+use litesvm::types::TransactionResult;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{Keypair, Signer};
+use utils::Utils;
+
+mod setup;
+
+const AUDIT_SEEDS: u64 = 8;
+const AUDIT_STEPS: usize = 40;
+const EXTRA_CONTRIBUTORS: usize = 2;
+const SESSION_SLOT_ID: u64 = 0;
+
+/// Error logs that must never appear, whatever the step outcome.
+const ARITHMETIC_FAILURES: [&str; 3] =
+    ["Error Code: Overflow", "Error Code: Underflow", "panicked"];
+
+/// xorshift64*, enough to vary step order without pulling in a rand dependency.
+struct AuditRng(u64);
+
+impl AuditRng {
+    fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Uniform-ish value in `[low, high]`.
+    fn range(&mut self, low: u64, high: u64) -> u64 {
+        if high <= low {
+            return low;
+        }
+        low + self.next_u64() % (high - low + 1)
+    }
+
+    fn pick(&mut self, len: usize) -> usize {
+        (self.next_u64() % len as u64) as usize
+    }
+}
+
+enum AuditStep {
+    Contribute,
+    Withdraw,
+    ClaimTask,
+    PayTask,
+}
+
+fn audit_fixture() -> TestFixture {
+    TestFixture::new()
+        .with_initialize_network()
+        .with_register_public_node()
+        .with_claim_public_node()
+        .with_register_validator_node()
+        .with_claim_validator_node()
+        .with_validate_public_node(true)
+        .with_validate_validator_node(true)
+        .with_create_agent()
+        .with_validated_agent(0)
+        .with_create_session(false)
+        .with_set_session_using_public_compute(SESSION_SLOT_ID, 0, TaskType::Completion(0))
+}
+
+fn assert_no_arithmetic_failure(seed: u64, step: usize, result: &TransactionResult) {
+    if let Err(failed) = result {
+        for log in &failed.meta.logs {
+            assert!(
+                !ARITHMETIC_FAILURES
+                    .iter()
+                    .any(|marker| log.contains(marker)),
+                "seed {seed} step {step}: arithmetic failure in program logs: {log}"
+            );
+        }
+    }
+}
+
+fn find_contribution(fixt: &TestFixture, contributor: &Keypair) -> Option<Contribution> {
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, SESSION_SLOT_ID);
+    let (contribution_pda, _) = fixt.find_contribution_pda(&session_pda, &contributor.pubkey());
+
+    fixt.svm
+        .get_account(&contribution_pda)
+        .map(|account| Contribution::from_bytes(&account.data).expect("Invalid Contribution"))
+}
+
+fn assert_share_invariants(fixt: &TestFixture, participants: &[Keypair], seed: u64, step: usize) {
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let session = fixt.get_session(&network_config_pda, SESSION_SLOT_ID);
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, SESSION_SLOT_ID);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+
+    let contributed_shares = participants
+        .iter()
+        .filter_map(|participant| find_contribution(fixt, participant))
+        .try_fold(0u64, |total, contribution| {
+            total.checked_add(contribution.shares)
+        })
+        .expect("contribution shares overflow u64");
+    assert_eq!(
+        contributed_shares, session.total_shares,
+        "seed {seed} step {step}: contribution shares diverged from session total"
+    );
+
+    let rent_exempt_minimum = fixt.svm.minimum_balance_for_rent_exemption(0);
+    let vault_lamports = fixt.svm.get_lamports(&vault_pda);
+    assert!(
+        vault_lamports >= session.locked_for_tasks + rent_exempt_minimum,
+        "seed {seed} step {step}: vault holds {vault_lamports} lamports, needs {} locked + {rent_exempt_minimum} rent",
+        session.locked_for_tasks
+    );
+}
+
+fn run_share_audit(seed: u64) {
+    let mut fixt = audit_fixture();
+    let mut rng = AuditRng::new(seed);
+
+    let mut participants = vec![
+        fixt.agent_owner.insecure_clone(),
+        fixt.contributor.insecure_clone(),
+    ];
+    for _ in 0..EXTRA_CONTRIBUTORS {
+        let contributor = Keypair::new();
+        fixt.svm
+            .airdrop(&contributor.pubkey(), 10 * LAMPORTS_PER_SOL)
+            .expect("Failed to fund contributor");
+        participants.push(contributor);
+    }
+
+    let network_config_pda = fixt.find_network_config_pda().0;
+    let (session_pda, _) = fixt.find_session_pda(&network_config_pda, SESSION_SLOT_ID);
+    let (vault_pda, _) = fixt.find_session_vault_pda(&session_pda);
+    let task_slot_id = fixt.find_session_task_slot_id(SESSION_SLOT_ID);
+    let public_node = fixt.public_node.insecure_clone();
+    let validator_node = fixt.validator_node.insecure_clone();
+
+    assert_share_invariants(&fixt, &participants, seed, 0);
+
+    for step in 1..=AUDIT_STEPS {
+        let audit_step = match rng.range(0, 3) {
+            0 => AuditStep::Contribute,
+            1 => AuditStep::Withdraw,
+            2 => AuditStep::ClaimTask,
+            _ => AuditStep::PayTask,
+        };
+
+        let result = match audit_step {
+            AuditStep::Contribute => {
+                let contributor = &participants[rng.pick(participants.len())];
+                let amount = rng.range(1_000, DEFAULT_CONTRIBUTION_AMOUNT);
+                Some(fixt.contribute_to_session(contributor, SESSION_SLOT_ID, amount))
+            }
+            AuditStep::Withdraw => {
+                let contributor = &participants[rng.pick(participants.len())];
+                match find_contribution(&fixt, contributor) {
+                    Some(contribution) if contribution.shares > 0 => {
+                        let shares_to_burn = rng.range(1, contribution.shares);
+                        Some(fixt.withdraw_from_session(
+                            contributor,
+                            SESSION_SLOT_ID,
+                            shares_to_burn,
+                        ))
+                    }
+                    _ => None,
+                }
+            }
+            AuditStep::ClaimTask => {
+                let session = fixt.get_session(&network_config_pda, SESSION_SLOT_ID);
+                let rent_exempt_minimum = fixt.svm.minimum_balance_for_rent_exemption(0);
+                let available = fixt
+                    .svm
+                    .get_lamports(&vault_pda)
+                    .saturating_sub(session.locked_for_tasks)
+                    .saturating_sub(rent_exempt_minimum);
+                let max_task_cost = rng.range(1, available.max(1));
+                Some(fixt.claim_task(
+                    &public_node,
+                    SESSION_SLOT_ID,
+                    task_slot_id,
+                    max_task_cost,
+                    10,
+                ))
+            }
+            AuditStep::PayTask => {
+                let task = fixt.get_task(&network_config_pda, task_slot_id);
+                if task.status != TaskStatus::Processing {
+                    None
+                } else {
+                    let result = fixt.submit_task_result(
+                        &public_node,
+                        SESSION_SLOT_ID,
+                        task_slot_id,
+                        format!("QmAuditInput{seed}x{step}"),
+                        format!("QmAuditOutput{seed}x{step}"),
+                        None,
+                        1,
+                    );
+                    assert_no_arithmetic_failure(seed, step, &result);
+                    assert_share_invariants(&fixt, &participants, seed, step);
+
+                    let payment_amount = rng.range(0, task.max_task_cost);
+                    Some(fixt.submit_public_task_validation(
+                        &validator_node,
+                        SESSION_SLOT_ID,
+                        task_slot_id,
+                        &public_node.pubkey(),
+                        payment_amount,
+                        true,
+                        false,
+                    ))
+                }
+            }
+        };
+
+        if let Some(result) = &result {
+            assert_no_arithmetic_failure(seed, step, result);
+        }
+        assert_share_invariants(&fixt, &participants, seed, step);
+        fixt.svm.expire_blockhash();
+    }
+}
+
+#[test]
+fn test_share_accounting_audit() {
+    match std::env::var("DAC_SHARE_AUDIT_SEED") {
+        Ok(seed) => run_share_audit(seed.parse().expect("DAC_SHARE_AUDIT_SEED must be a u64")),
+        Err(_) => (0..AUDIT_SEEDS).for_each(run_share_audit),
+    }
+}
@@ -3,6 +3,7 @@ use litesvm::{
     types::{TransactionMetadata, TransactionResult},
     LiteSVM,
 };
+use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_ed25519_program::new_ed25519_instruction_with_signature;
 use solana_sdk::{
     instruction::Instruction,
@@ -88,6 +89,57 @@ pub fn create_ed25519_instruction_with_signature(
     new_ed25519_instruction_with_signature(&message_data, &signature_bytes, &tee_pubkey_bytes)
 }
 
+/// Build one Ed25519 instruction carrying a signature per (message, signer) pair
+pub fn create_ed25519_instruction_with_signatures(entries: &[(&[u8], &Keypair)]) -> Instruction {
+    const HEADER_SIZE: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    let mut data = vec![entries.len() as u8, 0];
+    let mut payload = Vec::new();
+    let payload_start = HEADER_SIZE + OFFSETS_SIZE * entries.len();
+
+    for (message, key_pair) in entries {
+        let public_key_offset = (payload_start + payload.len()) as u16;
+        payload.extend_from_slice(key_pair.pubkey().as_ref());
+        let signature_offset = (payload_start + payload.len()) as u16;
+        payload.extend_from_slice(key_pair.sign_message(message).as_ref());
+        let message_data_offset = (payload_start + payload.len()) as u16;
+        payload.extend_from_slice(message);
+
+        for field in [
+            signature_offset,
+            CURRENT_INSTRUCTION,
+            public_key_offset,
+            CURRENT_INSTRUCTION,
+            message_data_offset,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    data.extend_from_slice(&payload);
+
+    Instruction {
+        program_id: solana_sdk_ids::ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Highest compute unit limit a transaction can request
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Prepend a compute unit limit request, for instructions that outgrow the default 200k
+/// units, such as initialize_network creating every pre-allocated task in one go
+pub fn with_compute_unit_limit(units: u32, instructions: &[Instruction]) -> Vec<Instruction> {
+    let mut limited = Vec::with_capacity(instructions.len() + 1);
+    limited.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+    limited.extend_from_slice(instructions);
+    limited
+}
+
 fn deploy_program_internal(svm: &mut LiteSVM, program_id: Pubkey, so_path: &str) -> Pubkey {
     svm.add_program_from_file(program_id, so_path)
         .expect("Failed to deploy program from file");